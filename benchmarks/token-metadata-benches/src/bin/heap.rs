@@ -0,0 +1,201 @@
+//! Heap bytes allocated by the decode and encode steps of `ReplaceAttributes`, measured on the
+//! host with a counting allocator.
+//!
+//! The on-chain allocator is a bump allocator that never frees within an instruction, so the
+//! bytes requested here are the heap the same steps consume on-chain (before alignment). Steps
+//! that only run on-chain (syscalls, CPIs, the event log line) are not included.
+//!
+//! "previous" replays the account read and write of the original program (99cde8a) on the same
+//! bytes: a streaming borsh decode straight from the account and a `borsh::to_vec` write.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use arch_program::{program_pack::Pack, pubkey::Pubkey};
+use arch_token_metadata::{
+    instruction::MetadataInstruction,
+    state::{
+        attributes_account_len, sort_attributes, AccountType, ContentHashAlgorithm, TokenMetadata,
+        TokenMetadataAttributes, CURRENT_ACCOUNT_VERSION, MAX_ATTRIBUTES, MAX_KEY_LENGTH,
+        MAX_VALUE_LENGTH,
+    },
+};
+use borsh::BorshDeserialize;
+
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    // A bump allocator cannot grow in place: every realloc is a fresh allocation
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Run `f` and return its result with the bytes it allocated
+fn allocated<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let out = f();
+    (out, ALLOCATED.load(Ordering::Relaxed) - before)
+}
+
+fn main() -> anyhow::Result<()> {
+    // Same payloads as the `replace_attributes*` CU benchmarks, plus the largest account allowed
+    let scenarios: [(&str, Vec<(String, String)>); 3] = [
+        (
+            "replace_attributes",
+            vec![("a".into(), "2".into()), ("b".into(), "3".into())],
+        ),
+        (
+            "replace_attributes_max_entries",
+            (0..MAX_ATTRIBUTES)
+                .map(|i| (format!("k{i}"), format!("v{i}")))
+                .collect(),
+        ),
+        (
+            "replace_attributes_max_size",
+            (0..MAX_ATTRIBUTES)
+                .map(|i| {
+                    (
+                        format!("{i:0>width$}", width = MAX_KEY_LENGTH),
+                        "v".repeat(MAX_VALUE_LENGTH),
+                    )
+                })
+                .collect(),
+        ),
+    ];
+
+    let metadata_account = metadata_account();
+    let mut report = serde_json::Map::new();
+    for (name, data) in scenarios {
+        report.insert(name.into(), measure(&metadata_account, data)?);
+    }
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&serde_json::Value::Object(report))?
+    );
+    Ok(())
+}
+
+/// Replace `data` with itself: the account keeps its size, so only decode and encode allocate
+fn measure(
+    metadata_account: &[u8],
+    data: Vec<(String, String)>,
+) -> anyhow::Result<serde_json::Value> {
+    let attrs = TokenMetadataAttributes {
+        account_type: AccountType::Attributes,
+        version: CURRENT_ACCOUNT_VERSION,
+        is_initialized: true,
+        mint: Pubkey::from_slice(&[1; 32]),
+        data: data.clone(),
+        revision: 1,
+        last_updated_slot: 1,
+        bump: Some(255),
+    };
+    let mut account = vec![0u8; attributes_account_len(&attrs.data)];
+    attrs
+        .try_pack_into_slice(&mut account)
+        .map_err(|e| anyhow::anyhow!("pack attributes: {e:?}"))?;
+    let ix_data = MetadataInstruction::ReplaceAttributes {
+        data,
+        expected_revision: None,
+    }
+    .pack();
+
+    let (instruction, instruction_bytes) = allocated(|| MetadataInstruction::unpack(&ix_data));
+    let MetadataInstruction::ReplaceAttributes { mut data, .. } =
+        instruction.map_err(|e| anyhow::anyhow!("unpack instruction: {e:?}"))?
+    else {
+        anyhow::bail!("unexpected instruction");
+    };
+    let (_, metadata_bytes) = allocated(|| TokenMetadata::unpack(metadata_account));
+    let (_, previous_metadata_bytes) =
+        allocated(|| previous_unpack::<TokenMetadata>(metadata_account));
+    let (loaded, attributes_bytes) =
+        allocated(|| TokenMetadataAttributes::unpack_from_slice(&account));
+    let mut loaded = loaded.map_err(|e| anyhow::anyhow!("unpack attributes: {e:?}"))?;
+    let (_, sort_bytes) = allocated(|| sort_attributes(&mut data));
+    loaded.data = data;
+    let (_, write_bytes) = allocated(|| loaded.try_pack_into_slice(&mut account));
+
+    let (_, previous_attributes_bytes) =
+        allocated(|| previous_unpack::<TokenMetadataAttributes>(&account));
+    let (_, previous_write_bytes) = allocated(|| previous_pack(&loaded, &mut account));
+
+    let shared = instruction_bytes + sort_bytes;
+    Ok(serde_json::json!({
+        "account_len": account.len(),
+        "instruction_decode": instruction_bytes,
+        "sort": sort_bytes,
+        "metadata_decode": { "previous": previous_metadata_bytes, "current": metadata_bytes },
+        "attributes_decode": { "previous": previous_attributes_bytes, "current": attributes_bytes },
+        "attributes_write": { "previous": previous_write_bytes, "current": write_bytes },
+        "total": {
+            "previous": shared
+                + previous_metadata_bytes
+                + previous_attributes_bytes
+                + previous_write_bytes,
+            "current": shared + metadata_bytes + attributes_bytes + write_bytes,
+        },
+    }))
+}
+
+/// `Pack::unpack_from_slice` as of 99cde8a: borsh streamed from the account, trailing bytes ignored
+fn previous_unpack<T: BorshDeserialize>(src: &[u8]) -> Option<T> {
+    let mut slice_ref: &[u8] = src;
+    T::deserialize(&mut slice_ref).ok()
+}
+
+/// `Pack::pack_into_slice` as of 99cde8a: encode into a fresh buffer, then copy and zero-fill
+fn previous_pack(attrs: &TokenMetadataAttributes, dst: &mut [u8]) {
+    let data = borsh::to_vec(attrs).unwrap();
+    dst[..data.len()].copy_from_slice(&data);
+    for b in &mut dst[data.len()..] {
+        *b = 0;
+    }
+}
+
+/// A metadata account as written by `setup_metadata_once` in the CU benchmarks
+fn metadata_account() -> Vec<u8> {
+    let metadata = TokenMetadata {
+        account_type: AccountType::Metadata,
+        version: CURRENT_ACCOUNT_VERSION,
+        is_initialized: true,
+        mint: Pubkey::from_slice(&[1; 32]),
+        name: "Bench Token".into(),
+        symbol: "BT".into(),
+        image: "https://example.com/i.png".into(),
+        description: "bench".into(),
+        update_authority: Some(Pubkey::from_slice(&[2; 32])),
+        pending_authority: None,
+        locked_fields: 0,
+        collection: None,
+        uri: String::new(),
+        content_hash: [0; 32],
+        content_hash_algorithm: ContentHashAlgorithm::None,
+        revision: 1,
+        last_updated_slot: 1,
+        bump: Some(255),
+        delegate_count: 0,
+        extension_count: 0,
+    };
+    let mut account = vec![0u8; TokenMetadata::LEN];
+    metadata.pack_into_slice(&mut account);
+    account
+}
@@ -51,13 +51,47 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("bench_create_metadata_and_attributes")?;
 
+    let r2_combined = bench_create_metadata_with_attributes(
+        &rpc,
+        &client,
+        payer,
+        &payer_priv_hex,
+        warmup_iters,
+        iters,
+    )
+    .await
+    .context("bench_create_metadata_with_attributes")?;
+
     let r3 = bench_update_metadata(&rpc, &client, payer, &payer_priv_hex, warmup_iters, iters)
         .await
         .context("bench_update_metadata")?;
 
-    let r4 = bench_replace_attributes(&rpc, &client, payer, &payer_priv_hex, warmup_iters, iters)
-        .await
-        .context("bench_replace_attributes")?;
+    let r4 = bench_replace_attributes(
+        &rpc,
+        &client,
+        payer,
+        &payer_priv_hex,
+        warmup_iters,
+        iters,
+        vec![("a".into(), "2".into()), ("b".into(), "3".into())],
+    )
+    .await
+    .context("bench_replace_attributes")?;
+
+    // A full 32-entry replace: the account write is where the removed heap buffer was largest
+    let r4_max = bench_replace_attributes(
+        &rpc,
+        &client,
+        payer,
+        &payer_priv_hex,
+        warmup_iters,
+        iters,
+        (0..arch_token_metadata::state::MAX_ATTRIBUTES)
+            .map(|i| (format!("k{i}"), format!("v{i}")))
+            .collect(),
+    )
+    .await
+    .context("bench_replace_attributes_max_entries")?;
 
     let r5 = bench_transfer_authority(&rpc, &client, payer, &payer_priv_hex, warmup_iters, iters)
         .await
@@ -67,6 +101,10 @@ async fn main() -> anyhow::Result<()> {
         .await
         .context("bench_make_immutable")?;
 
+    let r7 = bench_touch(&rpc, &client, payer, &payer_priv_hex, warmup_iters, iters)
+        .await
+        .context("bench_touch")?;
+
     // Full-flow benchmarks (builders including mint + init + metadata ops)
     let full_flows = bench_full_flows(&rpc, &client, payer, &payer_priv_hex, warmup_iters, iters)
         .await
@@ -75,13 +113,30 @@ async fn main() -> anyhow::Result<()> {
     let report = serde_json::json!({
         "create_metadata": r1,
         "create_metadata_and_attributes": r2,
+        "create_metadata_with_attributes": r2_combined,
         "update_metadata": r3,
         "replace_attributes": r4,
+        "replace_attributes_max_entries": r4_max,
         "transfer_authority": r5,
         "make_immutable": r6,
+        "touch": r7,
         "full_flows": full_flows,
     });
 
+    // Optional previous report: add the per-benchmark median change next to the new figures
+    let report = match std::env::var("BASELINE_REPORT") {
+        Ok(path) => {
+            let baseline: serde_json::Value = serde_json::from_str(
+                &std::fs::read_to_string(&path).with_context(|| format!("read {path}"))?,
+            )
+            .with_context(|| format!("parse {path}"))?;
+            let mut report = report;
+            report["vs_baseline"] = median_changes(&report, &baseline);
+            report
+        }
+        Err(_) => report,
+    };
+
     println!("{}", serde_json::to_string_pretty(&report)?);
 
     Ok(())
@@ -150,6 +205,7 @@ async fn one_create_metadata_tx(
         image: "https://example.com/i.png".into(),
         description: "bench".into(),
         immutable: false,
+        uri: None,
     };
 
     let create_mint_ix = client.create_mint_account_ix(payer, mint);
@@ -487,12 +543,14 @@ async fn one_create_md_and_attrs_tx(
         image: "https://example.com/i.png".into(),
         description: "bench".into(),
         immutable: false,
+        uri: None,
     })?;
     let create_attrs = client.create_attributes_ix(tmsdk::CreateAttributesParams {
         payer,
         mint,
         update_authority: payer,
         data: vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())],
+        expected_revision: None,
     })?;
     let create_mint = client.create_mint_account_ix(payer, mint);
     let init_mint = client.initialize_mint2_ix(mint, payer, None, 9)?;
@@ -512,6 +570,78 @@ async fn one_create_md_and_attrs_tx(
     Ok(cu)
 }
 
+// Same accounts as `create_metadata_and_attributes`, written by the single combined instruction
+async fn bench_create_metadata_with_attributes(
+    rpc: &AsyncArchRpcClient,
+    client: &tmsdk::TokenMetadataClient,
+    payer: Pubkey,
+    payer_priv_hex: &str,
+    warmup_iters: usize,
+    iters: usize,
+) -> anyhow::Result<serde_json::Value> {
+    let mut cu_values: Vec<u64> = Vec::with_capacity(iters);
+    for _ in 0..warmup_iters {
+        let _ = one_create_md_with_attrs_tx(rpc, client, payer, payer_priv_hex).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    for _ in 0..iters {
+        let cu = one_create_md_with_attrs_tx(rpc, client, payer, payer_priv_hex).await?;
+        cu_values.push(cu);
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    cu_values.sort_unstable();
+    let median = cu_values[cu_values.len() / 2];
+    let p90_idx = ((cu_values.len() as f64 * 0.9).floor() as usize).min(cu_values.len() - 1);
+    let p90 = cu_values[p90_idx];
+    Ok(
+        serde_json::json!({"iters": iters, "warmup_iters": warmup_iters, "median_cu": median, "p90_cu": p90, "all": cu_values}),
+    )
+}
+
+async fn one_create_md_with_attrs_tx(
+    rpc: &AsyncArchRpcClient,
+    client: &tmsdk::TokenMetadataClient,
+    payer: Pubkey,
+    payer_priv_hex: &str,
+) -> anyhow::Result<u64> {
+    use bitcoin::{key::Keypair, Network};
+    let (sk, mint, _) = arch_sdk::generate_new_keypair(Network::Regtest);
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let mint_kp = Keypair::from_secret_key(&secp, &sk.secret_key());
+    let payer_kp = keypair_from_priv_hex(payer_priv_hex)?;
+
+    let create_md_with_attrs = client.create_metadata_with_attributes_ix(
+        tmsdk::CreateMetadataParams {
+            payer,
+            mint,
+            mint_or_freeze_authority: payer,
+            name: "Bench Token".into(),
+            symbol: "BT".into(),
+            image: "https://example.com/i.png".into(),
+            description: "bench".into(),
+            immutable: false,
+            uri: None,
+        },
+        vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())],
+    )?;
+    let create_mint = client.create_mint_account_ix(payer, mint);
+    let init_mint = client.initialize_mint2_ix(mint, payer, None, 9)?;
+    let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+    let msg = ArchMessage::new(
+        &[create_mint, init_mint, create_md_with_attrs],
+        Some(payer),
+        bh,
+    );
+    let tx = arch_sdk::build_and_sign_transaction(msg, vec![payer_kp, mint_kp], Network::Regtest)?;
+    let txid = rpc.send_transaction(tx).await?;
+    let processed = rpc.wait_for_processed_transaction(&txid).await?;
+    let cu = processed
+        .compute_units_consumed()
+        .and_then(|s| s.parse::<u64>().ok())
+        .context("compute units not found in logs")?;
+    Ok(cu)
+}
+
 async fn bench_update_metadata(
     rpc: &AsyncArchRpcClient,
     client: &tmsdk::TokenMetadataClient,
@@ -562,14 +692,11 @@ async fn setup_metadata_once(
         image: "https://example.com/i.png".into(),
         description: "bench".into(),
         immutable: false,
+        uri: None,
     })?;
     let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
     let msg = ArchMessage::new(&[create_mint, init_mint, create_md], Some(payer), bh);
-    let tx = arch_sdk::build_and_sign_transaction(
-        msg,
-        vec![payer_kp, mint_kp.clone()],
-        Network::Regtest,
-    )?;
+    let tx = arch_sdk::build_and_sign_transaction(msg, vec![payer_kp, mint_kp], Network::Regtest)?;
     let txid = rpc.send_transaction(tx).await?;
     let _ = rpc.wait_for_processed_transaction(&txid).await?;
     Ok((mint_kp, mint))
@@ -591,6 +718,8 @@ async fn one_update_metadata_tx(
         symbol: None,
         image: None,
         description: None,
+        uri: None,
+        expected_revision: None,
     })?;
     let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
     let msg = ArchMessage::new(&[update], Some(payer), bh);
@@ -611,14 +740,16 @@ async fn bench_replace_attributes(
     payer_priv_hex: &str,
     warmup_iters: usize,
     iters: usize,
+    data: Vec<(String, String)>,
 ) -> anyhow::Result<serde_json::Value> {
     let mut cu_values: Vec<u64> = Vec::with_capacity(iters);
     for _ in 0..warmup_iters {
-        let _ = one_replace_attributes_tx(rpc, client, payer, payer_priv_hex).await;
+        let _ = one_replace_attributes_tx(rpc, client, payer, payer_priv_hex, data.clone()).await;
         tokio::time::sleep(Duration::from_millis(50)).await;
     }
     for _ in 0..iters {
-        let cu = one_replace_attributes_tx(rpc, client, payer, payer_priv_hex).await?;
+        let cu =
+            one_replace_attributes_tx(rpc, client, payer, payer_priv_hex, data.clone()).await?;
         cu_values.push(cu);
         tokio::time::sleep(Duration::from_millis(25)).await;
     }
@@ -636,6 +767,7 @@ async fn one_replace_attributes_tx(
     client: &tmsdk::TokenMetadataClient,
     payer: Pubkey,
     payer_priv_hex: &str,
+    data: Vec<(String, String)>,
 ) -> anyhow::Result<u64> {
     use bitcoin::Network;
     // Setup metadata + attributes first
@@ -647,14 +779,12 @@ async fn one_replace_attributes_tx(
         mint,
         update_authority: payer,
         data: vec![("a".into(), "1".into())],
+        expected_revision: None,
     })?;
     let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
     let msg1 = ArchMessage::new(&[create_attrs], Some(payer), bh);
-    let tx1 = arch_sdk::build_and_sign_transaction(
-        msg1,
-        vec![payer_kp.clone(), mint_kp.clone()],
-        Network::Regtest,
-    )?;
+    let tx1 =
+        arch_sdk::build_and_sign_transaction(msg1, vec![payer_kp, mint_kp], Network::Regtest)?;
     let txid1 = rpc.send_transaction(tx1).await?;
     let _ = rpc.wait_for_processed_transaction(&txid1).await?;
 
@@ -662,7 +792,9 @@ async fn one_replace_attributes_tx(
     let replace = client.replace_attributes_ix(tmsdk::ReplaceAttributesParams {
         mint,
         update_authority: payer,
-        data: vec![("a".into(), "2".into()), ("b".into(), "3".into())],
+        payer,
+        data,
+        expected_revision: None,
     })?;
     let bh2 = Hash::from_str(&rpc.get_best_block_hash().await?)?;
     let msg2 = ArchMessage::new(&[replace], Some(payer), bh2);
@@ -723,6 +855,7 @@ async fn one_transfer_authority_tx(
         mint,
         current_update_authority: payer,
         new_authority: new_auth,
+        expected_revision: None,
     })?;
     let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
     let msg = ArchMessage::new(&[transfer], Some(payer), bh);
@@ -775,6 +908,7 @@ async fn one_make_immutable_tx(
     let make_imm = client.make_immutable_ix(tmsdk::MakeImmutableParams {
         mint,
         current_update_authority: payer,
+        expected_revision: None,
     })?;
     let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
     let msg = ArchMessage::new(&[make_imm], Some(payer), bh);
@@ -787,3 +921,96 @@ async fn one_make_immutable_tx(
         .context("compute units not found in logs")?;
     Ok(cu)
 }
+
+async fn bench_touch(
+    rpc: &AsyncArchRpcClient,
+    client: &tmsdk::TokenMetadataClient,
+    payer: Pubkey,
+    payer_priv_hex: &str,
+    warmup_iters: usize,
+    iters: usize,
+) -> anyhow::Result<serde_json::Value> {
+    let mut cu_values: Vec<u64> = Vec::with_capacity(iters);
+    for _ in 0..warmup_iters {
+        let _ = one_touch_tx(rpc, client, payer, payer_priv_hex).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    for _ in 0..iters {
+        let cu = one_touch_tx(rpc, client, payer, payer_priv_hex).await?;
+        cu_values.push(cu);
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    cu_values.sort_unstable();
+    let median = cu_values[cu_values.len() / 2];
+    let p90_idx = ((cu_values.len() as f64 * 0.9).floor() as usize).min(cu_values.len() - 1);
+    let p90 = cu_values[p90_idx];
+    Ok(
+        serde_json::json!({"iters": iters, "warmup_iters": warmup_iters, "median_cu": median, "p90_cu": p90, "all": cu_values}),
+    )
+}
+
+/// Measures a repeat touch: the first one (not measured) creates the PDA and stores its bump
+async fn one_touch_tx(
+    rpc: &AsyncArchRpcClient,
+    client: &tmsdk::TokenMetadataClient,
+    payer: Pubkey,
+    payer_priv_hex: &str,
+) -> anyhow::Result<u64> {
+    use bitcoin::Network;
+    let (mint_kp, mint) = setup_metadata_once(rpc, client, payer, payer_priv_hex).await?;
+    let payer_kp = keypair_from_priv_hex(payer_priv_hex)?;
+    let mut cu = 0;
+    for _ in 0..2 {
+        let touch = client.touch_ix(tmsdk::TouchParams {
+            payer,
+            mint,
+            update_authority: payer,
+            expected_revision: None,
+        })?;
+        let bh = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+        let msg = ArchMessage::new(&[touch], Some(payer), bh);
+        let tx =
+            arch_sdk::build_and_sign_transaction(msg, vec![payer_kp, mint_kp], Network::Regtest)?;
+        let txid = rpc.send_transaction(tx).await?;
+        let processed = rpc.wait_for_processed_transaction(&txid).await?;
+        cu = processed
+            .compute_units_consumed()
+            .and_then(|s| s.parse::<u64>().ok())
+            .context("compute units not found in logs")?;
+    }
+    Ok(cu)
+}
+
+/// `{ before, after, saved }` median CU for every benchmark present in both reports
+fn median_changes(report: &serde_json::Value, baseline: &serde_json::Value) -> serde_json::Value {
+    let mut changes = serde_json::Map::new();
+    let (Some(report), Some(baseline)) = (report.as_object(), baseline.as_object()) else {
+        return serde_json::Value::Object(changes);
+    };
+    for (name, entry) in report {
+        let Some(before) = baseline.get(name) else {
+            continue;
+        };
+        match (entry["median_cu"].as_u64(), before["median_cu"].as_u64()) {
+            (Some(after), Some(before)) => {
+                changes.insert(
+                    name.clone(),
+                    serde_json::json!({
+                        "before": before,
+                        "after": after,
+                        "saved": before as i64 - after as i64,
+                    }),
+                );
+            }
+            // Nested groups such as `full_flows`
+            _ if entry.is_object() => {
+                let nested = median_changes(entry, before);
+                if nested.as_object().is_some_and(|m| !m.is_empty()) {
+                    changes.insert(name.clone(), nested);
+                }
+            }
+            _ => {}
+        }
+    }
+    serde_json::Value::Object(changes)
+}
@@ -57,6 +57,7 @@ async fn main() -> anyhow::Result<()> {
         image: "https://example.com/i.png".into(),
         description: "demo".into(),
         immutable: false,
+        uri: None,
     };
 
     let create_attrs_params = CreateAttributesParams {
@@ -67,6 +68,7 @@ async fn main() -> anyhow::Result<()> {
             ("rarity".into(), "common".into()),
             ("series".into(), "alpha".into()),
         ],
+        expected_revision: None,
     };
 
     println!("Building instructions: [compute_budget?], create_mint_account, initialize_mint2(decimals=9), create_metadata, create_attributes]");
@@ -3,12 +3,16 @@ use arch_program::{
     rent::minimum_rent, system_instruction,
 };
 use arch_testing::TestContext;
-use arch_token_metadata::find_metadata_pda_with_program;
 use arch_token_metadata::instruction::MetadataInstruction;
+use arch_token_metadata::{find_attributes_pda_with_program, find_metadata_pda_with_program};
 use bitcoin::key::Keypair;
 
 pub const ARCH_TOKEN_METADATA_ELF: &[u8] = arch_token_metadata_elf::ARCH_TOKEN_METADATA_ELF;
 
+/// The v0 program as published, which writes headerless legacy accounts
+pub const LEGACY_ARCH_TOKEN_METADATA_ELF: &[u8] =
+    arch_token_metadata_elf_v0::ARCH_TOKEN_METADATA_ELF;
+
 pub async fn deploy_token_metadata_program(ctx: &TestContext) -> anyhow::Result<Pubkey> {
     let (deployer_kp, _deployer_pk, _) = ctx.generate_new_keypair();
     ctx.fund_keypair_with_faucet(&deployer_kp).await?;
@@ -29,7 +33,7 @@ pub fn create_and_init_mint_instructions(
     let create_mint_ix = system_instruction::create_account(
         &payer_pk,
         &mint_pk,
-        minimum_rent(apl_token::state::Mint::LEN as usize),
+        minimum_rent(apl_token::state::Mint::LEN),
         apl_token::state::Mint::LEN as u64,
         &apl_token::id(),
     );
@@ -65,7 +69,7 @@ pub async fn create_and_init_mint(
         recent.parse()?,
     );
     let tx = ctx
-        .build_and_sign_transaction(msg, vec![payer_kp.clone(), mint_kp.clone()])
+        .build_and_sign_transaction(msg, vec![*payer_kp, *mint_kp])
         .await?;
     let txid = ctx.send_transaction(tx).await?;
     let res = ctx.wait_for_transaction(&txid).await?;
@@ -76,6 +80,7 @@ pub async fn create_and_init_mint(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn build_create_metadata_ix(
     program_id: Pubkey,
     payer_pk: Pubkey,
@@ -95,6 +100,7 @@ pub async fn build_create_metadata_ix(
         image: image.to_string(),
         description: description.to_string(),
         immutable,
+        uri: None,
     }
     .pack();
 
@@ -114,3 +120,97 @@ pub async fn build_create_metadata_ix(
 
     (instruction, metadata_pda)
 }
+
+/// An update-authority instruction on `mint_pk`'s metadata, with accounts
+/// `[metadata (writable), authority (signer)]`
+pub fn build_metadata_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    instruction: MetadataInstruction,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+        ],
+        data: instruction.pack(),
+    }
+}
+
+/// `CreateAttributes` for `mint_pk`, authorized by `update_authority_pk`
+pub fn build_create_attributes_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+    update_authority_pk: Pubkey,
+    data: Vec<(String, String)>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new_readonly(mint_pk, false),
+            AccountMeta::new(attributes_pda, false),
+            AccountMeta::new_readonly(update_authority_pk, true),
+            AccountMeta::new_readonly(metadata_pda, false),
+        ],
+        data: MetadataInstruction::CreateAttributes {
+            data,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+pub async fn send_instructions(
+    ctx: &TestContext,
+    payer_pk: Pubkey,
+    instructions: &[Instruction],
+    signers: Vec<Keypair>,
+) -> anyhow::Result<arch_sdk::Status> {
+    let (status, _logs) = send_instructions_with_logs(ctx, payer_pk, instructions, signers).await?;
+    Ok(status)
+}
+
+/// Assert that `pubkey` was closed: handed back to the system program with no data or lamports
+pub async fn assert_closed(ctx: &TestContext, pubkey: Pubkey) -> anyhow::Result<()> {
+    let acct = ctx.read_account_info(pubkey).await?;
+    assert_eq!(acct.owner, Pubkey::system_program(), "owner of {pubkey:?}");
+    assert!(acct.data.is_empty(), "data of {pubkey:?}");
+    assert_eq!(acct.lamports, 0, "lamports of {pubkey:?}");
+    Ok(())
+}
+
+/// Assert that `pubkey` holds at least the rent-exempt minimum for its current size
+pub async fn assert_rent_exempt(ctx: &TestContext, pubkey: Pubkey) -> anyhow::Result<()> {
+    let acct = ctx.read_account_info(pubkey).await?;
+    assert!(
+        acct.lamports >= minimum_rent(acct.data.len()),
+        "{pubkey:?} holds {} lamports for {} bytes",
+        acct.lamports,
+        acct.data.len()
+    );
+    Ok(())
+}
+
+/// Like `send_instructions`, but also returns the transaction logs
+pub async fn send_instructions_with_logs(
+    ctx: &TestContext,
+    payer_pk: Pubkey,
+    instructions: &[Instruction],
+    signers: Vec<Keypair>,
+) -> anyhow::Result<(arch_sdk::Status, Vec<String>)> {
+    let recent = ctx.get_recent_blockhash().await?;
+    let msg =
+        arch_program::sanitized::ArchMessage::new(instructions, Some(payer_pk), recent.parse()?);
+    let tx = ctx.build_and_sign_transaction(msg, signers).await?;
+    let txid = ctx.send_transaction(tx).await?;
+    let res = ctx.wait_for_transaction(&txid).await?;
+    Ok((res.status, res.logs))
+}
@@ -0,0 +1,389 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    rent::minimum_rent,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_attributes_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{
+        attributes_account_len, AccountType, TokenMetadata, TokenMetadataAttributes,
+        CURRENT_ACCOUNT_VERSION, LEGACY_V0_INITIALIZED,
+    },
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions, ARCH_TOKEN_METADATA_ELF, LEGACY_ARCH_TOKEN_METADATA_ELF,
+};
+use serial_test::serial;
+
+fn mutating_instructions(new_authority: Pubkey) -> Vec<MetadataInstruction> {
+    vec![
+        MetadataInstruction::UpdateMetadata {
+            name: Some("Hijacked".into()),
+            symbol: None,
+            image: None,
+            description: None,
+            uri: None,
+            expected_revision: None,
+        },
+        MetadataInstruction::TransferAuthority {
+            new_authority,
+            expected_revision: None,
+        },
+        MetadataInstruction::MakeImmutable {
+            expected_revision: None,
+        },
+    ]
+}
+
+// A second deployment of the same program produces accounts that deserialize as
+// TokenMetadata (same mint, same authority) but are owned by a different program.
+#[tokio::test]
+#[serial]
+async fn mutating_instructions_reject_foreign_owned_metadata() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let foreign_program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, spoofed_pda) = build_create_metadata_ix(
+            foreign_program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Spoof",
+            "SPF",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Sanity: the spoofed account decodes cleanly and names payer as authority
+        let spoofed = ctx.read_account_info(spoofed_pda).await?;
+        assert_eq!(spoofed.owner, foreign_program_id);
+        let spoofed_md = TokenMetadata::unpack(&spoofed.data).unwrap();
+        assert_eq!(spoofed_md.mint, mint_pk);
+        assert_eq!(spoofed_md.update_authority, Some(payer_pk));
+
+        for instruction in mutating_instructions(payer_pk) {
+            let ix = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(spoofed_pda, false),
+                    AccountMeta::new_readonly(payer_pk, true),
+                ],
+                data: instruction.pack(),
+            };
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(
+                matches!(status, Status::Failed(_)),
+                "{:?} accepted a foreign-owned metadata account",
+                instruction
+            );
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+// Program-owned accounts that are not the canonical metadata PDA (here: the
+// attributes PDA) must not be accepted as metadata.
+#[tokio::test]
+#[serial]
+async fn mutating_instructions_reject_non_canonical_metadata_pda() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let (attrs_pda, _ba) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let create_attrs_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(mint_pk, false),
+                AccountMeta::new(attrs_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+            ],
+            data: MetadataInstruction::CreateAttributes {
+                data: vec![],
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (expected_md_pda, _bmd) = find_metadata_pda_with_program(&program_id, &mint_pk);
+        assert_eq!(expected_md_pda, metadata_pda);
+        assert_eq!(ctx.read_account_info(attrs_pda).await?.owner, program_id);
+
+        for instruction in mutating_instructions(payer_pk) {
+            let ix = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(attrs_pda, false),
+                    AccountMeta::new_readonly(payer_pk, true),
+                ],
+                data: instruction.pack(),
+            };
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(
+                matches!(status, Status::Failed(_)),
+                "{:?} accepted a non-canonical metadata account",
+                instruction
+            );
+        }
+
+        // The canonical metadata account is untouched and still mutable
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.name, "Name");
+        assert_eq!(md.update_authority, Some(payer_pk));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn migrate_account_current_layout_is_noop() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let before = ctx.read_account_info(metadata_pda).await?;
+        assert_eq!(before.data[0], AccountType::Metadata as u8);
+        assert_eq!(before.data[1], CURRENT_ACCOUNT_VERSION);
+
+        let migrate_ix = |account: Pubkey| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(account, false),
+            ],
+            data: MetadataInstruction::MigrateAccount.pack(),
+        };
+
+        let status =
+            send_instructions(&ctx, payer_pk, &[migrate_ix(metadata_pda)], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let after = ctx.read_account_info(metadata_pda).await?;
+        assert_eq!(before.data, after.data);
+
+        // Accounts not owned by the program cannot be migrated
+        let status =
+            send_instructions(&ctx, payer_pk, &[migrate_ix(mint_pk)], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        Ok(())
+    })
+    .await
+}
+
+// The published v0 program writes headerless accounts at its own (smaller) maximum sizes;
+// after the program is upgraded in place, MigrateAccount must bring both into the current
+// layout.
+#[tokio::test]
+#[serial]
+async fn migrate_account_upgrades_legacy_accounts() {
+    TestRunner::run(|ctx| async move {
+        let (deployer_kp, _deployer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&deployer_kp).await?;
+        let (program_kp, program_id, _) = ctx.generate_new_keypair();
+        ctx.deploy_program(program_kp, deployer_kp, LEGACY_ARCH_TOKEN_METADATA_ELF)
+            .await?;
+
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+        let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+        let (attrs_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+
+        // v0 encodings: CreateMetadata (variant 0) and CreateAttributes (variant 2)
+        let create_md_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(mint_pk, false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+            ],
+            data: borsh::to_vec(&(0u8, "Name", "SYM", "https://i", "d", false))?,
+        };
+        let create_attrs_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(mint_pk, false),
+                AccountMeta::new(attrs_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+            ],
+            data: borsh::to_vec(&(2u8, vec![("b", "2"), ("a", "1")]))?,
+        };
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let status = send_instructions(&ctx, payer_pk, &[create_attrs_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let legacy_md = ctx.read_account_info(metadata_pda).await?;
+        assert_eq!(legacy_md.data[0], LEGACY_V0_INITIALIZED);
+        assert_eq!(legacy_md.data.len(), 1 + 32 + 260 + 20 + 516 + 516 + 33);
+        let legacy_attrs = ctx.read_account_info(attrs_pda).await?;
+        assert_eq!(legacy_attrs.data[0], LEGACY_V0_INITIALIZED);
+
+        // Upgrade the program in place; the legacy accounts are unreadable until migrated
+        ctx.deploy_program(program_kp, deployer_kp, ARCH_TOKEN_METADATA_ELF)
+            .await?;
+        assert!(TokenMetadata::unpack_from_slice(&legacy_md.data).is_err());
+
+        let migrate_ix = |migrator: Pubkey, account: Pubkey| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(migrator, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(account, false),
+            ],
+            data: MetadataInstruction::MigrateAccount.pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[migrate_ix(payer_pk, metadata_pda)],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // A migrator other than the fee payer, so its balance moves only by the refund
+        let (migrator_kp, migrator_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&migrator_kp).await?;
+        let migrator_lamports = ctx.read_account_info(migrator_pk).await?.lamports;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[migrate_ix(migrator_pk, attrs_pda)],
+            vec![payer_kp, migrator_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let acct = ctx.read_account_info(metadata_pda).await?;
+        assert_eq!(acct.data.len(), TokenMetadata::LEN);
+        assert_eq!(acct.data[0], AccountType::Metadata as u8);
+        assert_eq!(acct.data[1], CURRENT_ACCOUNT_VERSION);
+        let md = TokenMetadata::unpack(&acct.data).unwrap();
+        assert_eq!(md.mint, mint_pk);
+        assert_eq!(md.name, "Name");
+        assert_eq!(md.symbol, "SYM");
+        assert_eq!(md.update_authority, Some(payer_pk));
+        assert_eq!(md.pending_authority, None);
+
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        let attrs = TokenMetadataAttributes::unpack_from_slice(&acct.data).unwrap();
+        assert_eq!(acct.data.len(), attributes_account_len(&attrs.data));
+        assert_eq!(
+            attrs.data,
+            vec![("a".to_string(), "1".to_string()), ("b".into(), "2".into())]
+        );
+        // The v0 account was allocated at the maximum size; shrinking refunds the excess rent
+        assert_eq!(acct.lamports, minimum_rent(acct.data.len()));
+        assert_eq!(
+            ctx.read_account_info(migrator_pk).await?.lamports,
+            migrator_lamports + legacy_attrs.lamports - acct.lamports
+        );
+
+        // The migrated metadata accepts writes again
+        let update_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+            ],
+            data: MetadataInstruction::UpdateMetadata {
+                name: Some("Renamed".into()),
+                symbol: None,
+                image: None,
+                description: None,
+                uri: None,
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(&ctx, payer_pk, &[update_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.name, "Renamed");
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,520 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    rent::minimum_rent,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_attributes_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{attributes_account_len, TokenMetadataAttributes},
+};
+use arch_token_metadata_tests::{
+    build_create_attributes_ix, build_create_metadata_ix, create_and_init_mint,
+    deploy_token_metadata_program, send_instructions,
+};
+use serial_test::serial;
+
+fn patch_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    instruction: MetadataInstruction,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+    let mut accounts = vec![
+        AccountMeta::new(attributes_pda, false),
+        AccountMeta::new(authority_pk, true),
+        AccountMeta::new_readonly(metadata_pda, false),
+    ];
+    // Removal never grows the account, so it takes no payer
+    if !matches!(instruction, MetadataInstruction::RemoveAttributes { .. }) {
+        accounts.push(AccountMeta::new(authority_pk, true));
+        accounts.push(AccountMeta::new_readonly(Pubkey::system_program(), false));
+    }
+    Instruction {
+        program_id,
+        accounts,
+        data: instruction.pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn upsert_and_remove_attributes_patch_in_place() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("a".into(), "1".into()), ("b".into(), "2".into())],
+        );
+        let upsert_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("b".into(), "3".into()), ("c".into(), "4".into())],
+                expected_revision: None,
+            },
+        );
+        let remove_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::RemoveAttributes {
+                keys: vec!["a".into(), "missing".into()],
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix, upsert_ix, remove_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (attrs_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        let attrs = TokenMetadataAttributes::unpack_from_slice(&acct.data).unwrap();
+        assert_eq!(
+            attrs.data,
+            vec![("b".to_string(), "3".to_string()), ("c".into(), "4".into())]
+        );
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn attributes_account_is_resized_to_fit() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("a".into(), "1".into())],
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (attrs_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let assert_right_sized = |data: &[u8], lamports: u64| {
+            let attrs = TokenMetadataAttributes::unpack_from_slice(data).unwrap();
+            let len = attributes_account_len(&attrs.data);
+            assert_eq!(data.len(), len);
+            assert_eq!(lamports, minimum_rent(len));
+            attrs
+        };
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        let initial_lamports = acct.lamports;
+        assert_right_sized(&acct.data, acct.lamports);
+
+        // Growing is funded by the payer
+        let upsert_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("b".into(), "x".repeat(200))],
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[upsert_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        let attrs = assert_right_sized(&acct.data, acct.lamports);
+        assert_eq!(attrs.data.len(), 2);
+        assert!(acct.lamports > initial_lamports);
+
+        // Shrinking refunds the freed rent
+        let remove_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::RemoveAttributes {
+                keys: vec!["b".into()],
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[remove_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        let attrs = assert_right_sized(&acct.data, acct.lamports);
+        assert_eq!(attrs.data, vec![("a".to_string(), "1".to_string())]);
+        assert_eq!(acct.lamports, initial_lamports);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn upsert_attributes_over_cap_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let full: Vec<(String, String)> = (0..arch_token_metadata::state::MAX_ATTRIBUTES)
+            .map(|i| (format!("k{i}"), "v".to_string()))
+            .collect();
+        let create_attrs_ix =
+            build_create_attributes_ix(program_id, payer_pk, mint_pk, payer_pk, full);
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // Overwriting an existing key is fine, adding one more is not
+        let overwrite_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("k0".into(), "new".into())],
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[overwrite_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let grow_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("extra".into(), "v".into())],
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[grow_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn patch_attributes_wrong_signer_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (wrong_kp, wrong_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&wrong_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("a".into(), "1".into())],
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        for instruction in [
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("a".into(), "hijacked".into())],
+                expected_revision: None,
+            },
+            MetadataInstruction::RemoveAttributes {
+                keys: vec!["a".into()],
+                expected_revision: None,
+            },
+        ] {
+            let ix = patch_ix(program_id, mint_pk, wrong_pk, instruction);
+            let status = send_instructions(&ctx, wrong_pk, &[ix], vec![wrong_kp]).await?;
+            assert!(matches!(status, Status::Failed(_)));
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn attributes_stored_sorted_and_duplicates_rejected() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Duplicate keys are rejected on create
+        let dup_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![
+                ("website".into(), "a".into()),
+                ("website".into(), "b".into()),
+            ],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[dup_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Unsorted input is stored in key order
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![
+                ("website".into(), "w".into()),
+                ("category".into(), "c".into()),
+            ],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[create_attrs_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (attrs_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        let attrs = TokenMetadataAttributes::unpack_from_slice(&acct.data).unwrap();
+        assert_eq!(
+            attrs.data,
+            vec![
+                ("category".to_string(), "c".to_string()),
+                ("website".into(), "w".into())
+            ]
+        );
+        assert_eq!(attrs.get("website"), Some("w"));
+
+        // Duplicate keys are rejected on upsert
+        let dup_upsert_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("tags".into(), "a".into()), ("tags".into(), "b".into())],
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[dup_upsert_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn payer_is_optional_and_freed_rent_goes_to_the_authority() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("a".into(), "1".into()), ("b".into(), "x".repeat(200))],
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+        let (attrs_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let full_lamports = ctx.read_account_info(attrs_pda).await?.lamports;
+
+        // The original three-account form still works; with a readonly authority the freed
+        // rent stays in the account
+        let replace_ix = |data: Vec<(String, String)>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(attrs_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+            ],
+            data: MetadataInstruction::ReplaceAttributes {
+                data,
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let ix = replace_ix(vec![("a".into(), "1".into())]);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        assert_eq!(
+            acct.data.len(),
+            attributes_account_len(&[("a".into(), "1".into())])
+        );
+        assert_eq!(acct.lamports, full_lamports);
+
+        // Growing past the rent already held needs the payer
+        let ix = replace_ix(vec![("a".into(), "x".repeat(240))]);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // A writable single-key authority collects the rent freed by shrinking
+        let upsert_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpsertAttributes {
+                data: vec![("b".into(), "x".repeat(200))],
+                expected_revision: None,
+            },
+        );
+        let remove_ix = patch_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::RemoveAttributes {
+                keys: vec!["b".into()],
+                expected_revision: None,
+            },
+        );
+        let status =
+            send_instructions(&ctx, payer_pk, &[upsert_ix, remove_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let acct = ctx.read_account_info(attrs_pda).await?;
+        assert_eq!(acct.lamports, minimum_rent(acct.data.len()));
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,241 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_metadata_pda_with_program, instruction::MetadataInstruction, state::TokenMetadata,
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use serial_test::serial;
+
+fn authority_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    signer_pk: Pubkey,
+    instruction: MetadataInstruction,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(signer_pk, true),
+        ],
+        data: instruction.pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn propose_then_accept_transfers_authority() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (new_kp, new_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&new_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let propose_ix = authority_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::ProposeAuthority {
+                new_authority: new_pk,
+                expected_revision: None,
+            },
+        );
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, propose_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Proposal alone does not change the authority
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.update_authority, Some(payer_pk));
+        assert_eq!(md.pending_authority, Some(new_pk));
+
+        let accept_ix = authority_ix(
+            program_id,
+            mint_pk,
+            new_pk,
+            MetadataInstruction::AcceptAuthority {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, new_pk, &[accept_ix], vec![new_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.update_authority, Some(new_pk));
+        assert_eq!(md.pending_authority, None);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn accept_by_non_pending_signer_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_new_kp, new_pk, _) = ctx.generate_new_keypair();
+        let (wrong_kp, wrong_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&wrong_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let propose_ix = authority_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::ProposeAuthority {
+                new_authority: new_pk,
+                expected_revision: None,
+            },
+        );
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, propose_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let accept_ix = authority_ix(
+            program_id,
+            mint_pk,
+            wrong_pk,
+            MetadataInstruction::AcceptAuthority {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, wrong_pk, &[accept_ix], vec![wrong_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn cancelled_or_superseded_proposal_cannot_be_accepted() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (new_kp, new_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&new_kp).await?;
+        let (_other_kp, other_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let propose = MetadataInstruction::ProposeAuthority {
+            new_authority: new_pk,
+            expected_revision: None,
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                authority_ix(program_id, mint_pk, payer_pk, propose.clone()),
+                authority_ix(
+                    program_id,
+                    mint_pk,
+                    payer_pk,
+                    MetadataInstruction::CancelProposal {
+                        expected_revision: None,
+                    },
+                ),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let accept_ix = authority_ix(
+            program_id,
+            mint_pk,
+            new_pk,
+            MetadataInstruction::AcceptAuthority {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, new_pk, &[accept_ix.clone()], vec![new_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // A direct transfer clears an outstanding proposal
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                authority_ix(program_id, mint_pk, payer_pk, propose),
+                authority_ix(
+                    program_id,
+                    mint_pk,
+                    payer_pk,
+                    MetadataInstruction::TransferAuthority {
+                        new_authority: other_pk,
+                        expected_revision: None,
+                    },
+                ),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let status = send_instructions(&ctx, new_pk, &[accept_ix], vec![new_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,532 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    rent::minimum_rent, system_instruction,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    extension::ExtensionType,
+    find_attributes_pda_with_program, find_creators_pda_with_program,
+    find_delegate_pda_with_program, find_extension_pda_with_program,
+    find_metadata_pda_with_program, find_touch_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{TokenMetadata, TokenMetadataTouch, LOCK_IMAGE},
+};
+use arch_token_metadata_tests::{
+    assert_closed, assert_rent_exempt, build_create_attributes_ix, build_create_metadata_ix,
+    create_and_init_mint, deploy_token_metadata_program, send_instructions,
+    send_instructions_with_logs,
+};
+use serial_test::serial;
+
+fn close_attributes_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(attributes_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+            AccountMeta::new_readonly(metadata_pda, false),
+            AccountMeta::new(destination, false),
+        ],
+        data: MetadataInstruction::CloseAttributes {
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn close_metadata_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+    let (touch_pda, _) = find_touch_pda_with_program(&program_id, &mint_pk);
+    let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+            AccountMeta::new_readonly(mint_pk, false),
+            AccountMeta::new_readonly(attributes_pda, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(touch_pda, false),
+            AccountMeta::new(creators_pda, false),
+        ],
+        data: MetadataInstruction::CloseMetadata {
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+/// Touch, SetCreators, AddDelegate and CreateExtension, each signed by `payer_pk`
+fn create_dependents_ixs(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+) -> Vec<Instruction> {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (touch_pda, _) = find_touch_pda_with_program(&program_id, &mint_pk);
+    let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+    let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &payer_pk);
+    let royalty = ExtensionType::Royalty.id();
+    let (extension_pda, _) = find_extension_pda_with_program(&program_id, &mint_pk, royalty);
+    let with_pda = |pda: Pubkey, data: Vec<u8>| Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+        ],
+        data,
+    };
+    vec![
+        with_pda(
+            touch_pda,
+            MetadataInstruction::Touch {
+                expected_revision: None,
+            }
+            .pack(),
+        ),
+        with_pda(
+            creators_pda,
+            MetadataInstruction::SetCreators {
+                creators: vec![(payer_pk, 100)],
+                expected_revision: None,
+            }
+            .pack(),
+        ),
+        with_pda(
+            delegate_pda,
+            MetadataInstruction::AddDelegate {
+                delegate: payer_pk,
+                scope: LOCK_IMAGE,
+                expected_revision: None,
+            }
+            .pack(),
+        ),
+        with_pda(
+            extension_pda,
+            MetadataInstruction::CreateExtension {
+                extension_type: royalty,
+                data: vec![1],
+                expected_revision: None,
+            }
+            .pack(),
+        ),
+    ]
+}
+
+#[tokio::test]
+#[serial]
+async fn close_attributes_then_metadata_succeeds() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_dest_kp, dest_pk, _) = ctx.generate_new_keypair();
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://img",
+            "desc",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("k".into(), "v".into())],
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let md_lamports = ctx.read_account_info(metadata_pda).await?.lamports;
+        let attrs_lamports = ctx.read_account_info(attributes_pda).await?.lamports;
+
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                close_attributes_ix(program_id, mint_pk, payer_pk, dest_pk),
+                close_metadata_ix(program_id, mint_pk, payer_pk, dest_pk),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // Destination received both balances
+        let dest = ctx.read_account_info(dest_pk).await?;
+        assert_eq!(dest.lamports, md_lamports + attrs_lamports);
+
+        for closed in [metadata_pda, attributes_pda] {
+            assert_closed(&ctx, closed).await?;
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn close_metadata_with_open_attributes_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://img",
+            "desc",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("k".into(), "v".into())],
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = close_metadata_ix(program_id, mint_pk, payer_pk, payer_pk);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn close_metadata_with_nonzero_supply_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://img",
+            "desc",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Mint one token into a fresh token account
+        let (token_kp, token_pk, _) = ctx.generate_new_keypair();
+        let create_token_account_ix = system_instruction::create_account(
+            &payer_pk,
+            &token_pk,
+            minimum_rent(apl_token::state::Account::LEN),
+            apl_token::state::Account::LEN as u64,
+            &apl_token::id(),
+        );
+        let init_token_account_ix = apl_token::instruction::initialize_account3(
+            &apl_token::id(),
+            &token_pk,
+            &mint_pk,
+            &payer_pk,
+        )?;
+        let mint_to_ix = apl_token::instruction::mint_to(
+            &apl_token::id(),
+            &mint_pk,
+            &token_pk,
+            &payer_pk,
+            &[],
+            1,
+        )?;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_token_account_ix, init_token_account_ix, mint_to_ix],
+            vec![payer_kp, token_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = close_metadata_ix(program_id, mint_pk, payer_pk, payer_pk);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn close_attributes_wrong_authority_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (attacker_kp, attacker_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&attacker_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://img",
+            "desc",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("k".into(), "v".into())],
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = close_attributes_ix(program_id, mint_pk, attacker_pk, attacker_pk);
+        let status = send_instructions(&ctx, attacker_pk, &[ix], vec![attacker_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = close_metadata_ix(program_id, mint_pk, attacker_pk, attacker_pk);
+        let status = send_instructions(&ctx, attacker_pk, &[ix], vec![attacker_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn close_metadata_cleans_up_dependents_so_recreate_starts_clean() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_dest_kp, dest_pk, _) = ctx.generate_new_keypair();
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Name",
+            "SYM",
+            "https://img",
+            "desc",
+            false,
+        )
+        .await;
+        let mut ixs = vec![create_md_ix.clone()];
+        ixs.extend(create_dependents_ixs(program_id, payer_pk, mint_pk));
+        let status = send_instructions(&ctx, payer_pk, &ixs, vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data)?;
+        assert_eq!((md.delegate_count, md.extension_count), (1, 1));
+
+        // Open delegate records and extensions block the close
+        let ix = close_metadata_ix(program_id, mint_pk, payer_pk, dest_pk);
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs
+            .iter()
+            .any(|l| l.contains("delegate records must be revoked first")));
+
+        let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &payer_pk);
+        let revoke_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(delegate_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(payer_pk, false),
+            ],
+            data: MetadataInstruction::RevokeDelegate {
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let ix = close_metadata_ix(program_id, mint_pk, payer_pk, dest_pk);
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[revoke_ix, ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs
+            .iter()
+            .any(|l| l.contains("extensions must be closed first")));
+
+        let (extension_pda, _) =
+            find_extension_pda_with_program(&program_id, &mint_pk, ExtensionType::Royalty.id());
+        let close_ext_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(extension_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(payer_pk, false),
+            ],
+            data: MetadataInstruction::CloseExtension {
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let (touch_pda, _) = find_touch_pda_with_program(&program_id, &mint_pk);
+        let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+        let touch_lamports = ctx.read_account_info(touch_pda).await?.lamports;
+        let creators_lamports = ctx.read_account_info(creators_pda).await?.lamports;
+        let md_lamports = ctx.read_account_info(metadata_pda).await?.lamports;
+
+        let revoke_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(delegate_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(payer_pk, false),
+            ],
+            data: MetadataInstruction::RevokeDelegate {
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let ix = close_metadata_ix(program_id, mint_pk, payer_pk, dest_pk);
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[revoke_ix, close_ext_ix, ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // The touch and creators PDAs were closed with the metadata
+        let dest = ctx.read_account_info(dest_pk).await?;
+        assert_eq!(
+            dest.lamports,
+            md_lamports + touch_lamports + creators_lamports
+        );
+        let all = [
+            metadata_pda,
+            touch_pda,
+            creators_pda,
+            delegate_pda,
+            extension_pda,
+        ];
+        for closed in all {
+            assert_closed(&ctx, closed).await?;
+        }
+
+        // Re-created metadata for the same mint inherits none of the old records
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data)?;
+        assert_eq!((md.delegate_count, md.extension_count), (0, 0));
+        for closed in [touch_pda, creators_pda, delegate_pda, extension_pda] {
+            assert_closed(&ctx, closed).await?;
+        }
+
+        // Every re-created account is funded again
+        let ixs = create_dependents_ixs(program_id, payer_pk, mint_pk);
+        let status = send_instructions(&ctx, payer_pk, &ixs, vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let touch = TokenMetadataTouch::unpack(&ctx.read_account_info(touch_pda).await?.data)?;
+        assert_eq!(touch.touch_count, 1);
+        for recreated in all {
+            assert_rent_exempt(&ctx, recreated).await?;
+        }
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,396 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{Collection, TokenMetadata},
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use serial_test::serial;
+
+fn set_collection_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    collection_mint: Option<Pubkey>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let mut accounts = vec![
+        AccountMeta::new(metadata_pda, false),
+        AccountMeta::new_readonly(authority_pk, true),
+    ];
+    if let Some(collection_mint) = collection_mint {
+        let (collection_pda, _) = find_metadata_pda_with_program(&program_id, &collection_mint);
+        accounts.push(AccountMeta::new_readonly(collection_pda, false));
+    }
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetCollection {
+            collection_mint,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn collection_member_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    collection_mint: Pubkey,
+    collection_authority: Pubkey,
+    instruction: MetadataInstruction,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (collection_pda, _) = find_metadata_pda_with_program(&program_id, &collection_mint);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(collection_pda, false),
+            AccountMeta::new_readonly(collection_authority, true),
+        ],
+        data: instruction.pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn collection_authority_verifies_and_unverifies_member() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (curator_kp, curator_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&curator_kp).await?;
+        let (collection_kp, collection_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx,
+            &curator_kp,
+            curator_pk,
+            &collection_kp,
+            collection_pk,
+            &curator_pk,
+            None,
+        )
+        .await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_collection_ix, _) = build_create_metadata_ix(
+            program_id,
+            curator_pk,
+            collection_pk,
+            curator_pk,
+            "Items",
+            "ITM",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status =
+            send_instructions(&ctx, curator_pk, &[create_collection_ix], vec![curator_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Sword",
+            "SWD",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let set_ix = set_collection_ix(program_id, mint_pk, payer_pk, Some(collection_pk));
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, set_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(
+            md.collection,
+            Some(Collection {
+                key: collection_pk,
+                verified: false,
+            })
+        );
+
+        // The member's own authority cannot verify itself
+        let ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            payer_pk,
+            MetadataInstruction::VerifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            curator_pk,
+            MetadataInstruction::VerifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, curator_pk, &[ix], vec![curator_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert!(md.collection.unwrap().verified);
+
+        let ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            curator_pk,
+            MetadataInstruction::UnverifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, curator_pk, &[ix], vec![curator_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert!(!md.collection.unwrap().verified);
+
+        // Leaving the collection clears the reference
+        let ix = set_collection_ix(program_id, mint_pk, payer_pk, None);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.collection, None);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn set_collection_rejects_missing_or_mismatched_collection() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+        let (other_kp, other_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &other_kp, other_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // `other` has no metadata, so it cannot be a collection
+        let ix = set_collection_ix(program_id, mint_pk, payer_pk, Some(other_pk));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // A mint cannot be its own collection
+        let ix = set_collection_ix(program_id, mint_pk, payer_pk, Some(mint_pk));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Verifying against a collection the member never pointed at fails
+        let (create_other_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            other_pk,
+            payer_pk,
+            "C",
+            "C",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let verify_ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            other_pk,
+            payer_pk,
+            MetadataInstruction::VerifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[create_other_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let status = send_instructions(&ctx, payer_pk, &[verify_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn member_authority_unverifies_under_immutable_collection() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (curator_kp, curator_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&curator_kp).await?;
+        let (collection_kp, collection_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx,
+            &curator_kp,
+            curator_pk,
+            &collection_kp,
+            collection_pk,
+            &curator_pk,
+            None,
+        )
+        .await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_collection_ix, collection_pda) = build_create_metadata_ix(
+            program_id,
+            curator_pk,
+            collection_pk,
+            curator_pk,
+            "Items",
+            "ITM",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status =
+            send_instructions(&ctx, curator_pk, &[create_collection_ix], vec![curator_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "Sword",
+            "SWD",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let set_ix = set_collection_ix(program_id, mint_pk, payer_pk, Some(collection_pk));
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, set_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Verify, then freeze the collection
+        let verify_ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            curator_pk,
+            MetadataInstruction::VerifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let make_immutable_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(collection_pda, false),
+                AccountMeta::new_readonly(curator_pk, true),
+            ],
+            data: MetadataInstruction::MakeImmutable {
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            curator_pk,
+            &[verify_ix, make_immutable_ix],
+            vec![curator_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // The former collection authority can no longer unverify
+        let ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            curator_pk,
+            MetadataInstruction::UnverifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, curator_pk, &[ix], vec![curator_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert!(md.collection.unwrap().verified);
+
+        // The member's own authority can, but still cannot verify again
+        let unverify_ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            payer_pk,
+            MetadataInstruction::UnverifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[unverify_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(
+            md.collection,
+            Some(Collection {
+                key: collection_pk,
+                verified: false,
+            })
+        );
+
+        let verify_ix = collection_member_ix(
+            program_id,
+            mint_pk,
+            collection_pk,
+            payer_pk,
+            MetadataInstruction::VerifyCollectionMember {
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[verify_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
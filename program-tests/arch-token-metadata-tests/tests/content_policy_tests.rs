@@ -0,0 +1,121 @@
+use arch_program::{account::AccountMeta, instruction::Instruction, pubkey::Pubkey};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{find_metadata_pda_with_program, instruction::MetadataInstruction};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use serial_test::serial;
+
+fn update_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    symbol: Option<&str>,
+    image: Option<&str>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+        ],
+        data: MetadataInstruction::UpdateMetadata {
+            name: None,
+            symbol: symbol.map(str::to_string),
+            image: image.map(str::to_string),
+            description: None,
+            uri: None,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn create_rejects_policy_violations() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        for (name, symbol, image) in [
+            ("", "S", "https://i"),
+            ("N", "", "https://i"),
+            ("N", "S S", "https://i"),
+            ("N\u{7}", "S", "https://i"),
+            ("N", "S", "http://i"),
+            ("N", "S", "not a uri"),
+        ] {
+            let (ix, _) = build_create_metadata_ix(
+                program_id, payer_pk, mint_pk, payer_pk, name, symbol, image, "d", false,
+            )
+            .await;
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(
+                matches!(status, Status::Failed(_)),
+                "{name:?} {symbol:?} {image:?}"
+            );
+        }
+
+        // An empty image means "no image" and is accepted
+        let (ix, _) = build_create_metadata_ix(
+            program_id, payer_pk, mint_pk, payer_pk, "N", "S", "", "d", false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn update_rejects_policy_violations() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        for (symbol, image) in [(Some("S\t"), None), (None, Some("ftp://i"))] {
+            let ix = update_ix(program_id, mint_pk, payer_pk, symbol, image);
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(matches!(status, Status::Failed(_)));
+        }
+
+        let ix = update_ix(program_id, mint_pk, payer_pk, Some("S2"), Some("ar://tx"));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,174 @@
+use arch_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    instruction::MetadataInstruction,
+    state::{ContentHashAlgorithm, ContentUri, TokenMetadata, LOCK_URI},
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, build_metadata_ix, create_and_init_mint,
+    deploy_token_metadata_program, send_instructions,
+};
+use bitcoin::hashes::{sha256, Hash};
+use serial_test::serial;
+
+fn update_uri_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    uri: ContentUri,
+) -> Instruction {
+    build_metadata_ix(
+        program_id,
+        mint_pk,
+        authority_pk,
+        MetadataInstruction::UpdateMetadata {
+            name: None,
+            symbol: None,
+            image: None,
+            description: None,
+            uri: Some(uri),
+            expected_revision: None,
+        },
+    )
+}
+
+fn sha256_uri(uri: &str, json: &[u8]) -> ContentUri {
+    ContentUri {
+        uri: uri.into(),
+        content_hash: sha256::Hash::hash(json).to_byte_array(),
+        hash_algorithm: ContentHashAlgorithm::Sha256,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn content_uri_set_on_create_and_update() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (mut create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let v1 = sha256_uri("https://example.com/v1.json", br#"{"name":"N"}"#);
+        create_md_ix.data = MetadataInstruction::CreateMetadata {
+            name: "N".into(),
+            symbol: "S".into(),
+            image: "https://i".into(),
+            description: "d".into(),
+            immutable: false,
+            uri: Some(v1.clone()),
+        }
+        .pack();
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.uri, v1.uri);
+        assert_eq!(md.content_hash, v1.content_hash);
+        assert_eq!(md.content_hash_algorithm, ContentHashAlgorithm::Sha256);
+
+        let v2 = sha256_uri("ipfs://bafy/v2.json", br#"{"name":"N2"}"#);
+        let ix = update_uri_ix(program_id, mint_pk, payer_pk, v2.clone());
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Clearing drops the commitment with it
+        let cleared = ContentUri {
+            uri: String::new(),
+            content_hash: [0u8; 32],
+            hash_algorithm: ContentHashAlgorithm::None,
+        };
+        let ix = update_uri_ix(program_id, mint_pk, payer_pk, cleared);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.uri, "");
+        assert_eq!(md.content_hash, [0u8; 32]);
+        assert_eq!(md.content_hash_algorithm, ContentHashAlgorithm::None);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn content_uri_rejects_inconsistent_hash_and_lock() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let good = sha256_uri("https://example.com/t.json", b"{}");
+        for bad in [
+            // A hash without an algorithm
+            ContentUri {
+                hash_algorithm: ContentHashAlgorithm::None,
+                ..good.clone()
+            },
+            // A commitment without a URI
+            ContentUri {
+                uri: String::new(),
+                ..good.clone()
+            },
+        ] {
+            let ix = update_uri_ix(program_id, mint_pk, payer_pk, bad);
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(matches!(status, Status::Failed(_)));
+        }
+
+        let lock_ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::LockFields {
+                mask: LOCK_URI,
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, payer_pk, &[lock_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = update_uri_ix(program_id, mint_pk, payer_pk, good);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,242 @@
+//! The CPI wrappers and the SDK must build identical instructions, so a caller can move
+//! between the two without changing what the program sees
+
+use arch_program::{
+    account::AccountInfo, instruction::Instruction, pubkey::Pubkey, utxo::UtxoMeta,
+};
+use arch_token_metadata::{
+    cpi::{
+        create_metadata_instruction, update_metadata_instruction, CreateMetadataAccounts,
+        CreateMetadataArgs, UpdateMetadataAccounts, UpdateMetadataArgs, UpdateMetadataAuthority,
+    },
+    instruction::get_metadata_instruction,
+};
+use arch_token_metadata_sdk::{
+    sha256_content_uri, CreateMetadataParams, TokenMetadataClient, UpdateMetadataParams,
+};
+
+/// Backing storage for the `AccountInfo`s handed to the CPI builders
+struct TestAccounts {
+    keys: Vec<Pubkey>,
+    lamports: Vec<u64>,
+    data: Vec<Vec<u8>>,
+    owner: Pubkey,
+    utxo: UtxoMeta,
+}
+
+impl TestAccounts {
+    fn new(keys: &[Pubkey]) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            lamports: vec![0; keys.len()],
+            data: vec![Vec::new(); keys.len()],
+            owner: Pubkey::system_program(),
+            utxo: UtxoMeta::default(),
+        }
+    }
+
+    fn infos(&mut self) -> Vec<AccountInfo<'_>> {
+        self.keys
+            .iter()
+            .zip(self.lamports.iter_mut())
+            .zip(self.data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(
+                    key,
+                    lamports,
+                    data,
+                    &self.owner,
+                    &self.utxo,
+                    false,
+                    false,
+                    false,
+                )
+            })
+            .collect()
+    }
+}
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::from_slice(&[byte; 32])
+}
+
+fn assert_matches_sdk(
+    (instruction, account_infos): (Instruction, Vec<AccountInfo>),
+    expected: Instruction,
+) {
+    assert_eq!(instruction, expected);
+    let info_keys: Vec<Pubkey> = account_infos.iter().map(|info| *info.key).collect();
+    let meta_keys: Vec<Pubkey> = instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+    assert_eq!(info_keys, meta_keys);
+}
+
+fn update_args_and_params(
+    mint: Pubkey,
+    update_authority: Pubkey,
+) -> (UpdateMetadataArgs, UpdateMetadataParams) {
+    let uri = sha256_content_uri("https://example.com/new.json", b"{}");
+    let args = UpdateMetadataArgs {
+        name: Some("Renamed".to_string()),
+        image: Some("https://example.com/new.png".to_string()),
+        uri: Some(uri.clone()),
+        expected_revision: Some(7),
+        ..Default::default()
+    };
+    let params = UpdateMetadataParams {
+        mint,
+        update_authority,
+        name: args.name.clone(),
+        symbol: None,
+        image: args.image.clone(),
+        description: None,
+        uri: Some(uri),
+        expected_revision: args.expected_revision,
+    };
+    (args, params)
+}
+
+#[test]
+fn create_metadata_matches_sdk() {
+    let program_id = key(1);
+    let client = TokenMetadataClient::new(program_id);
+    let (payer, mint, mint_authority) = (key(2), key(3), key(4));
+    let mut accounts = TestAccounts::new(&[
+        payer,
+        Pubkey::system_program(),
+        mint,
+        client.metadata_pda(&mint),
+        mint_authority,
+    ]);
+    let infos = accounts.infos();
+    let uri = sha256_content_uri("https://example.com/token.json", b"{}");
+
+    let built = create_metadata_instruction(
+        &program_id,
+        &CreateMetadataAccounts {
+            payer: &infos[0],
+            system_program: &infos[1],
+            mint: &infos[2],
+            metadata: &infos[3],
+            mint_authority: &infos[4],
+        },
+        CreateMetadataArgs {
+            name: "Name".to_string(),
+            symbol: "SYM".to_string(),
+            image: "https://example.com/token.png".to_string(),
+            description: "d".to_string(),
+            immutable: false,
+            uri: Some(uri.clone()),
+        },
+    );
+    let expected = client
+        .create_metadata_ix(CreateMetadataParams {
+            payer,
+            mint,
+            mint_or_freeze_authority: mint_authority,
+            name: "Name".to_string(),
+            symbol: "SYM".to_string(),
+            image: "https://example.com/token.png".to_string(),
+            description: "d".to_string(),
+            immutable: false,
+            uri: Some(uri),
+        })
+        .unwrap();
+    assert_matches_sdk(built, expected);
+}
+
+#[test]
+fn update_metadata_as_update_authority_matches_sdk() {
+    let program_id = key(1);
+    let client = TokenMetadataClient::new(program_id);
+    let (mint, authority) = (key(3), key(4));
+    let mut accounts = TestAccounts::new(&[client.metadata_pda(&mint), authority]);
+    let infos = accounts.infos();
+    let (args, params) = update_args_and_params(mint, authority);
+
+    let built = update_metadata_instruction(
+        &program_id,
+        &UpdateMetadataAccounts {
+            metadata: &infos[0],
+            authority: UpdateMetadataAuthority::Single(&infos[1]),
+        },
+        args,
+    );
+    assert_matches_sdk(built, client.update_metadata_ix(params).unwrap());
+}
+
+#[test]
+fn update_metadata_as_delegate_matches_sdk() {
+    let program_id = key(1);
+    let client = TokenMetadataClient::new(program_id);
+    let (mint, delegate) = (key(3), key(5));
+    let mut accounts = TestAccounts::new(&[
+        client.metadata_pda(&mint),
+        delegate,
+        client.delegate_pda(&mint, &delegate),
+    ]);
+    let infos = accounts.infos();
+    let (args, params) = update_args_and_params(mint, delegate);
+
+    let built = update_metadata_instruction(
+        &program_id,
+        &UpdateMetadataAccounts {
+            metadata: &infos[0],
+            authority: UpdateMetadataAuthority::Delegate {
+                delegate: &infos[1],
+                record: &infos[2],
+            },
+        },
+        args,
+    );
+    assert_matches_sdk(
+        built,
+        client.update_metadata_as_delegate_ix(params).unwrap(),
+    );
+}
+
+#[test]
+fn update_metadata_as_multisig_matches_sdk() {
+    let program_id = key(1);
+    let client = TokenMetadataClient::new(program_id);
+    let (mint, multisig) = (key(3), key(6));
+    let members = [key(7), key(8)];
+    let mut accounts = TestAccounts::new(&[client.metadata_pda(&mint), multisig]);
+    let infos = accounts.infos();
+    let mut signer_accounts = TestAccounts::new(&members);
+    let signers = signer_accounts.infos();
+    let (args, params) = update_args_and_params(mint, multisig);
+
+    let built = update_metadata_instruction(
+        &program_id,
+        &UpdateMetadataAccounts {
+            metadata: &infos[0],
+            authority: UpdateMetadataAuthority::Multisig {
+                multisig: &infos[1],
+                signers: &signers,
+            },
+        },
+        args,
+    );
+    assert_matches_sdk(
+        built,
+        client
+            .update_metadata_multisig_ix(params, &members)
+            .unwrap(),
+    );
+}
+
+#[test]
+fn get_metadata_matches_sdk() {
+    let program_id = key(1);
+    let client = TokenMetadataClient::new(program_id);
+    let mint = key(3);
+
+    assert_eq!(
+        get_metadata_instruction(&program_id, &client.metadata_pda(&mint), &mint),
+        client.get_metadata_ix(mint).unwrap(),
+    );
+}
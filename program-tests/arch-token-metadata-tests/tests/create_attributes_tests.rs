@@ -46,9 +46,10 @@ async fn create_attributes_success_same_tx() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
 
@@ -66,7 +67,11 @@ async fn create_attributes_success_same_tx() {
 
         // attributes
         let data = vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())];
-        let create_attrs = MetadataInstruction::CreateAttributes { data: data.clone() }.pack();
+        let create_attrs = MetadataInstruction::CreateAttributes {
+            data: data.clone(),
+            expected_revision: None,
+        }
+        .pack();
         let create_attrs_ix = Instruction {
             program_id,
             accounts: vec![
@@ -97,7 +102,7 @@ async fn create_attributes_success_same_tx() {
         assert_eq!(res.status, Status::Processed);
 
         let acct = ctx.read_account_info(attrs_pda).await?;
-        let attrs = TokenMetadataAttributes::unpack(&acct.data).unwrap();
+        let attrs = TokenMetadataAttributes::unpack_from_slice(&acct.data).unwrap();
         assert!(attrs.is_initialized);
         assert_eq!(attrs.data, data);
         Ok(())
@@ -136,9 +141,10 @@ async fn create_attributes_success_two_txs() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -159,7 +165,7 @@ async fn create_attributes_success_two_txs() {
             recent1.parse()?,
         );
         let tx1 = ctx
-            .build_and_sign_transaction(msg1, vec![payer_kp.clone(), mint_kp.clone()])
+            .build_and_sign_transaction(msg1, vec![payer_kp, mint_kp])
             .await?;
         let txid1 = ctx.send_transaction(tx1).await?;
         let res1 = ctx.wait_for_transaction(&txid1).await?;
@@ -169,7 +175,11 @@ async fn create_attributes_success_two_txs() {
 
         // Tx2: create attributes
         let data = vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())];
-        let create_attrs = MetadataInstruction::CreateAttributes { data: data.clone() }.pack();
+        let create_attrs = MetadataInstruction::CreateAttributes {
+            data: data.clone(),
+            expected_revision: None,
+        }
+        .pack();
         let create_attrs_ix = Instruction {
             program_id,
             accounts: vec![
@@ -193,7 +203,7 @@ async fn create_attributes_success_two_txs() {
         assert_eq!(res2.status, Status::Processed);
 
         let acct = ctx.read_account_info(attrs_pda).await?;
-        let attrs = TokenMetadataAttributes::unpack(&acct.data).unwrap();
+        let attrs = TokenMetadataAttributes::unpack_from_slice(&acct.data).unwrap();
         assert!(attrs.is_initialized);
         assert_eq!(attrs.data, data);
         Ok(())
@@ -231,9 +241,10 @@ async fn create_attributes_wrong_authority_fails() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -250,6 +261,7 @@ async fn create_attributes_wrong_authority_fails() {
 
         let create_attrs = MetadataInstruction::CreateAttributes {
             data: vec![("a".into(), "b".into())],
+            expected_revision: None,
         }
         .pack();
         let create_attrs_ix = Instruction {
@@ -312,9 +324,10 @@ async fn create_attributes_empty_key_or_value_fails() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -331,7 +344,11 @@ async fn create_attributes_empty_key_or_value_fails() {
 
         // Attempt to create attributes with an empty key and empty value entries
         let data = vec![("".into(), "v".into()), ("k".into(), "".into())];
-        let ix_data = MetadataInstruction::CreateAttributes { data }.pack();
+        let ix_data = MetadataInstruction::CreateAttributes {
+            data,
+            expected_revision: None,
+        }
+        .pack();
         let create_attrs_ix = Instruction {
             program_id,
             accounts: vec![
@@ -392,9 +409,10 @@ async fn create_attributes_too_many_returns_specific_error() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -415,7 +433,11 @@ async fn create_attributes_too_many_returns_specific_error() {
             // MAX_ATTRIBUTES is 32, so 33 items
             data.push((format!("k{}", i), format!("v{}", i)));
         }
-        let ix_data = MetadataInstruction::CreateAttributes { data }.pack();
+        let ix_data = MetadataInstruction::CreateAttributes {
+            data,
+            expected_revision: None,
+        }
+        .pack();
         let create_attrs_ix = Instruction {
             program_id,
             accounts: vec![
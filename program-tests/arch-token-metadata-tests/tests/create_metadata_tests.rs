@@ -62,6 +62,7 @@ async fn create_metadata_mint_authority_rotation_old_fails() {
             image: "https://example.com/rof.png".to_string(),
             description: "old fails".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -147,6 +148,7 @@ async fn create_metadata_mint_authority_rotation_new_succeeds() {
             image: "https://example.com/rno.png".to_string(),
             description: "new ok".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -220,6 +222,7 @@ async fn create_metadata_freeze_auth_rejected_when_mint_auth_present() {
             image: "https://example.com/fr.png".to_string(),
             description: "fr".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -294,6 +297,7 @@ async fn create_metadata_no_authority_fails() {
             image: "https://example.com/na.png".to_string(),
             description: "no auth".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         // Attempt with payer as signer, but mint has no authorities
@@ -367,6 +371,7 @@ async fn create_metadata_mint_authority_success() {
             image: "https://arweave.net/abc123.png".to_string(),
             description: "The first token launched on Arch Network".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
 
@@ -477,6 +482,7 @@ async fn create_metadata_freeze_authority_success() {
             image: "https://example.com/fz.png".to_string(),
             description: "freeze auth creates".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
 
@@ -559,6 +565,7 @@ async fn create_metadata_wrong_signer_fails() {
             image: "https://example.com/ws.png".to_string(),
             description: "should fail".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -625,6 +632,7 @@ async fn create_metadata_duplicate_fails() {
             image: "https://example.com/dup.png".to_string(),
             description: "dup test".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -651,7 +659,7 @@ async fn create_metadata_duplicate_fails() {
             recent.parse()?,
         );
         let tx1 = ctx
-            .build_and_sign_transaction(message1, vec![payer_kp.clone(), mint_kp.clone()])
+            .build_and_sign_transaction(message1, vec![payer_kp, mint_kp])
             .await?;
         let txid1 = ctx.send_transaction(tx1).await?;
         let res1 = ctx.wait_for_transaction(&txid1).await?;
@@ -705,6 +713,7 @@ async fn create_metadata_immutable_success() {
             image: "https://example.com/imm.png".to_string(),
             description: "immutable".to_string(),
             immutable: true,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -776,6 +785,7 @@ async fn create_metadata_wrong_system_program_fails() {
             image: "https://example.com/bs.png".to_string(),
             description: "bad sys".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let bogus_sys = Pubkey::from_slice(&[9u8; 32]);
@@ -836,6 +846,7 @@ async fn create_metadata_mint_wrong_owner_fails() {
             image: "https://example.com/wo.png".to_string(),
             description: "wo".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -894,6 +905,7 @@ async fn create_metadata_uninitialized_mint_fails() {
             image: "https://example.com/ui.png".to_string(),
             description: "ui".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -970,6 +982,7 @@ async fn create_metadata_pda_mismatch_fails() {
             image: "https://example.com/pb.png".to_string(),
             description: "pda bad".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -1043,6 +1056,7 @@ async fn create_metadata_field_caps_exceeded_fails() {
             image: "https://example.com/i.png".to_string(),
             description: "d".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -1066,6 +1080,7 @@ async fn create_metadata_field_caps_exceeded_fails() {
             image: "https://example.com/i.png".to_string(),
             description: "d".to_string(),
             immutable: false,
+            uri: None,
         };
         let data2 = ix_data2.pack();
         let accounts2 = vec![
@@ -1132,6 +1147,7 @@ async fn create_metadata_image_length_exceeded_fails() {
             image: big_image,
             description: "d".to_string(),
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -1198,6 +1214,7 @@ async fn create_metadata_description_length_exceeded_fails() {
             image: "https://example.com/i.png".to_string(),
             description: big_desc,
             immutable: false,
+            uri: None,
         };
         let data = ix_data.pack();
         let accounts = vec![
@@ -0,0 +1,200 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    event::MetadataEvent,
+    find_attributes_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{TokenMetadata, TokenMetadataAttributes},
+};
+use arch_token_metadata_tests::{
+    create_and_init_mint, deploy_token_metadata_program, send_instructions,
+    send_instructions_with_logs,
+};
+use serial_test::serial;
+
+fn create_with_attributes_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+    mint_authority_pk: Pubkey,
+    immutable: bool,
+    data: Vec<(String, String)>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new_readonly(mint_pk, false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(mint_authority_pk, true),
+            AccountMeta::new(attributes_pda, false),
+        ],
+        data: MetadataInstruction::CreateMetadataWithAttributes {
+            name: "N".into(),
+            symbol: "S".into(),
+            image: "https://i".into(),
+            description: "d".into(),
+            immutable,
+            uri: None,
+            data,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn creates_metadata_and_attributes_in_one_instruction() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let ix = create_with_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            false,
+            vec![("b".into(), "2".into()), ("a".into(), "1".into())],
+        );
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let events: Vec<_> = logs
+            .iter()
+            .filter_map(|line| MetadataEvent::from_log(line))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![MetadataEvent::MetadataWithAttributesCreated {
+                mint: mint_pk,
+                update_authority: Some(payer_pk),
+            }]
+        );
+
+        let (metadata_pda, md_bump) = find_metadata_pda_with_program(&program_id, &mint_pk);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.update_authority, Some(payer_pk));
+        assert_eq!(md.bump, Some(md_bump));
+        let (attributes_pda, attrs_bump) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let attrs = TokenMetadataAttributes::unpack_from_slice(
+            &ctx.read_account_info(attributes_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!(attrs.mint, mint_pk);
+        assert_eq!(attrs.bump, Some(attrs_bump));
+        assert_eq!(
+            attrs.data,
+            vec![("a".to_string(), "1".to_string()), ("b".into(), "2".into())]
+        );
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn immutable_metadata_still_gets_its_attributes() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let ix = create_with_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            true,
+            vec![("a".into(), "1".into())],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.update_authority, None);
+        let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let attrs = TokenMetadataAttributes::unpack_from_slice(
+            &ctx.read_account_info(attributes_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!(attrs.get("a"), Some("1"));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn failures_leave_neither_account_behind() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (other_kp, other_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&other_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        // Signer is not the mint authority
+        let ix = create_with_attributes_ix(
+            program_id,
+            other_pk,
+            mint_pk,
+            other_pk,
+            false,
+            vec![("a".into(), "1".into())],
+        );
+        let status = send_instructions(&ctx, other_pk, &[ix], vec![other_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Invalid attributes roll back the metadata written earlier in the instruction
+        let ix = create_with_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            false,
+            vec![("a".into(), "1".into()), ("a".into(), "2".into())],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Nothing was left initialized, so a valid create still goes through
+        let ix = create_with_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            false,
+            vec![("a".into(), "1".into())],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,226 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_creators_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction, state::TokenMetadataCreators,
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use serial_test::serial;
+
+fn set_creators_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+    creators: Vec<(Pubkey, u8)>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(creators_pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+        ],
+        data: MetadataInstruction::SetCreators {
+            creators,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn sign_creator_ix(program_id: Pubkey, mint_pk: Pubkey, creator: Pubkey) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creators_pda, false),
+            AccountMeta::new_readonly(creator, true),
+            AccountMeta::new(metadata_pda, false),
+        ],
+        data: MetadataInstruction::SignCreator {
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn remove_creator_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    creator: Pubkey,
+    signer: Pubkey,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creators_pda, false),
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(metadata_pda, false),
+        ],
+        data: MetadataInstruction::RemoveCreator {
+            creator,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn creators_verify_by_signing_and_can_remove_themselves() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (artist_kp, artist_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&artist_kp).await?;
+        let (_studio_kp, studio_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let set_ix = set_creators_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            vec![(artist_pk, 70), (studio_pk, 30)],
+        );
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, set_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (creators_pda, creators_bump) = find_creators_pda_with_program(&program_id, &mint_pk);
+        let record =
+            TokenMetadataCreators::unpack(&ctx.read_account_info(creators_pda).await?.data)
+                .unwrap();
+        assert_eq!(record.creators.len(), 2);
+        assert_eq!(record.bump, Some(creators_bump));
+        assert!(record.creators.iter().all(|c| !c.verified));
+
+        let ix = sign_creator_ix(program_id, mint_pk, artist_pk);
+        let status = send_instructions(&ctx, artist_pk, &[ix], vec![artist_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Re-setting the same entry keeps the verification; a changed share drops it
+        let ix = set_creators_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            vec![(artist_pk, 70), (studio_pk, 30)],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let record =
+            TokenMetadataCreators::unpack(&ctx.read_account_info(creators_pda).await?.data)
+                .unwrap();
+        assert!(record.get(&artist_pk).unwrap().verified);
+
+        let ix = set_creators_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            vec![(artist_pk, 60), (studio_pk, 40)],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let record =
+            TokenMetadataCreators::unpack(&ctx.read_account_info(creators_pda).await?.data)
+                .unwrap();
+        assert!(!record.get(&artist_pk).unwrap().verified);
+
+        // A creator can take itself off the list
+        let ix = remove_creator_ix(program_id, mint_pk, artist_pk, artist_pk);
+        let status = send_instructions(&ctx, artist_pk, &[ix], vec![artist_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let record =
+            TokenMetadataCreators::unpack(&ctx.read_account_info(creators_pda).await?.data)
+                .unwrap();
+        assert!(record.get(&artist_pk).is_none());
+        assert!(record.get(&studio_pk).is_some());
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn creators_reject_bad_lists_and_strangers() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (stranger_kp, stranger_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&stranger_kp).await?;
+        let (_artist_kp, artist_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let set_ix = set_creators_ix(program_id, payer_pk, mint_pk, vec![(artist_pk, 100)]);
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, set_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Shares over 100 and duplicate addresses are rejected
+        for creators in [
+            vec![(artist_pk, 60), (stranger_pk, 41)],
+            vec![(artist_pk, 10), (artist_pk, 10)],
+        ] {
+            let ix = set_creators_ix(program_id, payer_pk, mint_pk, creators);
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(matches!(status, Status::Failed(_)));
+        }
+
+        // Unlisted keys can neither sign nor remove others
+        let ix = sign_creator_ix(program_id, mint_pk, stranger_pk);
+        let status = send_instructions(&ctx, stranger_pk, &[ix], vec![stranger_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = remove_creator_ix(program_id, mint_pk, artist_pk, stranger_pk);
+        let status = send_instructions(&ctx, stranger_pk, &[ix], vec![stranger_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,425 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_delegate_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{TokenMetadata, LOCK_DESCRIPTION, LOCK_IMAGE},
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use serial_test::serial;
+
+fn add_delegate_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+    delegate: Pubkey,
+    scope: u16,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(delegate_pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+        ],
+        data: MetadataInstruction::AddDelegate {
+            delegate,
+            scope,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn delegate_update_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    delegate: Pubkey,
+    name: Option<&str>,
+    image: Option<&str>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new_readonly(delegate_pda, false),
+        ],
+        data: MetadataInstruction::UpdateMetadata {
+            name: name.map(Into::into),
+            symbol: None,
+            image: image.map(Into::into),
+            description: None,
+            uri: None,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn revoke_delegate_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    delegate: Pubkey,
+    signer: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(delegate_pda, false),
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(destination, false),
+        ],
+        data: MetadataInstruction::RevokeDelegate {
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn delegate_updates_only_scoped_fields() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (delegate_kp, delegate_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&delegate_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let add_ix = add_delegate_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            delegate_pk,
+            LOCK_IMAGE | LOCK_DESCRIPTION,
+        );
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, add_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = delegate_update_ix(program_id, mint_pk, delegate_pk, None, Some("i2"));
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Out of scope
+        let ix = delegate_update_ix(program_id, mint_pk, delegate_pk, Some("N2"), None);
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // An update that sets no field is rejected rather than recorded as a write
+        let revision = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data)
+            .unwrap()
+            .revision;
+        let ix = delegate_update_ix(program_id, mint_pk, delegate_pk, None, None);
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.image, "i2");
+        assert_eq!(md.name, "N");
+        assert_eq!(md.revision, revision);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn revoked_or_voided_delegate_cannot_update() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (delegate_kp, delegate_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&delegate_kp).await?;
+        let (new_auth_kp, new_auth_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&new_auth_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                add_delegate_ix(program_id, payer_pk, mint_pk, delegate_pk, LOCK_IMAGE),
+                revoke_delegate_ix(program_id, mint_pk, delegate_pk, payer_pk, payer_pk),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = delegate_update_ix(program_id, mint_pk, delegate_pk, None, Some("i2"));
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Re-grant, then hand authority over: the old grant no longer counts
+        let transfer_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+            ],
+            data: MetadataInstruction::TransferAuthority {
+                new_authority: new_auth_pk,
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                add_delegate_ix(program_id, payer_pk, mint_pk, delegate_pk, LOCK_IMAGE),
+                transfer_ix,
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = delegate_update_ix(program_id, mint_pk, delegate_pk, None, Some("i3"));
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // The delegate can still renounce its record, returning the rent to the new authority
+        let ix = revoke_delegate_ix(program_id, mint_pk, delegate_pk, delegate_pk, new_auth_pk);
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn add_delegate_requires_update_authority() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (attacker_kp, attacker_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&attacker_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Attacker tries to grant itself rights
+        let ix = add_delegate_ix(program_id, attacker_pk, mint_pk, attacker_pk, LOCK_IMAGE);
+        let status = send_instructions(&ctx, attacker_pk, &[ix], vec![attacker_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Empty scope is rejected
+        let ix = add_delegate_ix(program_id, payer_pk, mint_pk, attacker_pk, 0);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn renouncing_delegate_cannot_redirect_rent() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (delegate_kp, delegate_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&delegate_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                add_delegate_ix(program_id, payer_pk, mint_pk, delegate_pk, LOCK_IMAGE),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // The delegate cannot send the record's rent to itself
+        let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate_pk);
+        let record_lamports = ctx.read_account_info(delegate_pda).await?.lamports;
+        let ix = revoke_delegate_ix(program_id, mint_pk, delegate_pk, delegate_pk, delegate_pk);
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert_eq!(
+            ctx.read_account_info(delegate_pda).await?.lamports,
+            record_lamports
+        );
+
+        // Renouncing works when the rent goes back to the update authority
+        let authority_lamports = ctx.read_account_info(payer_pk).await?.lamports;
+        let ix = revoke_delegate_ix(program_id, mint_pk, delegate_pk, delegate_pk, payer_pk);
+        let status = send_instructions(&ctx, delegate_pk, &[ix], vec![delegate_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        assert_eq!(
+            ctx.read_account_info(payer_pk).await?.lamports,
+            authority_lamports + record_lamports
+        );
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn update_authority_rejects_unexpected_trailing_accounts() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_, delegate_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                add_delegate_ix(program_id, payer_pk, mint_pk, delegate_pk, LOCK_IMAGE),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate_pk);
+        let update_ix = |trailing: &[Pubkey]| {
+            let mut ix = delegate_update_ix(program_id, mint_pk, payer_pk, None, Some("i2"));
+            ix.accounts.truncate(2);
+            ix.accounts.extend(
+                trailing
+                    .iter()
+                    .map(|pk| AccountMeta::new_readonly(*pk, false)),
+            );
+            ix
+        };
+
+        // An unrelated account is not taken for a delegate record
+        let status =
+            send_instructions(&ctx, payer_pk, &[update_ix(&[mint_pk])], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Nor is anything past the one delegate record slot
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[update_ix(&[delegate_pda, mint_pk])],
+            vec![payer_kp],
+        )
+        .await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let status = send_instructions(&ctx, payer_pk, &[update_ix(&[])], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.image, "i2");
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,146 @@
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    event::MetadataEvent,
+    instruction::MetadataInstruction,
+    state::{LOCK_DESCRIPTION, LOCK_NAME},
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, build_metadata_ix, create_and_init_mint,
+    deploy_token_metadata_program, send_instructions_with_logs,
+};
+use serial_test::serial;
+
+fn events(logs: &[String]) -> Vec<MetadataEvent> {
+    logs.iter()
+        .filter_map(|line| MetadataEvent::from_log(line))
+        .map(|event| event.expect("well-formed event"))
+        .collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn each_instruction_emits_one_event() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_new_auth_kp, new_auth_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let update_ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::UpdateMetadata {
+                name: Some("N2".into()),
+                symbol: None,
+                image: None,
+                description: Some("d2".into()),
+                uri: None,
+                expected_revision: None,
+            },
+        );
+        let transfer_ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::TransferAuthority {
+                new_authority: new_auth_pk,
+                expected_revision: None,
+            },
+        );
+        let (status, logs) = send_instructions_with_logs(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, update_ix, transfer_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        assert_eq!(
+            events(&logs),
+            vec![
+                MetadataEvent::MetadataCreated {
+                    mint: mint_pk,
+                    update_authority: Some(payer_pk),
+                },
+                MetadataEvent::MetadataUpdated {
+                    mint: mint_pk,
+                    changed_fields: LOCK_NAME | LOCK_DESCRIPTION,
+                },
+                MetadataEvent::AuthorityTransferred {
+                    mint: mint_pk,
+                    old_authority: payer_pk,
+                    new_authority: new_auth_pk,
+                },
+            ]
+        );
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn failed_instruction_emits_no_event() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let (status, _) =
+            send_instructions_with_logs(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // No pending proposal to cancel
+        let ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::CancelProposal {
+                expected_revision: None,
+            },
+        );
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(events(&logs).is_empty());
+        Ok(())
+    })
+    .await
+}
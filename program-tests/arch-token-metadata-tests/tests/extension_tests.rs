@@ -0,0 +1,322 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    rent::minimum_rent,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    event::MetadataEvent,
+    extension::ExtensionType,
+    find_extension_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{extension_account_len, AccountType, TokenMetadataExtension},
+};
+use arch_token_metadata_tests::{
+    assert_closed, assert_rent_exempt, build_create_metadata_ix, create_and_init_mint,
+    deploy_token_metadata_program, send_instructions, send_instructions_with_logs,
+};
+use serial_test::serial;
+
+fn create_extension_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    extension_type: u16,
+    data: Vec<u8>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (extension_pda, _) = find_extension_pda_with_program(&program_id, &mint_pk, extension_type);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(extension_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+        ],
+        data: MetadataInstruction::CreateExtension {
+            extension_type,
+            data,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn update_extension_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    extension_type: u16,
+    data: Vec<u8>,
+    expected_revision: Option<u64>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (extension_pda, _) = find_extension_pda_with_program(&program_id, &mint_pk, extension_type);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(extension_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+            AccountMeta::new_readonly(metadata_pda, false),
+            AccountMeta::new(authority_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+        ],
+        data: MetadataInstruction::UpdateExtension {
+            data,
+            expected_revision,
+        }
+        .pack(),
+    }
+}
+
+fn close_extension_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    extension_type: u16,
+    destination_pk: Pubkey,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (extension_pda, _) = find_extension_pda_with_program(&program_id, &mint_pk, extension_type);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(extension_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(destination_pk, false),
+        ],
+        data: MetadataInstruction::CloseExtension {
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn extension_create_update_close_roundtrip() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_dest_kp, dest_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let royalty = ExtensionType::Royalty.id();
+        let create_ext_ix =
+            create_extension_ix(program_id, payer_pk, mint_pk, payer_pk, royalty, vec![1, 2]);
+        let (status, logs) = send_instructions_with_logs(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_ext_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+        let events: Vec<_> = logs
+            .iter()
+            .filter_map(|line| MetadataEvent::from_log(line))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            events.last(),
+            Some(&MetadataEvent::ExtensionCreated {
+                mint: mint_pk,
+                extension_type: royalty,
+            })
+        );
+
+        let (extension_pda, ext_bump) =
+            find_extension_pda_with_program(&program_id, &mint_pk, royalty);
+        let ext = TokenMetadataExtension::unpack_from_slice(
+            &ctx.read_account_info(extension_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!(ext.account_type, AccountType::Extension);
+        assert_eq!(
+            (ext.mint, ext.extension_type, ext.bump),
+            (mint_pk, royalty, ext_bump)
+        );
+        assert_eq!((ext.data, ext.revision), (vec![1, 2], 1));
+
+        // Growing the payload resizes the account; a stale revision is rejected
+        let ix = update_extension_ix(program_id, mint_pk, payer_pk, royalty, vec![7; 40], Some(1));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let ix = update_extension_ix(program_id, mint_pk, payer_pk, royalty, vec![8], Some(1));
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Stale revision")));
+
+        let account = ctx.read_account_info(extension_pda).await?;
+        assert_eq!(account.data.len(), extension_account_len(40));
+        let ext = TokenMetadataExtension::unpack_from_slice(&account.data).unwrap();
+        assert_eq!((ext.data, ext.revision), (vec![7; 40], 2));
+
+        // The payer is only needed to grow: without it a shrink refunds the writable authority,
+        // while growth fails
+        let without_payer = |data, expected_revision| {
+            let mut ix = update_extension_ix(
+                program_id,
+                mint_pk,
+                payer_pk,
+                royalty,
+                data,
+                expected_revision,
+            );
+            ix.accounts.truncate(3);
+            ix.accounts[1].is_writable = true;
+            ix
+        };
+        let ix = without_payer(vec![9], Some(2));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let account = ctx.read_account_info(extension_pda).await?;
+        assert_eq!(account.data.len(), extension_account_len(1));
+        assert_eq!(account.lamports, minimum_rent(account.data.len()));
+        let ix = without_payer(vec![9; 40], Some(3));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = close_extension_ix(program_id, mint_pk, payer_pk, royalty, dest_pk);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let dest = ctx.read_account_info(dest_pk).await?;
+        assert_eq!(dest.lamports, account.lamports);
+        assert_closed(&ctx, extension_pda).await?;
+
+        // Once closed, the extension can be created again
+        let ix = create_extension_ix(program_id, payer_pk, mint_pk, payer_pk, royalty, vec![3]);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        assert_rent_exempt(&ctx, extension_pda).await?;
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn extension_requires_registered_type_and_update_authority() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (other_kp, other_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&other_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Type ids outside the registry are rejected
+        let ix = create_extension_ix(program_id, payer_pk, mint_pk, payer_pk, 999, vec![1]);
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Unknown extension type")));
+
+        // Only the update authority may create
+        let localization = ExtensionType::Localization.id();
+        let ix = create_extension_ix(
+            program_id,
+            other_pk,
+            mint_pk,
+            other_pk,
+            localization,
+            vec![1],
+        );
+        let status = send_instructions(&ctx, other_pk, &[ix], vec![other_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // The PDA must be the one for the requested type
+        let mut ix = create_extension_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            localization,
+            vec![1],
+        );
+        ix.accounts[3].pubkey =
+            find_extension_pda_with_program(&program_id, &mint_pk, ExtensionType::Royalty.id()).0;
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = create_extension_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            localization,
+            vec![1],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Creating twice fails, and neither update nor close accepts another signer
+        let ix = create_extension_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            localization,
+            vec![2],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        let ix = update_extension_ix(program_id, mint_pk, other_pk, localization, vec![2], None);
+        let status = send_instructions(&ctx, other_pk, &[ix], vec![other_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        let ix = close_extension_ix(program_id, mint_pk, other_pk, localization, other_pk);
+        let status = send_instructions(&ctx, other_pk, &[ix], vec![other_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let (extension_pda, _) =
+            find_extension_pda_with_program(&program_id, &mint_pk, localization);
+        let ext = TokenMetadataExtension::unpack_from_slice(
+            &ctx.read_account_info(extension_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!((ext.data, ext.revision), (vec![1], 1));
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,65 @@
+use arch_program::pubkey::Pubkey;
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    event::MetadataEvent, find_metadata_pda_with_program, instruction::get_metadata_instruction,
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions, send_instructions_with_logs,
+};
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn get_metadata_is_read_only_and_checks_the_mint() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let before = ctx.read_account_info(metadata_pda).await?.data;
+
+        let ix = get_metadata_instruction(&program_id, &metadata_pda, &mint_pk);
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        assert!(logs
+            .iter()
+            .all(|line| MetadataEvent::from_log(line).is_none()));
+        assert_eq!(ctx.read_account_info(metadata_pda).await?.data, before);
+
+        // The metadata account must belong to the mint named in the instruction
+        let other_mint = Pubkey::from_slice(&[9u8; 32]);
+        let ix = get_metadata_instruction(&program_id, &metadata_pda, &other_mint);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // A PDA with no metadata behind it is rejected
+        let (missing_pda, _) = find_metadata_pda_with_program(&program_id, &other_mint);
+        let ix = get_metadata_instruction(&program_id, &missing_pda, &other_mint);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
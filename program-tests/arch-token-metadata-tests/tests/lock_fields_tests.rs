@@ -0,0 +1,236 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_attributes_pda_with_program, find_metadata_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{TokenMetadata, LOCK_ATTRIBUTES, LOCK_NAME, LOCK_SYMBOL},
+};
+use arch_token_metadata_tests::{
+    build_create_attributes_ix, build_create_metadata_ix, build_metadata_ix, create_and_init_mint,
+    deploy_token_metadata_program, send_instructions,
+};
+use serial_test::serial;
+
+fn update_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    name: Option<&str>,
+    image: Option<&str>,
+) -> Instruction {
+    build_metadata_ix(
+        program_id,
+        mint_pk,
+        authority_pk,
+        MetadataInstruction::UpdateMetadata {
+            name: name.map(Into::into),
+            symbol: None,
+            image: image.map(Into::into),
+            description: None,
+            uri: None,
+            expected_revision: None,
+        },
+    )
+}
+
+#[tokio::test]
+#[serial]
+async fn locked_fields_reject_updates_but_others_stay_mutable() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let lock_ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::LockFields {
+                mask: LOCK_NAME | LOCK_SYMBOL,
+                expected_revision: None,
+            },
+        );
+        let status =
+            send_instructions(&ctx, payer_pk, &[create_md_ix, lock_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Unlocked field can still change
+        let ix = update_ix(program_id, mint_pk, payer_pk, None, Some("i2"));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Locked field cannot, even to the same value
+        let ix = update_ix(program_id, mint_pk, payer_pk, Some("N"), None);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.locked_fields, LOCK_NAME | LOCK_SYMBOL);
+        assert_eq!(md.name, "N");
+        assert_eq!(md.image, "i2");
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn lock_attributes_blocks_attribute_instructions() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let lock_ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            MetadataInstruction::LockFields {
+                mask: LOCK_ATTRIBUTES,
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                build_create_attributes_ix(
+                    program_id,
+                    payer_pk,
+                    mint_pk,
+                    payer_pk,
+                    vec![("k".into(), "v".into())],
+                ),
+                lock_ix,
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+        let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let upsert_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+            ],
+            data: MetadataInstruction::UpsertAttributes {
+                data: vec![("k".into(), "v2".into())],
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(&ctx, payer_pk, &[upsert_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // Core fields were not locked
+        let ix = update_ix(program_id, mint_pk, payer_pk, Some("N2"), None);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn lock_fields_rejects_bad_mask_and_wrong_signer() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (wrong_kp, wrong_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&wrong_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        for mask in [0u16, 1 << 15] {
+            let ix = build_metadata_ix(
+                program_id,
+                mint_pk,
+                payer_pk,
+                MetadataInstruction::LockFields {
+                    mask,
+                    expected_revision: None,
+                },
+            );
+            let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+            assert!(matches!(status, Status::Failed(_)));
+        }
+
+        let ix = build_metadata_ix(
+            program_id,
+            mint_pk,
+            wrong_pk,
+            MetadataInstruction::LockFields {
+                mask: LOCK_NAME,
+                expected_revision: None,
+            },
+        );
+        let status = send_instructions(&ctx, wrong_pk, &[ix], vec![wrong_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
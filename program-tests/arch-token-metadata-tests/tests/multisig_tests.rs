@@ -0,0 +1,828 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+    rent::minimum_rent,
+};
+use arch_sdk::Status;
+use arch_testing::TestContext;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    extension::ExtensionType,
+    find_attributes_pda_with_program, find_creators_pda_with_program,
+    find_delegate_pda_with_program, find_extension_pda_with_program,
+    find_metadata_pda_with_program, find_touch_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{
+        attributes_account_len, TokenMetadata, TokenMetadataCreators, TokenMetadataExtension,
+        TokenMetadataMultisig, LOCK_ALL, LOCK_NAME,
+    },
+};
+use arch_token_metadata_sdk::{
+    attributes_rent_delta, CloseExtensionParams, CreateAttributesParams, CreateExtensionParams,
+    RemoveAttributesParams, TokenMetadataClient, UpdateExtensionParams,
+};
+use arch_token_metadata_tests::{
+    assert_closed, build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use bitcoin::key::Keypair;
+use serial_test::serial;
+
+fn create_multisig_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    multisig_pk: Pubkey,
+    m: u8,
+    signers: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(multisig_pk, true),
+        ],
+        data: MetadataInstruction::CreateMultisig { m, signers }.pack(),
+    }
+}
+
+fn multisig_update_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    multisig_pk: Pubkey,
+    member_signers: &[Pubkey],
+    name: &str,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let mut accounts = vec![
+        AccountMeta::new(metadata_pda, false),
+        AccountMeta::new_readonly(multisig_pk, false),
+    ];
+    accounts.extend(
+        member_signers
+            .iter()
+            .map(|pk| AccountMeta::new_readonly(*pk, true)),
+    );
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::UpdateMetadata {
+            name: Some(name.into()),
+            symbol: None,
+            image: None,
+            description: None,
+            uri: None,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+fn membership_ix(
+    program_id: Pubkey,
+    multisig_pk: Pubkey,
+    member_signers: &[Pubkey],
+    instruction: MetadataInstruction,
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(multisig_pk, false)];
+    accounts.extend(
+        member_signers
+            .iter()
+            .map(|pk| AccountMeta::new_readonly(*pk, true)),
+    );
+    Instruction {
+        program_id,
+        accounts,
+        data: instruction.pack(),
+    }
+}
+
+/// Build a program instruction from raw account metas
+fn authority_ix(
+    program_id: Pubkey,
+    accounts: Vec<AccountMeta>,
+    instruction: MetadataInstruction,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts,
+        data: instruction.pack(),
+    }
+}
+
+/// Turn the `multisig_pk` meta into a non-signer and append `members` as signers
+fn with_members(mut ix: Instruction, multisig_pk: Pubkey, members: &[Pubkey]) -> Instruction {
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == multisig_pk {
+            meta.is_signer = false;
+        }
+    }
+    ix.accounts.extend(
+        members
+            .iter()
+            .map(|pk| AccountMeta::new_readonly(*pk, true)),
+    );
+    ix
+}
+
+/// Create a mint and its metadata, a 2-of-n multisig over `members`, and hand the update
+/// authority to the multisig. Returns (mint, metadata_pda).
+async fn multisig_owned_metadata(
+    ctx: &TestContext,
+    program_id: Pubkey,
+    payer_kp: &Keypair,
+    payer_pk: Pubkey,
+    multisig_kp: &Keypair,
+    multisig_pk: Pubkey,
+    members: Vec<Pubkey>,
+) -> anyhow::Result<(Pubkey, Pubkey)> {
+    let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+    create_and_init_mint(ctx, payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None).await?;
+    let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+        program_id,
+        payer_pk,
+        mint_pk,
+        payer_pk,
+        "N",
+        "S",
+        "https://i",
+        "d",
+        false,
+    )
+    .await;
+    let transfer_ix = authority_ix(
+        program_id,
+        vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+        ],
+        MetadataInstruction::TransferAuthority {
+            new_authority: multisig_pk,
+            expected_revision: None,
+        },
+    );
+    let status = send_instructions(
+        ctx,
+        payer_pk,
+        &[
+            create_md_ix,
+            create_multisig_ix(program_id, payer_pk, multisig_pk, 2, members),
+            transfer_ix,
+        ],
+        vec![*payer_kp, *multisig_kp],
+    )
+    .await?;
+    assert_eq!(status, Status::Processed);
+    Ok((mint_pk, metadata_pda))
+}
+
+/// Send `ix` signed only by the multisig keypair (must fail), then by two members (must pass)
+async fn assert_needs_members(
+    ctx: &TestContext,
+    payer: (&Keypair, Pubkey),
+    multisig: (&Keypair, Pubkey),
+    members: [(&Keypair, Pubkey); 2],
+    ix: Instruction,
+) -> anyhow::Result<()> {
+    let (payer_kp, payer_pk) = payer;
+    let (multisig_kp, multisig_pk) = multisig;
+    let status =
+        send_instructions(ctx, payer_pk, &[ix.clone()], vec![*payer_kp, *multisig_kp]).await?;
+    assert!(matches!(status, Status::Failed(_)));
+
+    let ix = with_members(ix, multisig_pk, &[members[0].1, members[1].1]);
+    let status = send_instructions(
+        ctx,
+        payer_pk,
+        &[ix],
+        vec![*payer_kp, *members[0].0, *members[1].0],
+    )
+    .await?;
+    assert_eq!(status, Status::Processed);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_authority_requires_m_signatures() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (_c_kp, c_pk, _) = ctx.generate_new_keypair();
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let transfer_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+            ],
+            data: MetadataInstruction::TransferAuthority {
+                new_authority: multisig_pk,
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                create_multisig_ix(program_id, payer_pk, multisig_pk, 2, vec![a_pk, b_pk, c_pk]),
+                transfer_ix,
+            ],
+            vec![payer_kp, multisig_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let ms =
+            TokenMetadataMultisig::unpack(&ctx.read_account_info(multisig_pk).await?.data).unwrap();
+        assert_eq!(ms.m, 2);
+        assert_eq!(ms.signers, vec![a_pk, b_pk, c_pk]);
+
+        // One member is not enough
+        let ix = multisig_update_ix(program_id, mint_pk, multisig_pk, &[a_pk], "N1");
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        // The same member listed twice only counts once
+        let ix = multisig_update_ix(program_id, mint_pk, multisig_pk, &[a_pk, a_pk], "N1");
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = multisig_update_ix(program_id, mint_pk, multisig_pk, &[a_pk, b_pk], "N2");
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.update_authority, Some(multisig_pk));
+        assert_eq!(md.name, "N2");
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_membership_changes() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (c_kp, c_pk, _) = ctx.generate_new_keypair();
+
+        // m > n is rejected
+        let (bad_kp, bad_pk, _) = ctx.generate_new_keypair();
+        let ix = create_multisig_ix(program_id, payer_pk, bad_pk, 3, vec![a_pk, b_pk]);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, bad_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = create_multisig_ix(program_id, payer_pk, multisig_pk, 2, vec![a_pk, b_pk]);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, multisig_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Adding needs m current members
+        let add = MetadataInstruction::AddMultisigSigner { signer: c_pk };
+        let ix = membership_ix(program_id, multisig_pk, &[a_pk], add.clone());
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let ix = membership_ix(program_id, multisig_pk, &[a_pk, b_pk], add);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let remove = MetadataInstruction::RemoveMultisigSigner { signer: a_pk };
+        let ix = membership_ix(program_id, multisig_pk, &[a_pk, b_pk], remove);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ms =
+            TokenMetadataMultisig::unpack(&ctx.read_account_info(multisig_pk).await?.data).unwrap();
+        assert_eq!(ms.signers, vec![b_pk, c_pk]);
+
+        // Removing another member would leave fewer than m
+        let remove = MetadataInstruction::RemoveMultisigSigner { signer: c_pk };
+        let ix = membership_ix(program_id, multisig_pk, &[b_pk, c_pk], remove);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, b_kp, c_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_authority_handover_requires_members() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (next_kp, next_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (_mint_pk, metadata_pda) = multisig_owned_metadata(
+            &ctx,
+            program_id,
+            &payer_kp,
+            payer_pk,
+            &multisig_kp,
+            multisig_pk,
+            vec![a_pk, b_pk],
+        )
+        .await?;
+        let ix = create_multisig_ix(program_id, payer_pk, next_pk, 2, vec![a_pk, b_pk]);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, next_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let payer = (&payer_kp, payer_pk);
+        let members = [(&a_kp, a_pk), (&b_kp, b_pk)];
+        let handover_ix = |signer_pk: Pubkey, instruction: MetadataInstruction| {
+            authority_ix(
+                program_id,
+                vec![
+                    AccountMeta::new(metadata_pda, false),
+                    AccountMeta::new_readonly(signer_pk, true),
+                ],
+                instruction,
+            )
+        };
+        let propose = MetadataInstruction::ProposeAuthority {
+            new_authority: next_pk,
+            expected_revision: None,
+        };
+
+        let ix = handover_ix(multisig_pk, propose.clone());
+        assert_needs_members(&ctx, payer, (&multisig_kp, multisig_pk), members, ix).await?;
+        let ix = handover_ix(
+            multisig_pk,
+            MetadataInstruction::CancelProposal {
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, (&multisig_kp, multisig_pk), members, ix).await?;
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.pending_authority, None);
+
+        let ix = with_members(
+            handover_ix(multisig_pk, propose),
+            multisig_pk,
+            &[a_pk, b_pk],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // A pending multisig accepts with its members, never with its own keypair
+        let ix = handover_ix(
+            next_pk,
+            MetadataInstruction::AcceptAuthority {
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, (&next_kp, next_pk), members, ix).await?;
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.update_authority, Some(next_pk));
+        assert_eq!(md.pending_authority, None);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_authority_gates_locks_collection_and_delegates() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (_delegate_kp, delegate_pk, _) = ctx.generate_new_keypair();
+        let (mint_pk, metadata_pda) = multisig_owned_metadata(
+            &ctx,
+            program_id,
+            &payer_kp,
+            payer_pk,
+            &multisig_kp,
+            multisig_pk,
+            vec![a_pk, b_pk],
+        )
+        .await?;
+
+        let (collection_kp, collection_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx,
+            &payer_kp,
+            payer_pk,
+            &collection_kp,
+            collection_pk,
+            &payer_pk,
+            None,
+        )
+        .await?;
+        let (create_md_ix, collection_metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            collection_pk,
+            payer_pk,
+            "C",
+            "C",
+            "https://c",
+            "c",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let payer = (&payer_kp, payer_pk);
+        let multisig = (&multisig_kp, multisig_pk);
+        let members = [(&a_kp, a_pk), (&b_kp, b_pk)];
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+            ],
+            MetadataInstruction::LockFields {
+                mask: LOCK_NAME,
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        // Member signers follow the optional collection metadata account
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+                AccountMeta::new_readonly(collection_metadata_pda, false),
+            ],
+            MetadataInstruction::SetCollection {
+                collection_mint: Some(collection_pk),
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate_pk);
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(delegate_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+            ],
+            MetadataInstruction::AddDelegate {
+                delegate: delegate_pk,
+                scope: LOCK_ALL,
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(delegate_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(payer_pk, false),
+            ],
+            MetadataInstruction::RevokeDelegate {
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.locked_fields, LOCK_NAME);
+        assert_eq!(md.collection.map(|c| c.key), Some(collection_pk));
+        assert_eq!(md.delegate_count, 0);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_authority_gates_creators_touch_and_closing() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (_creator_kp, creator_pk, _) = ctx.generate_new_keypair();
+        let (mint_pk, metadata_pda) = multisig_owned_metadata(
+            &ctx,
+            program_id,
+            &payer_kp,
+            payer_pk,
+            &multisig_kp,
+            multisig_pk,
+            vec![a_pk, b_pk],
+        )
+        .await?;
+        let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+        let (touch_pda, _) = find_touch_pda_with_program(&program_id, &mint_pk);
+
+        let payer = (&payer_kp, payer_pk);
+        let multisig = (&multisig_kp, multisig_pk);
+        let members = [(&a_kp, a_pk), (&b_kp, b_pk)];
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(mint_pk, false),
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+            ],
+            MetadataInstruction::CreateAttributes {
+                data: vec![("k".into(), "v".into())],
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(creators_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+            ],
+            MetadataInstruction::SetCreators {
+                creators: vec![(creator_pk, 60), (payer_pk, 40)],
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(creators_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+                AccountMeta::new(metadata_pda, false),
+            ],
+            MetadataInstruction::RemoveCreator {
+                creator: creator_pk,
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+        let creators =
+            TokenMetadataCreators::unpack(&ctx.read_account_info(creators_pda).await?.data)
+                .unwrap();
+        assert_eq!(creators.creators.len(), 1);
+        assert_eq!(creators.creators[0].address, payer_pk);
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(touch_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+            ],
+            MetadataInstruction::Touch {
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(payer_pk, false),
+            ],
+            MetadataInstruction::CloseAttributes {
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+
+        let ix = authority_ix(
+            program_id,
+            vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(multisig_pk, true),
+                AccountMeta::new_readonly(mint_pk, false),
+                AccountMeta::new_readonly(attributes_pda, false),
+                AccountMeta::new(payer_pk, false),
+                AccountMeta::new(touch_pda, false),
+                AccountMeta::new(creators_pda, false),
+            ],
+            MetadataInstruction::CloseMetadata {
+                expected_revision: None,
+            },
+        );
+        assert_needs_members(&ctx, payer, multisig, members, ix).await?;
+        assert_closed(&ctx, metadata_pda).await?;
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_authority_manages_extensions_through_sdk() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (_dest_kp, dest_pk, _) = ctx.generate_new_keypair();
+        let (mint_pk, metadata_pda) = multisig_owned_metadata(
+            &ctx,
+            program_id,
+            &payer_kp,
+            payer_pk,
+            &multisig_kp,
+            multisig_pk,
+            vec![a_pk, b_pk],
+        )
+        .await?;
+        let client = TokenMetadataClient::new(program_id);
+        let royalty = ExtensionType::Royalty;
+        let (extension_pda, _) =
+            find_extension_pda_with_program(&program_id, &mint_pk, royalty.id());
+
+        // One member is below the threshold
+        let create = |signers: &[Pubkey]| {
+            client.create_extension_multisig_ix(
+                CreateExtensionParams {
+                    payer: payer_pk,
+                    mint: mint_pk,
+                    update_authority: multisig_pk,
+                    extension_type: royalty,
+                    data: vec![1, 2],
+                    expected_revision: None,
+                },
+                signers,
+            )
+        };
+        let status =
+            send_instructions(&ctx, payer_pk, &[create(&[a_pk])?], vec![payer_kp, a_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create(&[a_pk, b_pk])?],
+            vec![payer_kp, a_kp, b_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // Growing the payload needs the payer ahead of the member signers
+        let ix = client.update_extension_multisig_ix(
+            UpdateExtensionParams {
+                mint: mint_pk,
+                update_authority: multisig_pk,
+                payer: payer_pk,
+                extension_type: royalty,
+                data: vec![3; 64],
+                expected_revision: Some(1),
+            },
+            &[a_pk, b_pk],
+        )?;
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let extension = TokenMetadataExtension::unpack_from_slice(
+            &ctx.read_account_info(extension_pda).await?.data,
+        )?;
+        assert_eq!((extension.data, extension.revision), (vec![3; 64], 2));
+
+        let ix = client.close_extension_multisig_ix(
+            CloseExtensionParams {
+                mint: mint_pk,
+                update_authority: multisig_pk,
+                extension_type: royalty,
+                destination: dest_pk,
+                expected_revision: Some(2),
+            },
+            &[a_pk, b_pk],
+        )?;
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        assert_closed(&ctx, extension_pda).await?;
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.extension_count, 0);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn multisig_attribute_shrink_refunds_the_payer() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (collector_kp, collector_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&collector_kp).await?;
+        let (multisig_kp, multisig_pk, _) = ctx.generate_new_keypair();
+        let (a_kp, a_pk, _) = ctx.generate_new_keypair();
+        let (b_kp, b_pk, _) = ctx.generate_new_keypair();
+        let (mint_pk, _) = multisig_owned_metadata(
+            &ctx,
+            program_id,
+            &payer_kp,
+            payer_pk,
+            &multisig_kp,
+            multisig_pk,
+            vec![a_pk, b_pk],
+        )
+        .await?;
+        let client = TokenMetadataClient::new(program_id);
+        let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+
+        let entries: Vec<(String, String)> =
+            vec![("a".into(), "1".into()), ("b".into(), "x".repeat(200))];
+        let ix = client.create_attributes_multisig_ix(
+            CreateAttributesParams {
+                payer: payer_pk,
+                mint: mint_pk,
+                update_authority: multisig_pk,
+                data: entries.clone(),
+                expected_revision: None,
+            },
+            &[a_pk, b_pk],
+        )?;
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp, a_kp, b_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // The multisig cannot collect rent, so the freed lamports go to the payer
+        let collector_lamports = ctx.read_account_info(collector_pk).await?.lamports;
+        let ix = client.remove_attributes_multisig_ix(
+            RemoveAttributesParams {
+                mint: mint_pk,
+                update_authority: multisig_pk,
+                payer: collector_pk,
+                keys: vec!["b".into()],
+                expected_revision: None,
+            },
+            &[a_pk, b_pk],
+        )?;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[ix],
+            vec![payer_kp, collector_kp, a_kp, b_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let kept = [entries[0].clone()];
+        let acct = ctx.read_account_info(attributes_pda).await?;
+        assert_eq!(acct.data.len(), attributes_account_len(&kept));
+        assert_eq!(acct.lamports, minimum_rent(acct.data.len()));
+        let refund = ctx.read_account_info(collector_pk).await?.lamports - collector_lamports;
+        assert_eq!(refund as i64, -attributes_rent_delta(&entries, &kept));
+        Ok(())
+    })
+    .await
+}
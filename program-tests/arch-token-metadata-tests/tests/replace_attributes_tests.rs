@@ -8,7 +8,10 @@ use arch_token_metadata::{
     find_attributes_pda_with_program, find_metadata_pda_with_program,
     instruction::MetadataInstruction, state::TokenMetadataAttributes,
 };
-use arch_token_metadata_tests::deploy_token_metadata_program;
+use arch_token_metadata_tests::{
+    build_create_attributes_ix, build_create_metadata_ix, create_and_init_mint,
+    deploy_token_metadata_program, send_instructions,
+};
 use serial_test::serial;
 
 #[tokio::test]
@@ -39,9 +42,10 @@ async fn replace_attributes_success() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -60,6 +64,7 @@ async fn replace_attributes_success() {
         let initial = vec![("a".into(), "1".into())];
         let create_attrs = MetadataInstruction::CreateAttributes {
             data: initial.clone(),
+            expected_revision: None,
         }
         .pack();
         let create_attrs_ix = Instruction {
@@ -79,6 +84,7 @@ async fn replace_attributes_success() {
         let replacement = vec![("b".into(), "2".into())];
         let replace = MetadataInstruction::ReplaceAttributes {
             data: replacement.clone(),
+            expected_revision: None,
         }
         .pack();
         let replace_ix = Instruction {
@@ -87,6 +93,8 @@ async fn replace_attributes_success() {
                 AccountMeta::new(attrs_pda, false),
                 AccountMeta::new_readonly(payer_pk, true),
                 AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
             ],
             data: replace,
         };
@@ -111,7 +119,7 @@ async fn replace_attributes_success() {
         assert_eq!(res.status, Status::Processed);
 
         let acct = ctx.read_account_info(attrs_pda).await?;
-        let attrs = TokenMetadataAttributes::unpack(&acct.data).unwrap();
+        let attrs = TokenMetadataAttributes::unpack_from_slice(&acct.data).unwrap();
         assert_eq!(attrs.data, replacement);
         Ok(())
     })
@@ -148,9 +156,10 @@ async fn replace_attributes_wrong_signer_fails() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -166,7 +175,11 @@ async fn replace_attributes_wrong_signer_fails() {
         };
 
         let initial = vec![("a".into(), "1".into())];
-        let create_attrs = MetadataInstruction::CreateAttributes { data: initial }.pack();
+        let create_attrs = MetadataInstruction::CreateAttributes {
+            data: initial,
+            expected_revision: None,
+        }
+        .pack();
         let create_attrs_ix = Instruction {
             program_id,
             accounts: vec![
@@ -181,13 +194,19 @@ async fn replace_attributes_wrong_signer_fails() {
         };
 
         let replacement = vec![("b".into(), "2".into())];
-        let replace = MetadataInstruction::ReplaceAttributes { data: replacement }.pack();
+        let replace = MetadataInstruction::ReplaceAttributes {
+            data: replacement,
+            expected_revision: None,
+        }
+        .pack();
         let replace_ix = Instruction {
             program_id,
             accounts: vec![
                 AccountMeta::new(attrs_pda, false),
                 AccountMeta::new_readonly(wrong_pk, true),
                 AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(wrong_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
             ],
             data: replace,
         };
@@ -244,9 +263,10 @@ async fn replace_attributes_empty_key_or_value_fails() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "N".into(),
             symbol: "S".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -262,7 +282,11 @@ async fn replace_attributes_empty_key_or_value_fails() {
         };
 
         let initial = vec![("a".into(), "1".into())];
-        let create_attrs = MetadataInstruction::CreateAttributes { data: initial }.pack();
+        let create_attrs = MetadataInstruction::CreateAttributes {
+            data: initial,
+            expected_revision: None,
+        }
+        .pack();
         let create_attrs_ix = Instruction {
             program_id,
             accounts: vec![
@@ -278,13 +302,19 @@ async fn replace_attributes_empty_key_or_value_fails() {
 
         // now attempt replace with empty key/value entries
         let replacement = vec![("".into(), "2".into()), ("b".into(), "".into())];
-        let replace = MetadataInstruction::ReplaceAttributes { data: replacement }.pack();
+        let replace = MetadataInstruction::ReplaceAttributes {
+            data: replacement,
+            expected_revision: None,
+        }
+        .pack();
         let replace_ix = Instruction {
             program_id,
             accounts: vec![
                 AccountMeta::new(attrs_pda, false),
                 AccountMeta::new_readonly(payer_pk, true),
                 AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
             ],
             data: replace,
         };
@@ -311,3 +341,237 @@ async fn replace_attributes_empty_key_or_value_fails() {
     })
     .await
 }
+
+fn replace_attributes_ix(
+    program_id: Pubkey,
+    attrs_pda: Pubkey,
+    update_authority_pk: Pubkey,
+    metadata_pda: Pubkey,
+    data: Vec<(String, String)>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(attrs_pda, false),
+            AccountMeta::new_readonly(update_authority_pk, true),
+            AccountMeta::new_readonly(metadata_pda, false),
+            AccountMeta::new(update_authority_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+        ],
+        data: MetadataInstruction::ReplaceAttributes {
+            data,
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+// Attributes of mint A must not be replaceable through the metadata of mint B,
+// even when the same key is update authority of both.
+#[tokio::test]
+#[serial]
+async fn replace_attributes_mismatched_mint_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_a_kp, mint_a_pk, _) = ctx.generate_new_keypair();
+        let (mint_b_kp, mint_b_pk, _) = ctx.generate_new_keypair();
+        for (mint_kp, mint_pk) in [(&mint_a_kp, mint_a_pk), (&mint_b_kp, mint_b_pk)] {
+            create_and_init_mint(&ctx, &payer_kp, payer_pk, mint_kp, mint_pk, &payer_pk, None)
+                .await?;
+            let (create_md_ix, _) = build_create_metadata_ix(
+                program_id,
+                payer_pk,
+                mint_pk,
+                payer_pk,
+                "N",
+                "S",
+                "https://i",
+                "d",
+                false,
+            )
+            .await;
+            let create_attrs_ix = build_create_attributes_ix(
+                program_id,
+                payer_pk,
+                mint_pk,
+                payer_pk,
+                vec![("a".into(), "1".into())],
+            );
+            let status = send_instructions(
+                &ctx,
+                payer_pk,
+                &[create_md_ix, create_attrs_ix],
+                vec![payer_kp],
+            )
+            .await?;
+            assert_eq!(status, Status::Processed);
+        }
+
+        let (attrs_a_pda, _ba) = find_attributes_pda_with_program(&program_id, &mint_a_pk);
+        let (metadata_b_pda, _bmd) = find_metadata_pda_with_program(&program_id, &mint_b_pk);
+        let replace_ix = replace_attributes_ix(
+            program_id,
+            attrs_a_pda,
+            payer_pk,
+            metadata_b_pda,
+            vec![("b".into(), "2".into())],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[replace_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let attrs = TokenMetadataAttributes::unpack_from_slice(
+            &ctx.read_account_info(attrs_a_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!(attrs.mint, mint_a_pk);
+        assert_eq!(attrs.data, vec![("a".to_string(), "1".to_string())]);
+        Ok(())
+    })
+    .await
+}
+
+// A former authority who still controls the mint can mint look-alike metadata under
+// another deployment of this program. That foreign-owned account must not authorize
+// attribute writes against the real metadata.
+#[tokio::test]
+#[serial]
+async fn replace_attributes_foreign_owned_metadata_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let foreign_program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (_owner_kp, owner_pk, _) = ctx.generate_new_keypair();
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        // Real metadata + attributes, then hand update authority to a new owner
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("a".into(), "1".into())],
+        );
+        let transfer_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+            ],
+            data: MetadataInstruction::TransferAuthority {
+                new_authority: owner_pk,
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix, transfer_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // Look-alike metadata for the same mint, owned by another program, naming payer
+        let (spoof_md_ix, spoofed_md_pda) = build_create_metadata_ix(
+            foreign_program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[spoof_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (attrs_pda, _ba) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let replace_ix = replace_attributes_ix(
+            program_id,
+            attrs_pda,
+            payer_pk,
+            spoofed_md_pda,
+            vec![("hijacked".into(), "yes".into())],
+        );
+        let status = send_instructions(&ctx, payer_pk, &[replace_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let attrs = TokenMetadataAttributes::unpack_from_slice(
+            &ctx.read_account_info(attrs_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!(attrs.data, vec![("a".to_string(), "1".to_string())]);
+        Ok(())
+    })
+    .await
+}
+
+// CreateAttributes must likewise refuse a foreign-owned metadata account.
+#[tokio::test]
+#[serial]
+async fn create_attributes_foreign_owned_metadata_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let foreign_program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (spoof_md_ix, spoofed_md_pda) = build_create_metadata_ix(
+            foreign_program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[spoof_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let mut create_attrs_ix = build_create_attributes_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            vec![("a".into(), "1".into())],
+        );
+        create_attrs_ix.accounts[5].pubkey = spoofed_md_pda;
+        let status = send_instructions(&ctx, payer_pk, &[create_attrs_ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
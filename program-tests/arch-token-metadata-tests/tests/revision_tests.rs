@@ -0,0 +1,428 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_attributes_pda_with_program, find_creators_pda_with_program,
+    find_delegate_pda_with_program, find_metadata_pda_with_program, find_touch_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{TokenMetadata, TokenMetadataAttributes, TokenMetadataCreators, LOCK_IMAGE},
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions, send_instructions_with_logs,
+};
+use serial_test::serial;
+
+fn update_name_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    name: &str,
+    expected_revision: Option<u64>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+        ],
+        data: MetadataInstruction::UpdateMetadata {
+            name: Some(name.to_string()),
+            symbol: None,
+            image: None,
+            description: None,
+            uri: None,
+            expected_revision,
+        }
+        .pack(),
+    }
+}
+
+fn upsert_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    authority_pk: Pubkey,
+    data: Vec<(String, String)>,
+    expected_revision: Option<u64>,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(attributes_pda, false),
+            AccountMeta::new_readonly(authority_pk, true),
+            AccountMeta::new_readonly(metadata_pda, false),
+            AccountMeta::new(authority_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+        ],
+        data: MetadataInstruction::UpsertAttributes {
+            data,
+            expected_revision,
+        }
+        .pack(),
+    }
+}
+
+fn delegate_ixs(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    payer_pk: Pubkey,
+    delegate: Pubkey,
+    expected_revision: Option<u64>,
+) -> (Instruction, Instruction) {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (delegate_pda, _) = find_delegate_pda_with_program(&program_id, &mint_pk, &delegate);
+    let add = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(delegate_pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+        ],
+        data: MetadataInstruction::AddDelegate {
+            delegate,
+            scope: LOCK_IMAGE,
+            expected_revision,
+        }
+        .pack(),
+    };
+    let revoke = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(delegate_pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(payer_pk, false),
+        ],
+        data: MetadataInstruction::RevokeDelegate { expected_revision }.pack(),
+    };
+    (add, revoke)
+}
+
+/// SetCreators or Touch, signed by `payer_pk` as update authority
+fn authority_write_ix(
+    program_id: Pubkey,
+    mint_pk: Pubkey,
+    payer_pk: Pubkey,
+    pda: Pubkey,
+    instruction: MetadataInstruction,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(pda, false),
+            AccountMeta::new_readonly(payer_pk, true),
+        ],
+        data: instruction.pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn stale_expected_revision_rejects_metadata_update() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let created =
+            TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(created.revision, 1);
+        assert!(created.last_updated_slot > 0);
+
+        // Two operators read revision 1; the first write wins
+        let ix = update_name_ix(program_id, mint_pk, payer_pk, "First", Some(1));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = update_name_ix(program_id, mint_pk, payer_pk, "Second", Some(1));
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Stale revision")));
+
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.name, "First");
+        assert_eq!(md.revision, 2);
+        assert!(md.last_updated_slot >= created.last_updated_slot);
+
+        // Omitting the expected revision keeps last-writer-wins behavior
+        let ix = update_name_ix(program_id, mint_pk, payer_pk, "Third", None);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!((md.name.as_str(), md.revision), ("Third", 3));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn attributes_track_their_own_revision() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+        let (attributes_pda, _) = find_attributes_pda_with_program(&program_id, &mint_pk);
+        let create_attrs_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(mint_pk, false),
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(payer_pk, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+            ],
+            data: MetadataInstruction::CreateAttributes {
+                data: vec![("a".into(), "1".into())],
+                expected_revision: None,
+            }
+            .pack(),
+        };
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[create_md_ix, create_attrs_ix],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        // Metadata writes do not move the attributes revision
+        let ix = update_name_ix(program_id, mint_pk, payer_pk, "Renamed", Some(1));
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = upsert_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            vec![("b".into(), "2".into())],
+            Some(1),
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = upsert_ix(
+            program_id,
+            mint_pk,
+            payer_pk,
+            vec![("c".into(), "3".into())],
+            Some(1),
+        );
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+
+        let attrs = TokenMetadataAttributes::unpack_from_slice(
+            &ctx.read_account_info(attributes_pda).await?.data,
+        )
+        .unwrap();
+        assert_eq!(attrs.revision, 2);
+        assert_eq!(attrs.get("c"), None);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn delegate_changes_advance_and_check_metadata_revision() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let (_, first, _) = ctx.generate_new_keypair();
+        let (add, _) = delegate_ixs(program_id, mint_pk, payer_pk, first, Some(1));
+        let status = send_instructions(&ctx, payer_pk, &[add], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!((md.delegate_count, md.revision), (1, 2));
+
+        // A second operator still holding revision 1 is rejected
+        let (_, second, _) = ctx.generate_new_keypair();
+        let (add, _) = delegate_ixs(program_id, mint_pk, payer_pk, second, Some(1));
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[add], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Stale revision")));
+
+        // Re-granting an open record is a write too, so only one of two re-grants lands
+        let (regrant, _) = delegate_ixs(program_id, mint_pk, payer_pk, first, Some(2));
+        let status = send_instructions(&ctx, payer_pk, &[regrant], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!((md.delegate_count, md.revision), (1, 3));
+
+        let (regrant, _) = delegate_ixs(program_id, mint_pk, payer_pk, first, Some(2));
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[regrant], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Stale revision")));
+
+        let (_, revoke) = delegate_ixs(program_id, mint_pk, payer_pk, first, Some(3));
+        let status = send_instructions(&ctx, payer_pk, &[revoke], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!((md.delegate_count, md.revision), (0, 4));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn creators_and_touch_writes_advance_metadata_revision() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, metadata_pda) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        // Two operators read revision 1 and each set their own list; the second is rejected
+        let (creators_pda, _) = find_creators_pda_with_program(&program_id, &mint_pk);
+        let (_, artist, _) = ctx.generate_new_keypair();
+        let (_, other, _) = ctx.generate_new_keypair();
+        let set_creators = |creator: Pubkey| {
+            authority_write_ix(
+                program_id,
+                mint_pk,
+                payer_pk,
+                creators_pda,
+                MetadataInstruction::SetCreators {
+                    creators: vec![(creator, 100)],
+                    expected_revision: Some(1),
+                },
+            )
+        };
+        let status =
+            send_instructions(&ctx, payer_pk, &[set_creators(artist)], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[set_creators(other)], vec![payer_kp])
+                .await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Stale revision")));
+
+        let creators =
+            TokenMetadataCreators::unpack(&ctx.read_account_info(creators_pda).await?.data)
+                .unwrap();
+        assert_eq!(creators.creators.len(), 1);
+        assert_eq!(creators.creators[0].address, artist);
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.revision, 2);
+
+        // Touch advances the revision as well
+        let (touch_pda, _) = find_touch_pda_with_program(&program_id, &mint_pk);
+        let touch = || {
+            authority_write_ix(
+                program_id,
+                mint_pk,
+                payer_pk,
+                touch_pda,
+                MetadataInstruction::Touch {
+                    expected_revision: Some(2),
+                },
+            )
+        };
+        let status = send_instructions(&ctx, payer_pk, &[touch()], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+        let (status, logs) =
+            send_instructions_with_logs(&ctx, payer_pk, &[touch()], vec![payer_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        assert!(logs.iter().any(|l| l.contains("Stale revision")));
+        let md = TokenMetadata::unpack(&ctx.read_account_info(metadata_pda).await?.data).unwrap();
+        assert_eq!(md.revision, 3);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,136 @@
+use arch_program::{
+    account::AccountMeta, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+};
+use arch_sdk::Status;
+use arch_testing::TestRunner;
+use arch_token_metadata::{
+    find_metadata_pda_with_program, find_touch_pda_with_program,
+    instruction::MetadataInstruction,
+    state::{AccountType, TokenMetadataTouch},
+};
+use arch_token_metadata_tests::{
+    build_create_metadata_ix, create_and_init_mint, deploy_token_metadata_program,
+    send_instructions,
+};
+use serial_test::serial;
+
+fn touch_ix(
+    program_id: Pubkey,
+    payer_pk: Pubkey,
+    mint_pk: Pubkey,
+    authority: Pubkey,
+) -> Instruction {
+    let (metadata_pda, _) = find_metadata_pda_with_program(&program_id, &mint_pk);
+    let (touch_pda, _) = find_touch_pda_with_program(&program_id, &mint_pk);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(Pubkey::system_program(), false),
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new(touch_pda, false),
+            AccountMeta::new_readonly(authority, true),
+        ],
+        data: MetadataInstruction::Touch {
+            expected_revision: None,
+        }
+        .pack(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn touch_creates_pda_and_increments_counter() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(
+            &ctx,
+            payer_pk,
+            &[
+                create_md_ix,
+                touch_ix(program_id, payer_pk, mint_pk, payer_pk),
+            ],
+            vec![payer_kp],
+        )
+        .await?;
+        assert_eq!(status, Status::Processed);
+
+        let (touch_pda, touch_bump) = find_touch_pda_with_program(&program_id, &mint_pk);
+        let acct = ctx.read_account_info(touch_pda).await?;
+        assert_eq!(acct.owner, program_id);
+        let first = TokenMetadataTouch::unpack(&acct.data).unwrap();
+        assert_eq!(first.account_type, AccountType::Touch);
+        assert_eq!(first.mint, mint_pk);
+        assert_eq!(first.touch_count, 1);
+        assert_eq!(first.bump, Some(touch_bump));
+
+        let ix = touch_ix(program_id, payer_pk, mint_pk, payer_pk);
+        let status = send_instructions(&ctx, payer_pk, &[ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let acct = ctx.read_account_info(touch_pda).await?;
+        let second = TokenMetadataTouch::unpack(&acct.data).unwrap();
+        assert_eq!(second.touch_count, 2);
+        assert!(second.last_touched_slot >= first.last_touched_slot);
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn touch_wrong_authority_fails() {
+    TestRunner::run(|ctx| async move {
+        let program_id = deploy_token_metadata_program(&ctx).await?;
+        let (payer_kp, payer_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&payer_kp).await?;
+        let (wrong_kp, wrong_pk, _) = ctx.generate_new_keypair();
+        ctx.fund_keypair_with_faucet(&wrong_kp).await?;
+        let (mint_kp, mint_pk, _) = ctx.generate_new_keypair();
+        create_and_init_mint(
+            &ctx, &payer_kp, payer_pk, &mint_kp, mint_pk, &payer_pk, None,
+        )
+        .await?;
+
+        let (create_md_ix, _) = build_create_metadata_ix(
+            program_id,
+            payer_pk,
+            mint_pk,
+            payer_pk,
+            "N",
+            "S",
+            "https://i",
+            "d",
+            false,
+        )
+        .await;
+        let status = send_instructions(&ctx, payer_pk, &[create_md_ix], vec![payer_kp]).await?;
+        assert_eq!(status, Status::Processed);
+
+        let ix = touch_ix(program_id, wrong_pk, mint_pk, wrong_pk);
+        let status = send_instructions(&ctx, wrong_pk, &[ix], vec![wrong_kp]).await?;
+        assert!(matches!(status, Status::Failed(_)));
+        Ok(())
+    })
+    .await
+}
@@ -39,6 +39,7 @@ async fn update_metadata_success() {
             image: "https://i".into(),
             description: "desc".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -59,6 +60,8 @@ async fn update_metadata_success() {
             symbol: Some("NS".into()),
             image: None,
             description: None,
+            uri: None,
+            expected_revision: None,
         }
         .pack();
         let upd_ix = Instruction {
@@ -122,9 +125,10 @@ async fn transfer_authority_and_make_immutable() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "Token".to_string(),
             symbol: "TOK".to_string(),
-            image: "i".to_string(),
+            image: "https://i".to_string(),
             description: "d".to_string(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let ix = Instruction {
@@ -141,7 +145,7 @@ async fn transfer_authority_and_make_immutable() {
         let recent = ctx.get_recent_blockhash().await?;
         let msg = ArchMessage::new(&[ix], Some(payer_pk), recent.parse()?);
         let tx = ctx
-            .build_and_sign_transaction(msg, vec![payer_kp, auth_a_kp.clone()])
+            .build_and_sign_transaction(msg, vec![payer_kp, auth_a_kp])
             .await?;
         let txid = ctx.send_transaction(tx).await?;
         let res = ctx.wait_for_transaction(&txid).await?;
@@ -152,6 +156,7 @@ async fn transfer_authority_and_make_immutable() {
         ctx.fund_keypair_with_faucet(&auth_b_kp).await?;
         let xfer = MetadataInstruction::TransferAuthority {
             new_authority: auth_b_pk,
+            expected_revision: None,
         }
         .pack();
         let ix = Instruction {
@@ -165,7 +170,7 @@ async fn transfer_authority_and_make_immutable() {
         let recent = ctx.get_recent_blockhash().await?;
         let msg = ArchMessage::new(&[ix], Some(payer_pk), recent.parse()?);
         let tx = ctx
-            .build_and_sign_transaction(msg, vec![payer_kp, auth_a_kp.clone()])
+            .build_and_sign_transaction(msg, vec![payer_kp, auth_a_kp])
             .await?;
         let txid = ctx.send_transaction(tx).await?;
         let res = ctx.wait_for_transaction(&txid).await?;
@@ -177,6 +182,8 @@ async fn transfer_authority_and_make_immutable() {
             symbol: None,
             image: None,
             description: None,
+            uri: None,
+            expected_revision: None,
         }
         .pack();
         let ix = Instruction {
@@ -202,6 +209,8 @@ async fn transfer_authority_and_make_immutable() {
             symbol: None,
             image: None,
             description: None,
+            uri: None,
+            expected_revision: None,
         }
         .pack();
         let ix = Instruction {
@@ -222,7 +231,10 @@ async fn transfer_authority_and_make_immutable() {
         assert_eq!(res.status, Status::Processed);
 
         // Make immutable by B
-        let make_imm = MetadataInstruction::MakeImmutable.pack();
+        let make_imm = MetadataInstruction::MakeImmutable {
+            expected_revision: None,
+        }
+        .pack();
         let ix = Instruction {
             program_id,
             accounts: vec![
@@ -243,6 +255,7 @@ async fn transfer_authority_and_make_immutable() {
         // Further transfer or update should fail
         let xfer_again = MetadataInstruction::TransferAuthority {
             new_authority: auth_a_pk,
+            expected_revision: None,
         }
         .pack();
         let ix = Instruction {
@@ -294,6 +307,7 @@ async fn update_metadata_wrong_authority_fails() {
             image: "https://i".into(),
             description: "desc".into(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let create_md_ix = Instruction {
@@ -314,6 +328,8 @@ async fn update_metadata_wrong_authority_fails() {
             symbol: None,
             image: None,
             description: None,
+            uri: None,
+            expected_revision: None,
         }
         .pack();
         let upd_ix = Instruction {
@@ -369,6 +385,7 @@ async fn update_metadata_immutable_fails() {
             image: "https://i".into(),
             description: "desc".into(),
             immutable: true,
+            uri: None,
         }
         .pack();
 
@@ -389,6 +406,8 @@ async fn update_metadata_immutable_fails() {
             symbol: None,
             image: None,
             description: None,
+            uri: None,
+            expected_revision: None,
         }
         .pack();
         let upd_ix = Instruction {
@@ -453,9 +472,10 @@ async fn transfer_authority_wrong_signer_fails() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "Token".to_string(),
             symbol: "TOK".to_string(),
-            image: "i".to_string(),
+            image: "https://i".to_string(),
             description: "d".to_string(),
             immutable: false,
+            uri: None,
         }
         .pack();
         let ix = Instruction {
@@ -472,7 +492,7 @@ async fn transfer_authority_wrong_signer_fails() {
         let recent = ctx.get_recent_blockhash().await?;
         let msg = ArchMessage::new(&[ix], Some(payer_pk), recent.parse()?);
         let tx = ctx
-            .build_and_sign_transaction(msg, vec![payer_kp, auth_a_kp.clone()])
+            .build_and_sign_transaction(msg, vec![payer_kp, auth_a_kp])
             .await?;
         let txid = ctx.send_transaction(tx).await?;
         let res = ctx.wait_for_transaction(&txid).await?;
@@ -481,6 +501,7 @@ async fn transfer_authority_wrong_signer_fails() {
         // Wrong signer attempts transfer A -> T (signed by wrong_pk instead of auth_a_pk)
         let xfer = MetadataInstruction::TransferAuthority {
             new_authority: target_pk,
+            expected_revision: None,
         }
         .pack();
         let ix = Instruction {
@@ -536,9 +557,10 @@ async fn make_immutable_already_immutable_fails() {
         let create_md = MetadataInstruction::CreateMetadata {
             name: "Name".into(),
             symbol: "SYM".into(),
-            image: "i".into(),
+            image: "https://i".into(),
             description: "d".into(),
             immutable: true,
+            uri: None,
         }
         .pack();
         let ix = Instruction {
@@ -555,14 +577,17 @@ async fn make_immutable_already_immutable_fails() {
         let recent = ctx.get_recent_blockhash().await?;
         let msg = ArchMessage::new(&[ix], Some(payer_pk), recent.parse()?);
         let tx = ctx
-            .build_and_sign_transaction(msg, vec![payer_kp, auth_kp.clone()])
+            .build_and_sign_transaction(msg, vec![payer_kp, auth_kp])
             .await?;
         let txid = ctx.send_transaction(tx).await?;
         let res = ctx.wait_for_transaction(&txid).await?;
         assert_eq!(res.status, Status::Processed);
 
         // Attempt MakeImmutable should fail since update_authority is None
-        let make_imm = MetadataInstruction::MakeImmutable.pack();
+        let make_imm = MetadataInstruction::MakeImmutable {
+            expected_revision: None,
+        }
+        .pack();
         let ix = Instruction {
             program_id,
             accounts: vec![
@@ -0,0 +1,497 @@
+//! Typed CPI wrappers for programs that call into the token metadata program
+//!
+//! Enabled by the `cpi` feature (which implies `no-entrypoint`). Each wrapper takes the
+//! accounts as `AccountInfo`s, builds the instruction with the account order the processor
+//! expects, and invokes it. Pass PDA signer seeds when a signing account (e.g. a mint
+//! authority) is a PDA of the calling program; pass `&[]` otherwise.
+
+use {
+    crate::{
+        instruction::{get_metadata_instruction, MetadataInstruction},
+        state::{ContentUri, TokenMetadataSummary},
+    },
+    arch_program::{
+        account::{AccountInfo, AccountMeta},
+        entrypoint::ProgramResult,
+        instruction::Instruction,
+        msg,
+        program::{get_return_data, invoke, invoke_signed},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+/// Accounts for `create_metadata`
+pub struct CreateMetadataAccounts<'a, 'b> {
+    /// Funds the metadata account (writable, signer)
+    pub payer: &'b AccountInfo<'a>,
+    /// System program
+    pub system_program: &'b AccountInfo<'a>,
+    /// Token mint
+    pub mint: &'b AccountInfo<'a>,
+    /// Metadata PDA of `mint` (writable)
+    pub metadata: &'b AccountInfo<'a>,
+    /// Mint authority, or freeze authority if the mint authority is revoked (signer)
+    pub mint_authority: &'b AccountInfo<'a>,
+}
+
+/// Arguments for `create_metadata`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateMetadataArgs {
+    /// The name of the token
+    pub name: String,
+    /// The symbol of the token
+    pub symbol: String,
+    /// The image URI for the token
+    pub image: String,
+    /// The description of the token
+    pub description: String,
+    /// If true, metadata is immutable (no updates allowed)
+    pub immutable: bool,
+    /// Optional off-chain JSON URI and content hash commitment
+    pub uri: Option<ContentUri>,
+}
+
+/// Accounts for `update_metadata`
+pub struct UpdateMetadataAccounts<'a, 'b> {
+    /// Metadata PDA (writable)
+    pub metadata: &'b AccountInfo<'a>,
+    /// Who authorizes the update
+    pub authority: UpdateMetadataAuthority<'a, 'b>,
+}
+
+/// How an `update_metadata` call is authorized. Signers may be PDAs of the calling program
+/// signing through `signer_seeds`.
+pub enum UpdateMetadataAuthority<'a, 'b> {
+    /// The single-key update authority (signer)
+    Single(&'b AccountInfo<'a>),
+    /// A delegate (signer) whose record scopes it to every field being updated
+    Delegate {
+        /// The delegate key (signer)
+        delegate: &'b AccountInfo<'a>,
+        /// Delegate record PDA for `delegate`
+        record: &'b AccountInfo<'a>,
+    },
+    /// A multisig update authority, passed as a non-signer, and at least `m` of its members
+    Multisig {
+        /// The multisig account
+        multisig: &'b AccountInfo<'a>,
+        /// Signing members (signers)
+        signers: &'b [AccountInfo<'a>],
+    },
+}
+
+/// Arguments for `update_metadata`; `None` leaves a field unchanged
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateMetadataArgs {
+    /// Optional new name for the token
+    pub name: Option<String>,
+    /// Optional new symbol for the token
+    pub symbol: Option<String>,
+    /// Optional new image URI for the token
+    pub image: Option<String>,
+    /// Optional new description for the token
+    pub description: Option<String>,
+    /// Optional new off-chain JSON URI and content hash commitment
+    pub uri: Option<ContentUri>,
+    /// If set, the update fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Create metadata for a mint via CPI
+pub fn create_metadata(
+    program_id: &Pubkey,
+    accounts: CreateMetadataAccounts,
+    args: CreateMetadataArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let (instruction, account_infos) = create_metadata_instruction(program_id, &accounts, args);
+    invoke_with_seeds(&instruction, &account_infos, signer_seeds)
+}
+
+/// Update core metadata fields via CPI, as the update authority, a delegate or a multisig
+/// (see `UpdateMetadataAuthority`)
+pub fn update_metadata(
+    program_id: &Pubkey,
+    accounts: UpdateMetadataAccounts,
+    args: UpdateMetadataArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let (instruction, account_infos) = update_metadata_instruction(program_id, &accounts, args);
+    invoke_with_seeds(&instruction, &account_infos, signer_seeds)
+}
+
+/// Build the `CreateMetadata` instruction `create_metadata` invokes, with its account infos
+/// in matching order
+pub fn create_metadata_instruction<'a>(
+    program_id: &Pubkey,
+    accounts: &CreateMetadataAccounts<'a, '_>,
+    args: CreateMetadataArgs,
+) -> (Instruction, Vec<AccountInfo<'a>>) {
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*accounts.payer.key, true),
+            AccountMeta::new_readonly(*accounts.system_program.key, false),
+            AccountMeta::new_readonly(*accounts.mint.key, false),
+            AccountMeta::new(*accounts.metadata.key, false),
+            AccountMeta::new_readonly(*accounts.mint_authority.key, true),
+        ],
+        data: MetadataInstruction::CreateMetadata {
+            name: args.name,
+            symbol: args.symbol,
+            image: args.image,
+            description: args.description,
+            immutable: args.immutable,
+            uri: args.uri,
+        }
+        .pack(),
+    };
+    let account_infos = vec![
+        accounts.payer.clone(),
+        accounts.system_program.clone(),
+        accounts.mint.clone(),
+        accounts.metadata.clone(),
+        accounts.mint_authority.clone(),
+    ];
+    (instruction, account_infos)
+}
+
+/// Build the `UpdateMetadata` instruction `update_metadata` invokes, with its account infos
+/// in matching order
+pub fn update_metadata_instruction<'a>(
+    program_id: &Pubkey,
+    accounts: &UpdateMetadataAccounts<'a, '_>,
+    args: UpdateMetadataArgs,
+) -> (Instruction, Vec<AccountInfo<'a>>) {
+    let mut metas = vec![AccountMeta::new(*accounts.metadata.key, false)];
+    let mut account_infos = vec![accounts.metadata.clone()];
+    match accounts.authority {
+        UpdateMetadataAuthority::Single(authority) => {
+            metas.push(AccountMeta::new_readonly(*authority.key, true));
+            account_infos.push(authority.clone());
+        }
+        UpdateMetadataAuthority::Delegate { delegate, record } => {
+            metas.push(AccountMeta::new_readonly(*delegate.key, true));
+            metas.push(AccountMeta::new_readonly(*record.key, false));
+            account_infos.extend([delegate.clone(), record.clone()]);
+        }
+        UpdateMetadataAuthority::Multisig { multisig, signers } => {
+            metas.push(AccountMeta::new_readonly(*multisig.key, false));
+            metas.extend(
+                signers
+                    .iter()
+                    .map(|info| AccountMeta::new_readonly(*info.key, true)),
+            );
+            account_infos.push(multisig.clone());
+            account_infos.extend(signers.iter().cloned());
+        }
+    }
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: metas,
+        data: MetadataInstruction::UpdateMetadata {
+            name: args.name,
+            symbol: args.symbol,
+            image: args.image,
+            description: args.description,
+            uri: args.uri,
+            expected_revision: args.expected_revision,
+        }
+        .pack(),
+    };
+    (instruction, account_infos)
+}
+
+/// Invoke `GetMetadata` and decode the returned summary.
+///
+/// The token metadata program validates that `metadata_info` is the canonical PDA for
+/// `mint_info`, so callers do not need to know the seeds or the account layout.
+pub fn get_metadata<'a>(
+    program_id: &Pubkey,
+    metadata_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+) -> Result<TokenMetadataSummary, ProgramError> {
+    invoke(
+        &get_metadata_instruction(program_id, metadata_info.key, mint_info.key),
+        &[metadata_info.clone(), mint_info.clone()],
+    )?;
+
+    match get_return_data() {
+        Some((returned_by, data)) if returned_by == *program_id => {
+            TokenMetadataSummary::decode(&data)
+        }
+        _ => {
+            msg!("Token metadata program returned no data");
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+}
+
+fn invoke_with_seeds(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    if signer_seeds.is_empty() {
+        invoke(instruction, account_infos)
+    } else {
+        invoke_signed(instruction, account_infos, signer_seeds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::state::ContentHashAlgorithm, arch_program::utxo::UtxoMeta};
+
+    /// Backing storage for the `AccountInfo`s handed to the builders
+    struct TestAccounts {
+        keys: Vec<Pubkey>,
+        lamports: Vec<u64>,
+        data: Vec<Vec<u8>>,
+        owner: Pubkey,
+        utxo: UtxoMeta,
+    }
+
+    impl TestAccounts {
+        fn new(keys: &[Pubkey]) -> Self {
+            Self {
+                keys: keys.to_vec(),
+                lamports: vec![0; keys.len()],
+                data: vec![Vec::new(); keys.len()],
+                owner: Pubkey::system_program(),
+                utxo: UtxoMeta::default(),
+            }
+        }
+
+        fn infos(&mut self) -> Vec<AccountInfo<'_>> {
+            self.keys
+                .iter()
+                .zip(self.lamports.iter_mut())
+                .zip(self.data.iter_mut())
+                .map(|((key, lamports), data)| {
+                    AccountInfo::new(
+                        key,
+                        lamports,
+                        data,
+                        &self.owner,
+                        &self.utxo,
+                        false,
+                        false,
+                        false,
+                    )
+                })
+                .collect()
+        }
+    }
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::from_slice(&[byte; 32])
+    }
+
+    fn content_uri() -> ContentUri {
+        ContentUri {
+            uri: "https://example.com/token.json".to_string(),
+            content_hash: [7; 32],
+            hash_algorithm: ContentHashAlgorithm::Sha256,
+        }
+    }
+
+    /// Check the program id, account metas and decoded data, and that the account infos follow
+    /// the metas
+    fn assert_instruction(
+        (instruction, account_infos): (Instruction, Vec<AccountInfo>),
+        program_id: Pubkey,
+        accounts: Vec<AccountMeta>,
+        data: MetadataInstruction,
+    ) {
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts, accounts);
+        assert_eq!(
+            MetadataInstruction::unpack(&instruction.data).unwrap(),
+            data
+        );
+        let info_keys: Vec<Pubkey> = account_infos.iter().map(|info| *info.key).collect();
+        let meta_keys: Vec<Pubkey> = accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(info_keys, meta_keys);
+    }
+
+    fn update_args() -> UpdateMetadataArgs {
+        UpdateMetadataArgs {
+            name: Some("Renamed".to_string()),
+            image: Some("https://example.com/new.png".to_string()),
+            uri: Some(content_uri()),
+            expected_revision: Some(7),
+            ..Default::default()
+        }
+    }
+
+    fn update_data() -> MetadataInstruction {
+        MetadataInstruction::UpdateMetadata {
+            name: Some("Renamed".to_string()),
+            symbol: None,
+            image: Some("https://example.com/new.png".to_string()),
+            description: None,
+            uri: Some(content_uri()),
+            expected_revision: Some(7),
+        }
+    }
+
+    #[test]
+    fn create_metadata_instruction_follows_the_processor_order() {
+        let (program_id, payer, mint, metadata, mint_authority) =
+            (key(1), key(2), key(3), key(4), key(5));
+        let mut accounts = TestAccounts::new(&[
+            payer,
+            Pubkey::system_program(),
+            mint,
+            metadata,
+            mint_authority,
+        ]);
+        let infos = accounts.infos();
+
+        let built = create_metadata_instruction(
+            &program_id,
+            &CreateMetadataAccounts {
+                payer: &infos[0],
+                system_program: &infos[1],
+                mint: &infos[2],
+                metadata: &infos[3],
+                mint_authority: &infos[4],
+            },
+            CreateMetadataArgs {
+                name: "Name".to_string(),
+                symbol: "SYM".to_string(),
+                image: "https://example.com/token.png".to_string(),
+                description: "d".to_string(),
+                immutable: true,
+                uri: Some(content_uri()),
+            },
+        );
+        assert_instruction(
+            built,
+            program_id,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(metadata, false),
+                AccountMeta::new_readonly(mint_authority, true),
+            ],
+            MetadataInstruction::CreateMetadata {
+                name: "Name".to_string(),
+                symbol: "SYM".to_string(),
+                image: "https://example.com/token.png".to_string(),
+                description: "d".to_string(),
+                immutable: true,
+                uri: Some(content_uri()),
+            },
+        );
+    }
+
+    #[test]
+    fn update_metadata_instruction_as_update_authority() {
+        let (program_id, metadata, authority) = (key(1), key(4), key(5));
+        let mut accounts = TestAccounts::new(&[metadata, authority]);
+        let infos = accounts.infos();
+
+        let built = update_metadata_instruction(
+            &program_id,
+            &UpdateMetadataAccounts {
+                metadata: &infos[0],
+                authority: UpdateMetadataAuthority::Single(&infos[1]),
+            },
+            update_args(),
+        );
+        assert_instruction(
+            built,
+            program_id,
+            vec![
+                AccountMeta::new(metadata, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+            update_data(),
+        );
+    }
+
+    #[test]
+    fn update_metadata_instruction_as_delegate_appends_the_record() {
+        let (program_id, metadata, delegate, record) = (key(1), key(4), key(6), key(7));
+        let mut accounts = TestAccounts::new(&[metadata, delegate, record]);
+        let infos = accounts.infos();
+
+        let built = update_metadata_instruction(
+            &program_id,
+            &UpdateMetadataAccounts {
+                metadata: &infos[0],
+                authority: UpdateMetadataAuthority::Delegate {
+                    delegate: &infos[1],
+                    record: &infos[2],
+                },
+            },
+            update_args(),
+        );
+        assert_instruction(
+            built,
+            program_id,
+            vec![
+                AccountMeta::new(metadata, false),
+                AccountMeta::new_readonly(delegate, true),
+                AccountMeta::new_readonly(record, false),
+            ],
+            update_data(),
+        );
+    }
+
+    #[test]
+    fn update_metadata_instruction_as_multisig_appends_the_members() {
+        let (program_id, metadata, multisig) = (key(1), key(4), key(8));
+        let members = [key(9), key(10)];
+        let mut accounts = TestAccounts::new(&[metadata, multisig]);
+        let infos = accounts.infos();
+        let mut member_accounts = TestAccounts::new(&members);
+        let signers = member_accounts.infos();
+
+        let built = update_metadata_instruction(
+            &program_id,
+            &UpdateMetadataAccounts {
+                metadata: &infos[0],
+                authority: UpdateMetadataAuthority::Multisig {
+                    multisig: &infos[1],
+                    signers: &signers,
+                },
+            },
+            update_args(),
+        );
+        assert_instruction(
+            built,
+            program_id,
+            vec![
+                AccountMeta::new(metadata, false),
+                AccountMeta::new_readonly(multisig, false),
+                AccountMeta::new_readonly(members[0], true),
+                AccountMeta::new_readonly(members[1], true),
+            ],
+            update_data(),
+        );
+    }
+
+    #[test]
+    fn get_metadata_instruction_is_readonly() {
+        let (program_id, metadata, mint) = (key(1), key(4), key(3));
+        let instruction = get_metadata_instruction(&program_id, &metadata, &mint);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new_readonly(metadata, false),
+                AccountMeta::new_readonly(mint, false),
+            ]
+        );
+        assert_eq!(
+            MetadataInstruction::unpack(&instruction.data).unwrap(),
+            MetadataInstruction::GetMetadata
+        );
+    }
+}
@@ -35,6 +35,93 @@ pub enum MetadataError {
     /// Too many attributes
     #[error("Too many attributes")]
     TooManyAttributes,
+    /// Account is not owned by the token metadata program
+    #[error("Account is not owned by the token metadata program")]
+    IncorrectOwner,
+    /// Account address does not match the expected PDA
+    #[error("Account address does not match the expected PDA")]
+    InvalidPda,
+    /// Account mint does not match the expected mint
+    #[error("Account mint does not match the expected mint")]
+    MintMismatch,
+    /// Account type discriminator does not match the expected account type
+    #[error("Unexpected account type")]
+    InvalidAccountType,
+    /// Account layout version is not supported by this program
+    #[error("Unsupported account version")]
+    UnsupportedAccountVersion,
+    /// Account uses the legacy (v0) layout and must be migrated first
+    #[error("Account must be migrated to the current layout")]
+    AccountNeedsMigration,
+    /// Mint supply must be zero for this operation
+    #[error("Mint supply is not zero")]
+    MintSupplyNotZero,
+    /// Attributes account must be closed before the metadata account
+    #[error("Attributes account is still open")]
+    AttributesStillOpen,
+    /// Attribute keys must be unique
+    #[error("Duplicate attribute key")]
+    DuplicateAttributeKey,
+    /// No authority proposal is pending
+    #[error("No pending authority")]
+    NoPendingAuthority,
+    /// The field is locked and can no longer be modified
+    #[error("Field is locked")]
+    FieldLocked,
+    /// The delegate record does not authorize this edit
+    #[error("Delegate not authorized")]
+    DelegateNotAuthorized,
+    /// Multisig threshold or member list is invalid
+    #[error("Invalid multisig")]
+    InvalidMultisig,
+    /// Member metadata does not reference the supplied collection
+    #[error("Collection mismatch")]
+    CollectionMismatch,
+    /// Too many creators
+    #[error("Too many creators")]
+    TooManyCreators,
+    /// The same creator address appears more than once
+    #[error("Duplicate creator")]
+    DuplicateCreator,
+    /// Creator shares sum to more than 100
+    #[error("Invalid creator shares")]
+    InvalidCreatorShares,
+    /// Key is not in the creators list
+    #[error("Creator not found")]
+    CreatorNotFound,
+    /// Name is empty or only whitespace
+    #[error("Name is empty")]
+    EmptyName,
+    /// Symbol is empty
+    #[error("Symbol is empty")]
+    EmptySymbol,
+    /// Symbol contains characters outside printable ASCII, or whitespace
+    #[error("Invalid symbol character")]
+    InvalidSymbol,
+    /// Field contains a control character
+    #[error("Control character not allowed")]
+    ControlCharacter,
+    /// URI does not start with an allowed scheme
+    #[error("Unsupported URI scheme")]
+    UnsupportedUriScheme,
+    /// Account was written since the caller read it (`expected_revision` mismatch)
+    #[error("Stale revision")]
+    StaleRevision,
+    /// Account data is too small to hold the serialized account
+    #[error("Account data too small")]
+    AccountTooSmall,
+    /// Extension type id is not in the registry
+    #[error("Unknown extension type")]
+    UnknownExtensionType,
+    /// Extension data exceeds `MAX_EXTENSION_DATA_LEN`
+    #[error("Extension data too long")]
+    ExtensionDataTooLong,
+    /// Delegate records must be revoked before the metadata account is closed
+    #[error("Delegate records are still open")]
+    DelegatesStillOpen,
+    /// Extension accounts must be closed before the metadata account is closed
+    #[error("Extension accounts are still open")]
+    ExtensionsStillOpen,
 }
 
 impl From<MetadataError> for ProgramError {
@@ -66,6 +153,47 @@ impl PrintProgramError for MetadataError {
             MetadataError::InvalidInstructionData => msg!("Error: Invalid instruction data"),
             MetadataError::StringTooLong => msg!("Error: String too long"),
             MetadataError::TooManyAttributes => msg!("Error: Too many attributes"),
+            MetadataError::IncorrectOwner => {
+                msg!("Error: Account is not owned by the token metadata program")
+            }
+            MetadataError::InvalidPda => {
+                msg!("Error: Account address does not match the expected PDA")
+            }
+            MetadataError::MintMismatch => {
+                msg!("Error: Account mint does not match the expected mint")
+            }
+            MetadataError::InvalidAccountType => msg!("Error: Unexpected account type"),
+            MetadataError::UnsupportedAccountVersion => {
+                msg!("Error: Unsupported account version")
+            }
+            MetadataError::AccountNeedsMigration => {
+                msg!("Error: Account must be migrated to the current layout")
+            }
+            MetadataError::MintSupplyNotZero => msg!("Error: Mint supply is not zero"),
+            MetadataError::AttributesStillOpen => msg!("Error: Attributes account is still open"),
+            MetadataError::DuplicateAttributeKey => msg!("Error: Duplicate attribute key"),
+            MetadataError::NoPendingAuthority => msg!("Error: No pending authority"),
+            MetadataError::FieldLocked => msg!("Error: Field is locked"),
+            MetadataError::DelegateNotAuthorized => msg!("Error: Delegate not authorized"),
+            MetadataError::InvalidMultisig => msg!("Error: Invalid multisig"),
+            MetadataError::CollectionMismatch => msg!("Error: Collection mismatch"),
+            MetadataError::TooManyCreators => msg!("Error: Too many creators"),
+            MetadataError::DuplicateCreator => msg!("Error: Duplicate creator"),
+            MetadataError::InvalidCreatorShares => msg!("Error: Invalid creator shares"),
+            MetadataError::CreatorNotFound => msg!("Error: Creator not found"),
+            MetadataError::EmptyName => msg!("Error: Name is empty"),
+            MetadataError::EmptySymbol => msg!("Error: Symbol is empty"),
+            MetadataError::InvalidSymbol => msg!("Error: Invalid symbol character"),
+            MetadataError::ControlCharacter => msg!("Error: Control character not allowed"),
+            MetadataError::UnsupportedUriScheme => msg!("Error: Unsupported URI scheme"),
+            MetadataError::StaleRevision => msg!("Error: Stale revision"),
+            MetadataError::AccountTooSmall => msg!("Error: Account data too small"),
+            MetadataError::UnknownExtensionType => msg!("Error: Unknown extension type"),
+            MetadataError::ExtensionDataTooLong => msg!("Error: Extension data too long"),
+            MetadataError::DelegatesStillOpen => msg!("Error: Delegate records are still open"),
+            MetadataError::ExtensionsStillOpen => {
+                msg!("Error: Extension accounts are still open")
+            }
         }
     }
 }
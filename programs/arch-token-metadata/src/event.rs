@@ -0,0 +1,311 @@
+//! Structured events emitted by the processor for indexers
+//!
+//! Every state-changing instruction logs exactly one event; `GetMetadata` and a no-op
+//! `MigrateAccount` log none. An event is a single line: `EVENT_LOG_PREFIX` followed by the
+//! hex encoding of `[EVENT_VERSION, borsh(MetadataEvent)]`.
+//! Variants are only ever appended, so existing tags keep their meaning across versions.
+
+use {
+    crate::state::{AccountType, Creator},
+    arch_program::{msg, program_error::ProgramError, pubkey::Pubkey},
+    borsh::{BorshDeserialize, BorshSerialize},
+};
+
+/// Version byte written in front of every encoded event
+pub const EVENT_VERSION: u8 = 1;
+
+/// Marker that starts the payload of an event log line
+pub const EVENT_LOG_PREFIX: &str = "arch-token-metadata:event:";
+
+/// Events emitted by the token metadata program, one per state-changing instruction.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum MetadataEvent {
+    /// `CreateMetadata`
+    MetadataCreated {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Stored update authority (`None` if created immutable)
+        update_authority: Option<Pubkey>,
+    },
+    /// `UpdateMetadata`
+    MetadataUpdated {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Fields written by the update, using the `LOCK_*` bit layout
+        changed_fields: u16,
+    },
+    /// `CreateAttributes`
+    AttributesCreated {
+        /// Mint the attributes belong to
+        mint: Pubkey,
+    },
+    /// `ReplaceAttributes`
+    AttributesReplaced {
+        /// Mint the attributes belong to
+        mint: Pubkey,
+    },
+    /// `UpsertAttributes`
+    AttributesUpserted {
+        /// Mint the attributes belong to
+        mint: Pubkey,
+        /// Keys inserted or overwritten
+        keys: Vec<String>,
+    },
+    /// `RemoveAttributes`
+    AttributesRemoved {
+        /// Mint the attributes belong to
+        mint: Pubkey,
+        /// Keys requested for removal (including ones that were not present)
+        keys: Vec<String>,
+    },
+    /// `TransferAuthority` or `AcceptAuthority`
+    AuthorityTransferred {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Previous update authority
+        old_authority: Pubkey,
+        /// New update authority
+        new_authority: Pubkey,
+    },
+    /// `MakeImmutable`
+    MadeImmutable {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+    },
+    /// `MigrateAccount` (only when the account was actually rewritten or grown)
+    AccountMigrated {
+        /// Migrated account
+        account: Pubkey,
+        /// Type of the migrated account
+        account_type: AccountType,
+    },
+    /// `CloseAttributes`
+    AttributesClosed {
+        /// Mint the attributes belonged to
+        mint: Pubkey,
+    },
+    /// `CloseMetadata`
+    MetadataClosed {
+        /// Mint the metadata belonged to
+        mint: Pubkey,
+    },
+    /// `Touch`
+    Touched {
+        /// Mint that was touched
+        mint: Pubkey,
+        /// Slot recorded in the touch PDA
+        slot: u64,
+        /// Touch counter after this touch
+        touch_count: u64,
+    },
+    /// `ProposeAuthority`
+    AuthorityProposed {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Proposed new authority
+        proposed_authority: Pubkey,
+    },
+    /// `CancelProposal`
+    ProposalCancelled {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+    },
+    /// `LockFields`
+    FieldsLocked {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Full lock bitmask after the instruction
+        locked_fields: u16,
+    },
+    /// `AddDelegate`
+    DelegateAdded {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Key granted rights
+        delegate: Pubkey,
+        /// Fields the delegate may edit
+        scope: u16,
+    },
+    /// `RevokeDelegate`
+    DelegateRevoked {
+        /// Mint the metadata belongs to
+        mint: Pubkey,
+        /// Key whose rights were revoked
+        delegate: Pubkey,
+    },
+    /// `CreateMultisig`
+    MultisigCreated {
+        /// Multisig account
+        multisig: Pubkey,
+        /// Number of member signatures required
+        m: u8,
+        /// Member keys
+        signers: Vec<Pubkey>,
+    },
+    /// `AddMultisigSigner`
+    MultisigSignerAdded {
+        /// Multisig account
+        multisig: Pubkey,
+        /// Added member
+        signer: Pubkey,
+    },
+    /// `RemoveMultisigSigner`
+    MultisigSignerRemoved {
+        /// Multisig account
+        multisig: Pubkey,
+        /// Removed member
+        signer: Pubkey,
+    },
+    /// `SetCollection`
+    CollectionSet {
+        /// Member mint
+        mint: Pubkey,
+        /// Collection mint, or `None` if cleared
+        collection_mint: Option<Pubkey>,
+    },
+    /// `VerifyCollectionMember` or `UnverifyCollectionMember`
+    CollectionVerificationChanged {
+        /// Member mint
+        mint: Pubkey,
+        /// Collection mint
+        collection_mint: Pubkey,
+        /// New verified flag
+        verified: bool,
+    },
+    /// `SetCreators`
+    CreatorsSet {
+        /// Mint the creators belong to
+        mint: Pubkey,
+        /// Full creators list after the instruction
+        creators: Vec<Creator>,
+    },
+    /// `SignCreator`
+    CreatorSigned {
+        /// Mint the creators belong to
+        mint: Pubkey,
+        /// Creator that verified itself
+        creator: Pubkey,
+    },
+    /// `RemoveCreator`
+    CreatorRemoved {
+        /// Mint the creators belong to
+        mint: Pubkey,
+        /// Removed creator
+        creator: Pubkey,
+    },
+    /// `CreateMetadataWithAttributes`
+    MetadataWithAttributesCreated {
+        /// Mint the metadata and attributes belong to
+        mint: Pubkey,
+        /// Stored update authority (`None` if created immutable)
+        update_authority: Option<Pubkey>,
+    },
+    /// `CreateExtension`
+    ExtensionCreated {
+        /// Mint the extension belongs to
+        mint: Pubkey,
+        /// Registered `ExtensionType` id
+        extension_type: u16,
+    },
+    /// `UpdateExtension`
+    ExtensionUpdated {
+        /// Mint the extension belongs to
+        mint: Pubkey,
+        /// Registered `ExtensionType` id
+        extension_type: u16,
+    },
+    /// `CloseExtension`
+    ExtensionClosed {
+        /// Mint the extension belonged to
+        mint: Pubkey,
+        /// Registered `ExtensionType` id
+        extension_type: u16,
+    },
+}
+
+impl MetadataEvent {
+    /// Encode as `[EVENT_VERSION, borsh(self)]`
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![EVENT_VERSION];
+        out.extend_from_slice(&borsh::to_vec(self).unwrap());
+        out
+    }
+
+    /// Decode bytes produced by `encode`, rejecting unknown versions
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        match data.split_first() {
+            Some((&EVENT_VERSION, body)) => {
+                borsh::from_slice(body).map_err(|_| ProgramError::InvalidArgument)
+            }
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+
+    /// Log the event for indexers
+    pub fn emit(&self) {
+        msg!("{}{}", EVENT_LOG_PREFIX, to_hex(&self.encode()));
+    }
+
+    /// Decode the event carried by a log line, if the line carries one. The runtime may add its
+    /// own prefix to program logs, so the marker is searched for anywhere in the line.
+    pub fn from_log(line: &str) -> Option<Result<Self, ProgramError>> {
+        let start = line.find(EVENT_LOG_PREFIX)? + EVENT_LOG_PREFIX.len();
+        let payload = line[start..].trim_end();
+        Some(from_hex(payload).and_then(|bytes| Self::decode(&bytes)))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, ProgramError> {
+    if s.len() % 2 != 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let hi = (pair[0] as char).to_digit(16);
+            let lo = (pair[1] as char).to_digit(16);
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => Ok((hi * 16 + lo) as u8),
+                _ => Err(ProgramError::InvalidArgument),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_log_roundtrip() {
+        let event = MetadataEvent::MetadataUpdated {
+            mint: Pubkey::from_slice(&[3u8; 32]),
+            changed_fields: 0b101,
+        };
+        let line = format!(
+            "Program log: {}{}",
+            EVENT_LOG_PREFIX,
+            to_hex(&event.encode())
+        );
+        assert_eq!(MetadataEvent::from_log(&line).unwrap().unwrap(), event);
+        assert!(MetadataEvent::from_log("Program log: hello").is_none());
+
+        // Unknown versions and malformed payloads are reported, not skipped
+        let mut bumped = event.encode();
+        bumped[0] = EVENT_VERSION + 1;
+        let line = format!("{}{}", EVENT_LOG_PREFIX, to_hex(&bumped));
+        assert!(MetadataEvent::from_log(&line).unwrap().is_err());
+        let line = format!("{}zz", EVENT_LOG_PREFIX);
+        assert!(MetadataEvent::from_log(&line).unwrap().is_err());
+    }
+}
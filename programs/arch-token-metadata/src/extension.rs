@@ -0,0 +1,35 @@
+//! Registry of extension type ids
+
+/// Extension types the program accepts.
+///
+/// The id is part of the extension PDA seeds (`["ext", mint, id.to_le_bytes()]`) and of the
+/// account's TLV header, so ids are never reused or renumbered; new types are appended.
+/// The program stores extension payloads as opaque bytes; their encoding is defined per type
+/// (see the typed helpers in the SDK).
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionType {
+    /// Secondary sale royalty: basis points and recipient
+    Royalty = 1,
+    /// Localized name and description for one language tag
+    Localization = 2,
+}
+
+impl ExtensionType {
+    /// Every registered extension type, in id order
+    pub const ALL: [ExtensionType; 2] = [ExtensionType::Royalty, ExtensionType::Localization];
+
+    /// Look up a registered extension type by id
+    pub fn from_u16(id: u16) -> Option<Self> {
+        match id {
+            1 => Some(ExtensionType::Royalty),
+            2 => Some(ExtensionType::Localization),
+            _ => None,
+        }
+    }
+
+    /// The type id used in seeds, instruction data and the account header
+    pub fn id(self) -> u16 {
+        self as u16
+    }
+}
@@ -1,11 +1,18 @@
 //! Instruction types
 
 use {
-    arch_program::{program_error::ProgramError, pubkey::Pubkey},
+    crate::state::ContentUri,
+    arch_program::{
+        account::AccountMeta, instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+    },
     borsh::{BorshDeserialize, BorshSerialize},
 };
 
 /// Instructions supported by the token metadata program.
+///
+/// Wherever an instruction is authorized by the update authority, that authority may be a
+/// multisig account: pass the multisig in the authority slot (not as a signer) and append at
+/// least `m` of its member signers after the listed accounts.
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub enum MetadataInstruction {
     /// Create core metadata for a token
@@ -20,8 +27,12 @@ pub enum MetadataInstruction {
         description: String,
         /// If true, metadata is immutable (no updates allowed)
         immutable: bool,
+        /// Optional off-chain JSON URI and content hash commitment
+        uri: Option<ContentUri>,
     },
-    /// Update core metadata
+    /// Update core metadata. A delegate may sign instead of the update authority by appending
+    /// its delegate record account; a multisig authority appends its member signers instead.
+    /// At least one field must be set.
     UpdateMetadata {
         /// Optional new name for the token
         name: Option<String>,
@@ -31,30 +42,325 @@ pub enum MetadataInstruction {
         image: Option<String>,
         /// Optional new description for the token
         description: Option<String>,
+        /// Optional new off-chain JSON URI and content hash commitment
+        uri: Option<ContentUri>,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
     },
     /// Create metadata attributes
     CreateAttributes {
         /// Key-value pairs for extensible attributes
         data: Vec<(String, String)>,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
     },
-    /// Replace metadata attributes
+    /// Replace metadata attributes, resizing the account to fit. An optional trailing
+    /// `[payer, system_program]` funds growth and receives rent freed by shrinking; without
+    /// it, freed rent goes to a writable single-key update authority, otherwise it stays in
+    /// the account
     ReplaceAttributes {
         /// Key-value pairs for extensible attributes
         data: Vec<(String, String)>,
+        /// If set, fail with `StaleRevision` unless the attributes are at this revision
+        expected_revision: Option<u64>,
     },
-    /// Transfer update authority (must provide a new authority)
+    /// Transfer update authority (must provide a new authority). Multisig authorities append
+    /// their member signers.
     TransferAuthority {
         /// New authority to transfer to
         new_authority: Pubkey,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Make metadata immutable (revoke update authority). Multisig authorities append their
+    /// member signers.
+    MakeImmutable {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Rewrite a legacy (v0) metadata or attributes account into the current layout, or grow an
+    /// account allocated before trailing fields were added. Permissionless; the payer funds any
+    /// additional rent and receives any freed by shrinking. No-op if already current.
+    MigrateAccount,
+    /// Close the attributes account, reclaiming its lamports (update authority only)
+    CloseAttributes {
+        /// If set, fail with `StaleRevision` unless the attributes are at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Close the metadata account, reclaiming its lamports (update authority only).
+    /// Requires the mint supply to be zero and the attributes account to be closed first.
+    CloseMetadata {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Insert or overwrite individual attributes by key, leaving other entries untouched.
+    /// Grows the account as needed, funded by the payer.
+    UpsertAttributes {
+        /// Key-value pairs to insert or overwrite
+        data: Vec<(String, String)>,
+        /// If set, fail with `StaleRevision` unless the attributes are at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Remove individual attributes by key; keys that are not present are ignored. The
+    /// account shrinks to fit, refunding rent as in `ReplaceAttributes`, so pass the payer
+    /// pair to collect it under a delegate or multisig.
+    RemoveAttributes {
+        /// Keys to remove
+        keys: Vec<String>,
+        /// If set, fail with `StaleRevision` unless the attributes are at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Record the current slot and bump the counter in the touch PDA, signalling indexers
+    /// to re-read the mint (update authority only). Creates the touch PDA on first use.
+    Touch {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Propose a new update authority; takes effect only once the proposed key signs
+    /// `AcceptAuthority`. Replaces any outstanding proposal.
+    ProposeAuthority {
+        /// Proposed new authority
+        new_authority: Pubkey,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Accept a pending authority proposal (must be signed by the proposed authority)
+    AcceptAuthority {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Cancel a pending authority proposal (current update authority only)
+    CancelProposal {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Permanently lock individual metadata fields (update authority only). Bits are the
+    /// `LOCK_*` constants in `state`; bits already set stay set.
+    LockFields {
+        /// Bitmask of fields to lock
+        mask: u16,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Grant (or re-scope) a delegate that may edit the fields in `scope` without the update
+    /// authority's signature (update authority only). Creates the delegate record PDA.
+    AddDelegate {
+        /// Key being granted rights
+        delegate: Pubkey,
+        /// Fields the delegate may edit, using the `LOCK_*` bit layout
+        scope: u16,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Close a delegate record, reclaiming its lamports (update authority or the delegate; a
+    /// renouncing delegate must send them to the update authority)
+    RevokeDelegate {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Create an M-of-N multisig that can be used as an update authority. The multisig account
+    /// is a fresh keypair that signs its own creation.
+    CreateMultisig {
+        /// Number of member signatures required
+        m: u8,
+        /// Member keys
+        signers: Vec<Pubkey>,
+    },
+    /// Add a member to a multisig (requires `m` current member signatures)
+    AddMultisigSigner {
+        /// Key to add
+        signer: Pubkey,
+    },
+    /// Remove a member from a multisig (requires `m` current member signatures); the
+    /// remaining member count must stay at or above `m`
+    RemoveMultisigSigner {
+        /// Key to remove
+        signer: Pubkey,
+    },
+    /// Point this mint's metadata at a collection, or clear it with `None` (update authority
+    /// only). The collection is another mint whose metadata lives in this program; the
+    /// reference starts unverified.
+    SetCollection {
+        /// Mint of the collection
+        collection_mint: Option<Pubkey>,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Mark a member's collection reference as verified (collection update authority only)
+    VerifyCollectionMember {
+        /// If set, fail with `StaleRevision` unless the member metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Clear the verified flag on a member's collection reference (collection update
+    /// authority, or the member's own update authority, which keeps this possible once the
+    /// collection is immutable)
+    UnverifyCollectionMember {
+        /// If set, fail with `StaleRevision` unless the member metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Set the creators list (update authority only). Creates the creators PDA on first use.
+    /// Entries whose address and share are unchanged keep their verification; all others
+    /// start unverified.
+    SetCreators {
+        /// (creator address, share in percent) pairs
+        creators: Vec<(Pubkey, u8)>,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Mark the signing creator as verified. The metadata account follows the creator, and its
+    /// revision advances like any other creators write.
+    SignCreator {
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Remove a creator from the list (update authority, or the creator removing itself)
+    RemoveCreator {
+        /// Creator address to remove
+        creator: Pubkey,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Read-only: validate the metadata PDA for a mint and return its
+    /// `TokenMetadataSummary` (borsh) via return data. Writes nothing and emits no event.
+    GetMetadata,
+    /// Create core metadata and its attributes in one instruction. Accounts are those of
+    /// `CreateMetadata` followed by the attributes PDA; the mint authority is checked once and
+    /// both PDAs are written atomically, even when `immutable` is set.
+    CreateMetadataWithAttributes {
+        /// The name of the token
+        name: String,
+        /// The symbol of the token
+        symbol: String,
+        /// The image URI for the token
+        image: String,
+        /// The description of the token
+        description: String,
+        /// If true, metadata is immutable once both accounts are written
+        immutable: bool,
+        /// Optional off-chain JSON URI and content hash commitment
+        uri: Option<ContentUri>,
+        /// Key-value pairs for extensible attributes
+        data: Vec<(String, String)>,
+    },
+    /// Create the extension PDA `["ext", mint, extension_type]` (update authority only)
+    CreateExtension {
+        /// Registered `ExtensionType` id
+        extension_type: u16,
+        /// Type-specific payload (at most `MAX_EXTENSION_DATA_LEN` bytes)
+        data: Vec<u8>,
+        /// If set, fail with `StaleRevision` unless the metadata is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Replace an extension's payload (update authority only), resizing the account to fit.
+    /// Growth and refunds work as in `ReplaceAttributes`: an optional trailing
+    /// `[payer, system_program]` funds growth and receives freed rent, ahead of any multisig
+    /// member signers
+    UpdateExtension {
+        /// New type-specific payload (at most `MAX_EXTENSION_DATA_LEN` bytes)
+        data: Vec<u8>,
+        /// If set, fail with `StaleRevision` unless the extension is at this revision
+        expected_revision: Option<u64>,
+    },
+    /// Close an extension account (update authority only)
+    CloseExtension {
+        /// If set, fail with `StaleRevision` unless the extension is at this revision
+        expected_revision: Option<u64>,
+    },
+}
+
+/// Layouts of the original instructions, before optional trailing fields were appended.
+///
+/// Only decoded when the current layout does not parse: a legacy encoding is a strict prefix
+/// of the current one, so it can never be mistaken for it. Missing fields decode as `None`.
+#[derive(BorshDeserialize)]
+enum LegacyMetadataInstruction {
+    CreateMetadata {
+        name: String,
+        symbol: String,
+        image: String,
+        description: String,
+        immutable: bool,
+    },
+    UpdateMetadata {
+        name: Option<String>,
+        symbol: Option<String>,
+        image: Option<String>,
+        description: Option<String>,
+    },
+    CreateAttributes {
+        data: Vec<(String, String)>,
+    },
+    ReplaceAttributes {
+        data: Vec<(String, String)>,
+    },
+    TransferAuthority {
+        new_authority: Pubkey,
     },
-    /// Make metadata immutable (revoke update authority)
     MakeImmutable,
 }
 
+impl From<LegacyMetadataInstruction> for MetadataInstruction {
+    fn from(legacy: LegacyMetadataInstruction) -> Self {
+        match legacy {
+            LegacyMetadataInstruction::CreateMetadata {
+                name,
+                symbol,
+                image,
+                description,
+                immutable,
+            } => MetadataInstruction::CreateMetadata {
+                name,
+                symbol,
+                image,
+                description,
+                immutable,
+                uri: None,
+            },
+            LegacyMetadataInstruction::UpdateMetadata {
+                name,
+                symbol,
+                image,
+                description,
+            } => MetadataInstruction::UpdateMetadata {
+                name,
+                symbol,
+                image,
+                description,
+                uri: None,
+                expected_revision: None,
+            },
+            LegacyMetadataInstruction::CreateAttributes { data } => {
+                MetadataInstruction::CreateAttributes {
+                    data,
+                    expected_revision: None,
+                }
+            }
+            LegacyMetadataInstruction::ReplaceAttributes { data } => {
+                MetadataInstruction::ReplaceAttributes {
+                    data,
+                    expected_revision: None,
+                }
+            }
+            LegacyMetadataInstruction::TransferAuthority { new_authority } => {
+                MetadataInstruction::TransferAuthority {
+                    new_authority,
+                    expected_revision: None,
+                }
+            }
+            LegacyMetadataInstruction::MakeImmutable => MetadataInstruction::MakeImmutable {
+                expected_revision: None,
+            },
+        }
+    }
+}
+
 impl MetadataInstruction {
-    /// Unpack a byte array into a MetadataInstruction
+    /// Unpack a byte array into a MetadataInstruction. Encodings produced by clients that
+    /// predate a trailing optional field are still accepted, with that field as `None`.
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        borsh::from_slice(input).map_err(|_| ProgramError::InvalidInstructionData)
+        borsh::from_slice(input)
+            .or_else(|_| borsh::from_slice::<LegacyMetadataInstruction>(input).map(Self::from))
+            .map_err(|_| ProgramError::InvalidInstructionData)
     }
 
     /// Pack the MetadataInstruction into a byte array
@@ -62,3 +368,105 @@ impl MetadataInstruction {
         borsh::to_vec(self).unwrap()
     }
 }
+
+/// Build a `GetMetadata` instruction for `metadata` (the metadata PDA of `mint`)
+pub fn get_metadata_instruction(
+    program_id: &Pubkey,
+    metadata: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+        data: MetadataInstruction::GetMetadata.pack(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_encodings_decode_with_missing_fields_as_none() {
+        // Baseline CreateMetadata: tag, four strings, immutable flag, no uri
+        let legacy = borsh::to_vec(&(0u8, "N", "S", "https://i", "d", false)).unwrap();
+        assert_eq!(
+            MetadataInstruction::unpack(&legacy).unwrap(),
+            MetadataInstruction::CreateMetadata {
+                name: "N".into(),
+                symbol: "S".into(),
+                image: "https://i".into(),
+                description: "d".into(),
+                immutable: false,
+                uri: None,
+            }
+        );
+
+        // Baseline UpdateMetadata: tag and four optional strings
+        let legacy =
+            borsh::to_vec(&(1u8, Some("N2"), None::<String>, None::<String>, Some("d2"))).unwrap();
+        assert_eq!(
+            MetadataInstruction::unpack(&legacy).unwrap(),
+            MetadataInstruction::UpdateMetadata {
+                name: Some("N2".into()),
+                symbol: None,
+                image: None,
+                description: Some("d2".into()),
+                uri: None,
+                expected_revision: None,
+            }
+        );
+
+        // Baseline CreateAttributes, ReplaceAttributes, TransferAuthority and the unit
+        // MakeImmutable
+        let legacy_create = borsh::to_vec(&(2u8, vec![("a", "1")])).unwrap();
+        assert_eq!(
+            MetadataInstruction::unpack(&legacy_create).unwrap(),
+            MetadataInstruction::CreateAttributes {
+                data: vec![("a".into(), "1".into())],
+                expected_revision: None,
+            }
+        );
+        let legacy_replace = borsh::to_vec(&(3u8, vec![("a", "1")])).unwrap();
+        assert_eq!(
+            MetadataInstruction::unpack(&legacy_replace).unwrap(),
+            MetadataInstruction::ReplaceAttributes {
+                data: vec![("a".into(), "1".into())],
+                expected_revision: None,
+            }
+        );
+        let new_authority = Pubkey::from_slice(&[9u8; 32]);
+        let legacy_transfer = borsh::to_vec(&(4u8, new_authority)).unwrap();
+        assert_eq!(
+            MetadataInstruction::unpack(&legacy_transfer).unwrap(),
+            MetadataInstruction::TransferAuthority {
+                new_authority,
+                expected_revision: None,
+            }
+        );
+        assert_eq!(
+            MetadataInstruction::unpack(&[5]).unwrap(),
+            MetadataInstruction::MakeImmutable {
+                expected_revision: None,
+            }
+        );
+
+        // Current encodings still round-trip, and truncated input is rejected
+        let current = MetadataInstruction::UpdateMetadata {
+            name: None,
+            symbol: None,
+            image: None,
+            description: None,
+            uri: None,
+            expected_revision: Some(7),
+        };
+        assert_eq!(
+            MetadataInstruction::unpack(&current.pack()).unwrap(),
+            current
+        );
+        assert!(MetadataInstruction::unpack(&legacy[..legacy.len() - 1]).is_err());
+    }
+}
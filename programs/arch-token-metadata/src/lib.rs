@@ -3,7 +3,11 @@
 
 //! Arch Network Token Metadata Standard
 
+#[cfg(any(feature = "cpi", test))]
+pub mod cpi;
 pub mod error;
+pub mod event;
+pub mod extension;
 pub mod instruction;
 pub mod processor;
 pub mod state;
@@ -34,6 +38,18 @@ pub const METADATA_SEED: &[u8] = b"metadata";
 /// PDA seed for attributes account
 pub const ATTRIBUTES_SEED: &[u8] = b"attributes";
 
+/// PDA seed for touch account
+pub const TOUCH_SEED: &[u8] = b"touch";
+
+/// PDA seed for delegate records
+pub const DELEGATE_SEED: &[u8] = b"delegate";
+
+/// PDA seed for the creators account
+pub const CREATORS_SEED: &[u8] = b"creators";
+
+/// PDA seed for extension accounts
+pub const EXTENSION_SEED: &[u8] = b"ext";
+
 /// Helper to derive the `TokenMetadata` PDA for a given mint
 pub fn find_metadata_pda_with_program(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[METADATA_SEED, mint.as_ref()], program_id)
@@ -43,3 +59,114 @@ pub fn find_metadata_pda_with_program(program_id: &Pubkey, mint: &Pubkey) -> (Pu
 pub fn find_attributes_pda_with_program(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[ATTRIBUTES_SEED, mint.as_ref()], program_id)
 }
+
+/// Recreate the `TokenMetadata` PDA for a mint from its stored bump, without a bump search
+pub fn create_metadata_pda_with_bump(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[METADATA_SEED, mint.as_ref(), &[bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)
+}
+
+/// Recreate the `TokenMetadataAttributes` PDA for a mint from its stored bump, without a bump
+/// search
+pub fn create_attributes_pda_with_bump(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[ATTRIBUTES_SEED, mint.as_ref(), &[bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)
+}
+
+/// Helper to derive the `TokenMetadataTouch` PDA for a given mint
+pub fn find_touch_pda_with_program(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOUCH_SEED, mint.as_ref()], program_id)
+}
+
+/// Recreate the `TokenMetadataTouch` PDA for a mint from its stored bump, without a bump search
+pub fn create_touch_pda_with_bump(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[TOUCH_SEED, mint.as_ref(), &[bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)
+}
+
+/// Helper to derive the `TokenMetadataDelegate` PDA for a given mint and delegate
+pub fn find_delegate_pda_with_program(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DELEGATE_SEED, mint.as_ref(), delegate.as_ref()],
+        program_id,
+    )
+}
+
+/// Recreate the `TokenMetadataDelegate` PDA for a mint and delegate from its stored bump,
+/// without a bump search
+pub fn create_delegate_pda_with_bump(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(
+        &[DELEGATE_SEED, mint.as_ref(), delegate.as_ref(), &[bump]],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)
+}
+
+/// Helper to derive the `TokenMetadataCreators` PDA for a given mint
+pub fn find_creators_pda_with_program(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREATORS_SEED, mint.as_ref()], program_id)
+}
+
+/// Recreate the `TokenMetadataCreators` PDA for a mint from its stored bump, without a bump
+/// search
+pub fn create_creators_pda_with_bump(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[CREATORS_SEED, mint.as_ref(), &[bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)
+}
+
+/// Helper to derive the `TokenMetadataExtension` PDA for a given mint and extension type id
+pub fn find_extension_pda_with_program(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    extension_type: u16,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EXTENSION_SEED, mint.as_ref(), &extension_type.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Recreate the `TokenMetadataExtension` PDA for a mint and extension type id from its stored
+/// bump, without a bump search
+pub fn create_extension_pda_with_bump(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    extension_type: u16,
+    bump: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(
+        &[
+            EXTENSION_SEED,
+            mint.as_ref(),
+            &extension_type.to_le_bytes(),
+            &[bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)
+}
@@ -2,33 +2,68 @@
 
 use {
     crate::{
+        create_attributes_pda_with_bump, create_creators_pda_with_bump,
+        create_delegate_pda_with_bump, create_extension_pda_with_bump,
+        create_metadata_pda_with_bump, create_touch_pda_with_bump,
         error::MetadataError,
-        find_attributes_pda_with_program, find_metadata_pda_with_program,
+        event::MetadataEvent,
+        extension::ExtensionType,
+        find_attributes_pda_with_program, find_creators_pda_with_program,
+        find_delegate_pda_with_program, find_extension_pda_with_program,
+        find_metadata_pda_with_program, find_touch_pda_with_program,
         instruction::MetadataInstruction,
         state::{
-            TokenMetadata, TokenMetadataAttributes, DESCRIPTION_MAX_LEN, IMAGE_MAX_LEN,
-            MAX_ATTRIBUTES, MAX_KEY_LENGTH, MAX_VALUE_LENGTH, NAME_MAX_LEN, SYMBOL_MAX_LEN,
+            attributes_account_len, extension_account_len, sort_attributes,
+            upgrade_legacy_account_data, validate_creators, validate_description,
+            validate_extension_data, validate_image, validate_metadata_fields, validate_name,
+            validate_symbol, AccountType, Collection, ContentHashAlgorithm, ContentUri, Creator,
+            TokenMetadata, TokenMetadataAttributes, TokenMetadataCreators, TokenMetadataDelegate,
+            TokenMetadataExtension, TokenMetadataMultisig, TokenMetadataSummary,
+            TokenMetadataTouch, ACCOUNT_HEADER_LEN, CURRENT_ACCOUNT_VERSION, LEGACY_V0_INITIALIZED,
+            LOCK_ALL, LOCK_ATTRIBUTES, LOCK_DESCRIPTION, LOCK_IMAGE, LOCK_NAME, LOCK_SYMBOL,
+            LOCK_URI, MAX_ATTRIBUTES, MAX_KEY_LENGTH, MAX_MULTISIG_SIGNERS,
+            MAX_PERMITTED_DATA_INCREASE, MAX_VALUE_LENGTH,
         },
-        ATTRIBUTES_SEED, METADATA_SEED,
+        ATTRIBUTES_SEED, CREATORS_SEED, DELEGATE_SEED, EXTENSION_SEED, METADATA_SEED, TOUCH_SEED,
     },
     apl_token::{self, state::Mint},
     arch_program::{
         account::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         msg,
-        program::invoke_signed,
+        program::{get_clock, invoke, invoke_signed, set_return_data},
         program_error::ProgramError,
         program_option::COption,
         program_pack::{IsInitialized, Pack},
         pubkey::Pubkey,
         rent::minimum_rent,
-        system_instruction::create_account,
+        system_instruction::{create_account, transfer},
     },
+    core::cmp::Ordering,
 };
 
 /// Program state handler.
 pub struct Processor {}
 
+/// Fields of a `CreateMetadata` (shared with `CreateMetadataWithAttributes`)
+struct CreateMetadataArgs {
+    name: String,
+    symbol: String,
+    image: String,
+    description: String,
+    immutable: bool,
+    uri: Option<ContentUri>,
+}
+
+/// Optional field changes of an `UpdateMetadata`; `None` leaves a field unchanged
+struct UpdateMetadataArgs {
+    name: Option<String>,
+    symbol: Option<String>,
+    image: Option<String>,
+    description: Option<String>,
+    uri: Option<ContentUri>,
+}
+
 impl Processor {
     /// Process a single instruction
     pub fn process(
@@ -45,45 +80,278 @@ impl Processor {
                 image,
                 description,
                 immutable,
+                uri,
             } => Self::process_create_metadata(
                 program_id,
                 accounts,
-                name,
-                symbol,
-                image,
-                description,
-                immutable,
+                CreateMetadataArgs {
+                    name,
+                    symbol,
+                    image,
+                    description,
+                    immutable,
+                    uri,
+                },
             ),
             MetadataInstruction::UpdateMetadata {
                 name,
                 symbol,
                 image,
                 description,
-            } => Self::process_update_metadata(accounts, name, symbol, image, description),
-            MetadataInstruction::CreateAttributes { data } => {
-                Self::process_create_attributes(program_id, accounts, data)
+                uri,
+                expected_revision,
+            } => Self::process_update_metadata(
+                program_id,
+                accounts,
+                UpdateMetadataArgs {
+                    name,
+                    symbol,
+                    image,
+                    description,
+                    uri,
+                },
+                expected_revision,
+            ),
+            MetadataInstruction::CreateAttributes {
+                data,
+                expected_revision,
+            } => Self::process_create_attributes(program_id, accounts, data, expected_revision),
+            MetadataInstruction::ReplaceAttributes {
+                data,
+                expected_revision,
+            } => Self::process_replace_attributes(program_id, accounts, data, expected_revision),
+
+            MetadataInstruction::TransferAuthority {
+                new_authority,
+                expected_revision,
+            } => Self::process_transfer_authority(
+                program_id,
+                accounts,
+                new_authority,
+                expected_revision,
+            ),
+
+            MetadataInstruction::MakeImmutable { expected_revision } => {
+                Self::process_make_immutable(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::MigrateAccount => {
+                Self::process_migrate_account(program_id, accounts)
+            }
+
+            MetadataInstruction::CloseAttributes { expected_revision } => {
+                Self::process_close_attributes(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::CloseMetadata { expected_revision } => {
+                Self::process_close_metadata(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::UpsertAttributes {
+                data,
+                expected_revision,
+            } => Self::process_upsert_attributes(program_id, accounts, data, expected_revision),
+
+            MetadataInstruction::RemoveAttributes {
+                keys,
+                expected_revision,
+            } => Self::process_remove_attributes(program_id, accounts, keys, expected_revision),
+
+            MetadataInstruction::Touch { expected_revision } => {
+                Self::process_touch(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::ProposeAuthority {
+                new_authority,
+                expected_revision,
+            } => Self::process_propose_authority(
+                program_id,
+                accounts,
+                new_authority,
+                expected_revision,
+            ),
+
+            MetadataInstruction::AcceptAuthority { expected_revision } => {
+                Self::process_accept_authority(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::CancelProposal { expected_revision } => {
+                Self::process_cancel_proposal(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::LockFields {
+                mask,
+                expected_revision,
+            } => Self::process_lock_fields(program_id, accounts, mask, expected_revision),
+
+            MetadataInstruction::AddDelegate {
+                delegate,
+                scope,
+                expected_revision,
+            } => {
+                Self::process_add_delegate(program_id, accounts, delegate, scope, expected_revision)
+            }
+
+            MetadataInstruction::RevokeDelegate { expected_revision } => {
+                Self::process_revoke_delegate(program_id, accounts, expected_revision)
+            }
+
+            MetadataInstruction::CreateMultisig { m, signers } => {
+                Self::process_create_multisig(program_id, accounts, m, signers)
+            }
+
+            MetadataInstruction::AddMultisigSigner { signer } => {
+                Self::process_add_multisig_signer(program_id, accounts, signer)
+            }
+
+            MetadataInstruction::RemoveMultisigSigner { signer } => {
+                Self::process_remove_multisig_signer(program_id, accounts, signer)
+            }
+
+            MetadataInstruction::SetCollection {
+                collection_mint,
+                expected_revision,
+            } => Self::process_set_collection(
+                program_id,
+                accounts,
+                collection_mint,
+                expected_revision,
+            ),
+
+            MetadataInstruction::VerifyCollectionMember { expected_revision } => {
+                Self::process_set_collection_verified(program_id, accounts, true, expected_revision)
             }
-            MetadataInstruction::ReplaceAttributes { data } => {
-                Self::process_replace_attributes(program_id, accounts, data)
+
+            MetadataInstruction::UnverifyCollectionMember { expected_revision } => {
+                Self::process_set_collection_verified(
+                    program_id,
+                    accounts,
+                    false,
+                    expected_revision,
+                )
             }
 
-            MetadataInstruction::TransferAuthority { new_authority } => {
-                Self::process_transfer_authority(accounts, new_authority)
+            MetadataInstruction::SetCreators {
+                creators,
+                expected_revision,
+            } => Self::process_set_creators(program_id, accounts, creators, expected_revision),
+
+            MetadataInstruction::SignCreator { expected_revision } => {
+                Self::process_sign_creator(program_id, accounts, expected_revision)
             }
 
-            MetadataInstruction::MakeImmutable => Self::process_make_immutable(accounts),
+            MetadataInstruction::RemoveCreator {
+                creator,
+                expected_revision,
+            } => Self::process_remove_creator(program_id, accounts, creator, expected_revision),
+
+            MetadataInstruction::GetMetadata => Self::process_get_metadata(program_id, accounts),
+            MetadataInstruction::CreateMetadataWithAttributes {
+                name,
+                symbol,
+                image,
+                description,
+                immutable,
+                uri,
+                data,
+            } => Self::process_create_metadata_with_attributes(
+                program_id,
+                accounts,
+                CreateMetadataArgs {
+                    name,
+                    symbol,
+                    image,
+                    description,
+                    immutable,
+                    uri,
+                },
+                data,
+            ),
+            MetadataInstruction::CreateExtension {
+                extension_type,
+                data,
+                expected_revision,
+            } => Self::process_create_extension(
+                program_id,
+                accounts,
+                extension_type,
+                data,
+                expected_revision,
+            ),
+            MetadataInstruction::UpdateExtension {
+                data,
+                expected_revision,
+            } => Self::process_update_extension(program_id, accounts, data, expected_revision),
+            MetadataInstruction::CloseExtension { expected_revision } => {
+                Self::process_close_extension(program_id, accounts, expected_revision)
+            }
         }
     }
 
     fn process_create_metadata(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        name: String,
-        symbol: String,
-        image: String,
-        description: String,
-        immutable: bool,
+        args: CreateMetadataArgs,
+    ) -> ProgramResult {
+        let metadata = Self::create_metadata_account(program_id, accounts, args)?;
+        MetadataEvent::MetadataCreated {
+            mint: metadata.mint,
+            update_authority: metadata.update_authority,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_create_metadata_with_attributes(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CreateMetadataArgs,
+        data: Vec<(String, String)>,
     ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let mint_info = next_account_info(account_info_iter)?; // []
+        let _metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let _mint_authority_info = next_account_info(account_info_iter)?; // [signer]
+        let attributes_info = next_account_info(account_info_iter)?; // [writable]
+
+        // The mint authority check in `create_metadata_account` also authorizes the
+        // attributes: the new metadata's update authority is that same signer
+        let metadata = Self::create_metadata_account(program_id, accounts, args)?;
+        Self::create_attributes_account(
+            program_id,
+            payer_info,
+            system_program_info,
+            mint_info,
+            attributes_info,
+            data,
+        )?;
+
+        MetadataEvent::MetadataWithAttributesCreated {
+            mint: metadata.mint,
+            update_authority: metadata.update_authority,
+        }
+        .emit();
+        Ok(())
+    }
+
+    /// Validate the mint authority and fields of a `CreateMetadata`, then create and write the
+    /// metadata PDA. `accounts` starts with the `CreateMetadata` accounts; any that follow are
+    /// ignored.
+    fn create_metadata_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CreateMetadataArgs,
+    ) -> Result<TokenMetadata, ProgramError> {
+        let CreateMetadataArgs {
+            name,
+            symbol,
+            image,
+            description,
+            immutable,
+            uri,
+        } = args;
         let account_info_iter = &mut accounts.iter();
         let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
         let system_program_info = next_account_info(account_info_iter)?; // []
@@ -149,24 +417,16 @@ impl Processor {
             return Err(ProgramError::InvalidSeeds);
         }
 
-        // Validate field sizes
-        if name.len() > NAME_MAX_LEN
-            || symbol.len() > SYMBOL_MAX_LEN
-            || image.len() > IMAGE_MAX_LEN
-            || description.len() > DESCRIPTION_MAX_LEN
-        {
-            msg!(
-                "Metadata field size is too long: name={}/{}, symbol={}/{}, image={}/{}, description={}/{}",
-                name.len(),
-                NAME_MAX_LEN,
-                symbol.len(),
-                SYMBOL_MAX_LEN,
-                image.len(),
-                IMAGE_MAX_LEN,
-                description.len(),
-                DESCRIPTION_MAX_LEN,
-            );
-            return Err(MetadataError::StringTooLong.into());
+        // Validate field sizes and content policy
+        if let Err(err) = validate_metadata_fields(&name, &symbol, &image, &description) {
+            msg!("Metadata content policy violation: {}", err);
+            return Err(err.into());
+        }
+        if let Some(ref content) = uri {
+            if let Err(err) = content.validate() {
+                msg!("Invalid content URI or hash: {}", err);
+                return Err(err.into());
+            }
         }
 
         // If not owned by this program, create metadata PDA via CPI using PDA seeds
@@ -215,7 +475,9 @@ impl Processor {
         }
 
         // Write metadata
-        let metadata = TokenMetadata {
+        let mut metadata = TokenMetadata {
+            account_type: AccountType::Metadata,
+            version: CURRENT_ACCOUNT_VERSION,
             is_initialized: true,
             mint: *mint_info.key,
             name,
@@ -223,71 +485,106 @@ impl Processor {
             image,
             description,
             update_authority: if immutable { None } else { matched_signer },
+            pending_authority: None,
+            locked_fields: 0,
+            collection: None,
+            uri: String::new(),
+            content_hash: [0u8; 32],
+            content_hash_algorithm: ContentHashAlgorithm::None,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: Some(md_bump),
+            delegate_count: 0,
+            extension_count: 0,
         };
+        if let Some(content) = uri {
+            metadata.set_content_uri(content);
+        }
 
-        metadata.pack_into_slice(&mut metadata_info.data.borrow_mut());
-
-        Ok(())
+        write_metadata(metadata_info, &mut metadata)?;
+        Ok(metadata)
     }
 
     fn process_update_metadata(
+        program_id: &Pubkey,
         accounts: &[AccountInfo],
-        name: Option<String>,
-        symbol: Option<String>,
-        image: Option<String>,
-        description: Option<String>,
+        args: UpdateMetadataArgs,
+        expected_revision: Option<u64>,
     ) -> ProgramResult {
+        let UpdateMetadataArgs {
+            name,
+            symbol,
+            image,
+            description,
+            uri,
+        } = args;
         let account_info_iter = &mut accounts.iter();
         let metadata_info = next_account_info(account_info_iter)?; // [writable]
-        let update_authority_info = next_account_info(account_info_iter)?; // [signer]
-
-        if !update_authority_info.is_signer {
-            msg!("Update authority is not a signer");
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        // Load existing metadata
-        let mut metadata = TokenMetadata::unpack(&metadata_info.data.borrow())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let remaining = account_info_iter.as_slice(); // delegate record, or multisig member signers
 
-        if !metadata.is_initialized() {
-            msg!("Metadata not initialized");
-            return Err(ProgramError::UninitializedAccount);
-        }
+        // Load existing metadata (owner, PDA and mint back-reference checked)
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
 
-        // Enforce update authority (immutable if None)
-        match metadata.update_authority {
-            Some(current_auth) => {
-                if !cmp_pubkeys(&current_auth, update_authority_info.key) {
-                    msg!("Update authority does not match");
-                    return Err(MetadataError::InvalidAuthority.into());
-                }
-            }
-            None => {
-                msg!("Metadata is immutable");
-                return Err(MetadataError::InvalidAuthority.into());
+        // Enforce update authority (immutable if None), or a delegate scoped to every touched field
+        let mut required = 0;
+        for (present, bit) in [
+            (name.is_some(), LOCK_NAME),
+            (symbol.is_some(), LOCK_SYMBOL),
+            (image.is_some(), LOCK_IMAGE),
+            (description.is_some(), LOCK_DESCRIPTION),
+            (uri.is_some(), LOCK_URI),
+        ] {
+            if present {
+                required |= bit;
             }
         }
+        // Every delegate covers the empty scope, so a field-less update would be a no-op write
+        if required == 0 {
+            msg!("UpdateMetadata must set at least one field");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Self::check_authority_or_delegate(
+            program_id,
+            &metadata,
+            update_authority_info,
+            remaining,
+            required,
+        )?;
 
-        // Validate and apply optional fields
-        if let Some(ref n) = name {
-            if n.len() > NAME_MAX_LEN {
-                return Err(MetadataError::StringTooLong.into());
-            }
+        // Reject changes to locked fields
+        if name.is_some() {
+            Self::check_unlocked(&metadata, LOCK_NAME, "name")?;
         }
-        if let Some(ref s) = symbol {
-            if s.len() > SYMBOL_MAX_LEN {
-                return Err(MetadataError::StringTooLong.into());
-            }
+        if symbol.is_some() {
+            Self::check_unlocked(&metadata, LOCK_SYMBOL, "symbol")?;
         }
-        if let Some(ref i) = image {
-            if i.len() > IMAGE_MAX_LEN {
-                return Err(MetadataError::StringTooLong.into());
-            }
+        if image.is_some() {
+            Self::check_unlocked(&metadata, LOCK_IMAGE, "image")?;
         }
-        if let Some(ref d) = description {
-            if d.len() > DESCRIPTION_MAX_LEN {
-                return Err(MetadataError::StringTooLong.into());
+        if description.is_some() {
+            Self::check_unlocked(&metadata, LOCK_DESCRIPTION, "description")?;
+        }
+        if uri.is_some() {
+            Self::check_unlocked(&metadata, LOCK_URI, "uri")?;
+        }
+
+        // Validate and apply optional fields
+        let policy = [
+            name.as_deref().map(validate_name),
+            symbol.as_deref().map(validate_symbol),
+            image.as_deref().map(validate_image),
+            description.as_deref().map(validate_description),
+        ];
+        if let Err(err) = policy.into_iter().flatten().collect::<Result<(), _>>() {
+            msg!("Metadata content policy violation: {}", err);
+            return Err(err.into());
+        }
+        if let Some(ref content) = uri {
+            if let Err(err) = content.validate() {
+                msg!("Invalid content URI or hash: {}", err);
+                return Err(err.into());
             }
         }
 
@@ -303,8 +600,16 @@ impl Processor {
         if let Some(d) = description {
             metadata.description = d;
         }
+        if let Some(content) = uri {
+            metadata.set_content_uri(content);
+        }
 
-        metadata.pack_into_slice(&mut metadata_info.data.borrow_mut());
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::MetadataUpdated {
+            mint: metadata.mint,
+            changed_fields: required,
+        }
+        .emit();
         Ok(())
     }
 
@@ -312,19 +617,59 @@ impl Processor {
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         data: Vec<(String, String)>,
+        expected_revision: Option<u64>,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
         let system_program_info = next_account_info(account_info_iter)?; // []
         let mint_info = next_account_info(account_info_iter)?; // []
         let attributes_info = next_account_info(account_info_iter)?; // [writable]
-        let update_authority_info = next_account_info(account_info_iter)?; // [signer]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
         let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
 
-        if !payer_info.is_signer || !update_authority_info.is_signer {
+        if !payer_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        // Ensure metadata exists, is canonical, belongs to this mint, and authority matches
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        if !cmp_pubkeys(&metadata.mint, mint_info.key) {
+            msg!("Metadata mint does not match provided mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        Self::check_unlocked(&metadata, LOCK_ATTRIBUTES, "attributes")?;
+
+        let attrs = Self::create_attributes_account(
+            program_id,
+            payer_info,
+            system_program_info,
+            mint_info,
+            attributes_info,
+            data,
+        )?;
+        MetadataEvent::AttributesCreated { mint: attrs.mint }.emit();
+        Ok(())
+    }
+
+    /// Validate `data`, then create (or resize a zeroed, program-owned) attributes PDA for
+    /// `mint_info` and write it. Callers must have authorized the write already.
+    fn create_attributes_account<'a>(
+        program_id: &Pubkey,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        mint_info: &AccountInfo<'a>,
+        attributes_info: &AccountInfo<'a>,
+        mut data: Vec<(String, String)>,
+    ) -> Result<TokenMetadataAttributes, ProgramError> {
         // Validate attribute PDA address using this program_id
         let (expected_attrs_pda, attrs_bump) =
             find_attributes_pda_with_program(program_id, mint_info.key);
@@ -333,51 +678,11 @@ impl Processor {
             return Err(ProgramError::InvalidSeeds);
         }
 
-        // Ensure metadata exists and authority matches
-        let metadata = TokenMetadata::unpack(&metadata_info.data.borrow())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        if !metadata.is_initialized() || !cmp_pubkeys(&metadata.mint, mint_info.key) {
-            msg!("Metadata not initialized or mint mismatch");
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        match metadata.update_authority {
-            Some(current_auth) => {
-                if !cmp_pubkeys(&current_auth, update_authority_info.key) {
-                    msg!("Update authority does not match");
-                    return Err(MetadataError::InvalidAuthority.into());
-                }
-            }
-            None => {
-                msg!("Metadata is immutable");
-                return Err(MetadataError::InvalidAuthority.into());
-            }
-        }
-
-        // Validate vector sizes and elements
-        if data.len() > MAX_ATTRIBUTES {
-            msg!("Too many attributes: {} > {}", data.len(), MAX_ATTRIBUTES);
-            return Err(MetadataError::TooManyAttributes.into());
-        }
-        for (k, v) in &data {
-            if k.is_empty() || v.is_empty() {
-                msg!("Attribute key and value must be non-empty");
-                return Err(MetadataError::InvalidInstructionData.into());
-            }
-            if k.len() > MAX_KEY_LENGTH || v.len() > MAX_VALUE_LENGTH {
-                msg!(
-                    "Attribute key or value is too long: key={}/{}, value={}/{}",
-                    k.len(),
-                    MAX_KEY_LENGTH,
-                    v.len(),
-                    MAX_VALUE_LENGTH,
-                );
-                return Err(MetadataError::StringTooLong.into());
-            }
-        }
+        // Validate vector sizes and elements; store in canonical key order
+        validate_attributes(&mut data)?;
 
-        // Allocate full max size so future replacements never need reallocation
-        let required_space: u64 = TokenMetadataAttributes::LEN as u64;
+        // Allocate only what the entries need; later writes resize the account
+        let required_space = attributes_account_len(&data) as u64;
 
         if attributes_info.owner != program_id {
             if *system_program_info.key != Pubkey::system_program() {
@@ -410,143 +715,1729 @@ impl Processor {
                 ]],
             )?;
         } else {
-            let curr_len = attributes_info.data.borrow().len() as u64;
-            if curr_len != required_space {
-                msg!(
-                    "Attributes account size mismatch: curr={} required={}",
-                    curr_len,
-                    required_space
-                );
-                return Err(ProgramError::InvalidAccountData);
-            }
-        }
-
-        // Ensure not already initialized
-        {
-            let data_ref = attributes_info.data.borrow();
-            if !data_ref.is_empty() && data_ref[0] != 0 {
-                return Err(MetadataError::MetadataAlreadyExists.into());
+            // Ensure not already initialized
+            {
+                let data_ref = attributes_info.data.borrow();
+                if !data_ref.is_empty() && data_ref[0] != 0 {
+                    return Err(MetadataError::MetadataAlreadyExists.into());
+                }
             }
+            resize_account(
+                attributes_info,
+                Some((payer_info, system_program_info)),
+                None,
+                required_space as usize,
+            )?;
         }
 
-        let attrs = TokenMetadataAttributes {
+        let mut attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
             is_initialized: true,
             mint: *mint_info.key,
             data,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: Some(attrs_bump),
         };
-        attrs.pack_into_slice(&mut attributes_info.data.borrow_mut());
-        Ok(())
+        write_attributes(
+            attributes_info,
+            Some((payer_info, system_program_info)),
+            None,
+            &mut attrs,
+        )?;
+        Ok(attrs)
     }
 
     fn process_replace_attributes(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        data: Vec<(String, String)>,
+        mut data: Vec<(String, String)>,
+        expected_revision: Option<u64>,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let attributes_info = next_account_info(account_info_iter)?; // [writable]
-        let update_authority_info = next_account_info(account_info_iter)?; // [signer]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
         let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
 
-        if !update_authority_info.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
+        // Optional [payer (writable, signer), system_program], needed only to grow the account;
+        // the rest is a delegate record, or multisig member signers
+        let (funding, remaining) = split_funding(account_info_iter.as_slice());
 
-        // Validate metadata and authority
-        let metadata = TokenMetadata::unpack(&metadata_info.data.borrow())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        if !metadata.is_initialized() {
-            return Err(ProgramError::UninitializedAccount);
-        }
-        match metadata.update_authority {
-            Some(current_auth) => {
-                if !cmp_pubkeys(&current_auth, update_authority_info.key) {
-                    return Err(MetadataError::InvalidAuthority.into());
-                }
+        // Validate metadata (owner, PDA) and authority
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_authority_or_delegate(
+            program_id,
+            &metadata,
+            update_authority_info,
+            remaining,
+            LOCK_ATTRIBUTES,
+        )?;
+        Self::check_unlocked(&metadata, LOCK_ATTRIBUTES, "attributes")?;
+
+        // Ensure attributes exist and belong to the same mint as the metadata
+        let mut attrs = Self::load_attributes(program_id, attributes_info, &metadata.mint)?;
+        Self::check_revision(attrs.revision, expected_revision)?;
+
+        // Validate sizes; store in canonical key order
+        validate_attributes(&mut data)?;
+
+        // Replace vector
+        attrs.data = data;
+        let refund_info =
+            rent_refund_recipient(program_id, &metadata, update_authority_info, funding);
+        write_attributes(attributes_info, funding, refund_info, &mut attrs)?;
+        MetadataEvent::AttributesReplaced { mint: attrs.mint }.emit();
+        Ok(())
+    }
+
+    fn process_upsert_attributes(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mut data: Vec<(String, String)>,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let attributes_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
+
+        // Optional [payer (writable, signer), system_program], needed only to grow the account;
+        // the rest is a delegate record, or multisig member signers
+        let (funding, remaining) = split_funding(account_info_iter.as_slice());
+
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_authority_or_delegate(
+            program_id,
+            &metadata,
+            update_authority_info,
+            remaining,
+            LOCK_ATTRIBUTES,
+        )?;
+        Self::check_unlocked(&metadata, LOCK_ATTRIBUTES, "attributes")?;
+        let mut attrs = Self::load_attributes(program_id, attributes_info, &metadata.mint)?;
+        Self::check_revision(attrs.revision, expected_revision)?;
+
+        validate_attributes(&mut data)?;
+
+        // Patch in place, keeping entries sorted: overwrite existing keys, insert new ones
+        attrs.normalize();
+        let keys = data.iter().map(|(k, _)| k.clone()).collect();
+        for (k, v) in data {
+            match attrs
+                .data
+                .binary_search_by(|(existing, _)| existing.as_str().cmp(k.as_str()))
+            {
+                Ok(i) => attrs.data[i].1 = v,
+                Err(i) => attrs.data.insert(i, (k, v)),
             }
-            None => return Err(MetadataError::InvalidAuthority.into()),
         }
-
-        // Validate PDA
-        let (expected_attrs_pda, _bump) =
-            find_attributes_pda_with_program(program_id, &metadata.mint);
-        if !cmp_pubkeys(&expected_attrs_pda, attributes_info.key) {
-            return Err(ProgramError::InvalidSeeds);
+        if attrs.data.len() > MAX_ATTRIBUTES {
+            msg!(
+                "Too many attributes after upsert: {} > {}",
+                attrs.data.len(),
+                MAX_ATTRIBUTES
+            );
+            return Err(MetadataError::TooManyAttributes.into());
         }
 
-        // Ensure attributes exist
-        let mut attrs = TokenMetadataAttributes::unpack(&attributes_info.data.borrow())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        if !attrs.is_initialized() {
-            return Err(ProgramError::UninitializedAccount);
+        let refund_info =
+            rent_refund_recipient(program_id, &metadata, update_authority_info, funding);
+        write_attributes(attributes_info, funding, refund_info, &mut attrs)?;
+        MetadataEvent::AttributesUpserted {
+            mint: attrs.mint,
+            keys,
         }
+        .emit();
+        Ok(())
+    }
+
+    fn process_remove_attributes(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        keys: Vec<String>,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let attributes_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
 
-        // Validate sizes
-        if data.len() > MAX_ATTRIBUTES {
+        // Optional [payer (writable, signer), system_program]; removing entries only ever
+        // shrinks the account, so the payer only receives the freed rent. The rest is a
+        // delegate record, or multisig member signers
+        let (funding, remaining) = split_funding(account_info_iter.as_slice());
+
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_authority_or_delegate(
+            program_id,
+            &metadata,
+            update_authority_info,
+            remaining,
+            LOCK_ATTRIBUTES,
+        )?;
+        Self::check_unlocked(&metadata, LOCK_ATTRIBUTES, "attributes")?;
+        let mut attrs = Self::load_attributes(program_id, attributes_info, &metadata.mint)?;
+        Self::check_revision(attrs.revision, expected_revision)?;
+
+        if keys.len() > MAX_ATTRIBUTES {
+            msg!("Too many keys: {} > {}", keys.len(), MAX_ATTRIBUTES);
             return Err(MetadataError::TooManyAttributes.into());
         }
-        for (k, v) in &data {
-            if k.is_empty() || v.is_empty() {
+        for k in &keys {
+            if k.is_empty() {
+                msg!("Attribute key must be non-empty");
                 return Err(MetadataError::InvalidInstructionData.into());
             }
-            if k.len() > MAX_KEY_LENGTH || v.len() > MAX_VALUE_LENGTH {
+            if k.len() > MAX_KEY_LENGTH {
+                msg!("Attribute key is too long: {}/{}", k.len(), MAX_KEY_LENGTH);
                 return Err(MetadataError::StringTooLong.into());
             }
         }
 
-        // Replace vector
-        attrs.data = data;
-        attrs.pack_into_slice(&mut attributes_info.data.borrow_mut());
+        attrs.normalize();
+        attrs.data.retain(|(k, _)| !keys.contains(k));
+
+        let refund_info =
+            rent_refund_recipient(program_id, &metadata, update_authority_info, funding);
+        write_attributes(attributes_info, funding, refund_info, &mut attrs)?;
+        MetadataEvent::AttributesRemoved {
+            mint: attrs.mint,
+            keys,
+        }
+        .emit();
         Ok(())
     }
 
     fn process_transfer_authority(
+        program_id: &Pubkey,
         accounts: &[AccountInfo],
         new_authority: Pubkey,
+        expected_revision: Option<u64>,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let metadata_info = next_account_info(account_info_iter)?; // [writable]
-        let current_authority_info = next_account_info(account_info_iter)?; // [signer]
+        let current_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
 
-        if !current_authority_info.is_signer {
-            msg!("Current authority is not a signer");
-            return Err(ProgramError::MissingRequiredSignature);
-        }
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            current_authority_info,
+            multisig_signer_infos,
+        )?;
 
-        let mut metadata = TokenMetadata::unpack(&metadata_info.data.borrow())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        if !metadata.is_initialized() {
-            msg!("Metadata not initialized");
-            return Err(ProgramError::UninitializedAccount);
+        let old_authority = *current_authority_info.key;
+        metadata.update_authority = Some(new_authority);
+        // A stale proposal must not survive a handover it was not part of
+        metadata.pending_authority = None;
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::AuthorityTransferred {
+            mint: metadata.mint,
+            old_authority,
+            new_authority,
         }
+        .emit();
+        Ok(())
+    }
 
-        match metadata.update_authority {
-            Some(current_auth) => {
-                if !cmp_pubkeys(&current_auth, current_authority_info.key) {
-                    msg!("Signer is not current update authority");
-                    return Err(MetadataError::InvalidAuthority.into());
+    fn process_make_immutable(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let current_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            current_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        metadata.update_authority = None;
+        metadata.pending_authority = None;
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::MadeImmutable {
+            mint: metadata.mint,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_propose_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_authority: Pubkey,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let current_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            current_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        // Replaces any outstanding proposal
+        metadata.pending_authority = Some(new_authority);
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::AuthorityProposed {
+            mint: metadata.mint,
+            proposed_authority: new_authority,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_accept_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let pending_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        // A multisig accepts with its members' signatures, like every other authority action
+        check_signed(program_id, pending_authority_info, multisig_signer_infos)?;
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        if metadata.update_authority.is_none() {
+            msg!("Metadata is immutable; cannot accept authority");
+            return Err(MetadataError::InvalidAuthority.into());
+        }
+        match metadata.pending_authority {
+            Some(pending) => {
+                if !cmp_pubkeys(&pending, pending_authority_info.key) {
+                    msg!("Signer is not the pending authority");
+                    return Err(MetadataError::InvalidAuthority.into());
+                }
+            }
+            None => {
+                msg!("No pending authority proposal");
+                return Err(MetadataError::NoPendingAuthority.into());
+            }
+        }
+
+        let old_authority = metadata.update_authority;
+        metadata.update_authority = metadata.pending_authority.take();
+        write_metadata(metadata_info, &mut metadata)?;
+        if let (Some(old_authority), Some(new_authority)) =
+            (old_authority, metadata.update_authority)
+        {
+            MetadataEvent::AuthorityTransferred {
+                mint: metadata.mint,
+                old_authority,
+                new_authority,
+            }
+            .emit();
+        }
+        Ok(())
+    }
+
+    fn process_cancel_proposal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let current_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            current_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        if metadata.pending_authority.is_none() {
+            msg!("No pending authority proposal");
+            return Err(MetadataError::NoPendingAuthority.into());
+        }
+
+        metadata.pending_authority = None;
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::ProposalCancelled {
+            mint: metadata.mint,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_lock_fields(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mask: u16,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        if mask == 0 || mask & !LOCK_ALL != 0 {
+            msg!("Invalid lock mask: {:#06x}", mask);
+            return Err(MetadataError::InvalidInstructionData.into());
+        }
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        // Bits are only ever added; there is no instruction that clears them
+        metadata.locked_fields |= mask;
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::FieldsLocked {
+            mint: metadata.mint,
+            locked_fields: metadata.locked_fields,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_set_collection(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        collection_mint: Option<Pubkey>,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let collection_metadata_info = match collection_mint {
+            Some(_) => Some(next_account_info(account_info_iter)?), // []
+            None => None,
+        };
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        metadata.collection = match (collection_mint, collection_metadata_info) {
+            (Some(collection_mint), Some(collection_metadata_info)) => {
+                let collection = Self::load_metadata(program_id, collection_metadata_info)?;
+                if !cmp_pubkeys(&collection.mint, &collection_mint)
+                    || cmp_pubkeys(&collection_mint, &metadata.mint)
+                {
+                    msg!("Collection metadata does not match collection mint");
+                    return Err(MetadataError::CollectionMismatch.into());
+                }
+                // Re-pointing at a different collection drops any earlier verification
+                let verified = metadata
+                    .collection
+                    .is_some_and(|c| c.verified && cmp_pubkeys(&c.key, &collection_mint));
+                Some(Collection {
+                    key: collection_mint,
+                    verified,
+                })
+            }
+            _ => None,
+        };
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::CollectionSet {
+            mint: metadata.mint,
+            collection_mint,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_set_collection_verified(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        verified: bool,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let collection_metadata_info = next_account_info(account_info_iter)?; // []
+        let authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        let collection = Self::load_metadata(program_id, collection_metadata_info)?;
+        // Unverifying only lowers trust, so the member's own update authority may do it too;
+        // otherwise members of an immutable collection could never shed a verification
+        let by_member = !verified
+            && metadata
+                .update_authority
+                .is_some_and(|auth| cmp_pubkeys(&auth, authority_info.key));
+        Self::check_update_authority_signers(
+            program_id,
+            if by_member { &metadata } else { &collection },
+            authority_info,
+            multisig_signer_infos,
+        )?;
+
+        let Some(member_collection) = metadata.collection.as_mut() else {
+            msg!("Metadata does not reference a collection");
+            return Err(MetadataError::CollectionMismatch.into());
+        };
+        if !cmp_pubkeys(&member_collection.key, &collection.mint) {
+            msg!("Metadata references a different collection");
+            return Err(MetadataError::CollectionMismatch.into());
+        }
+
+        member_collection.verified = verified;
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::CollectionVerificationChanged {
+            mint: metadata.mint,
+            collection_mint: collection.mint,
+            verified,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_set_creators(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        creators: Vec<(Pubkey, u8)>,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let creators_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        // An existing account carries its bump; only first use searches for it
+        let stored = if creators_info.owner == program_id {
+            Some(TokenMetadataCreators::unpack_unchecked(
+                &creators_info.data.borrow(),
+            )?)
+        } else {
+            None
+        };
+        let (expected_creators_pda, creators_bump) = match stored.as_ref().and_then(|r| r.bump) {
+            Some(bump) => (
+                create_creators_pda_with_bump(program_id, &metadata.mint, bump)?,
+                bump,
+            ),
+            None => find_creators_pda_with_program(program_id, &metadata.mint),
+        };
+        if !cmp_pubkeys(&expected_creators_pda, creators_info.key) {
+            msg!("Creators PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        let existing = if let Some(stored) = stored {
+            stored.creators
+        } else {
+            if *system_program_info.key != Pubkey::system_program() {
+                msg!("System program id does not match expected system program id");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            invoke_signed(
+                &create_account(
+                    payer_info.key,
+                    creators_info.key,
+                    minimum_rent(TokenMetadataCreators::LEN),
+                    TokenMetadataCreators::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_info.clone(),
+                    creators_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    CREATORS_SEED, //
+                    metadata.mint.as_ref(),
+                    &[creators_bump],
+                ]],
+            )?;
+            Vec::new()
+        };
+
+        // A verification only vouches for the exact entry the creator signed
+        let creators: Vec<Creator> = creators
+            .into_iter()
+            .map(|(address, share)| Creator {
+                address,
+                verified: existing
+                    .iter()
+                    .any(|c| c.verified && c.share == share && cmp_pubkeys(&c.address, &address)),
+                share,
+            })
+            .collect();
+        if let Err(err) = validate_creators(&creators) {
+            msg!("Invalid creators list: {}", err);
+            return Err(err.into());
+        }
+
+        let record = TokenMetadataCreators {
+            account_type: AccountType::Creators,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: metadata.mint,
+            creators,
+            bump: Some(creators_bump),
+        };
+        record.pack_into_slice(&mut creators_info.data.borrow_mut());
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::CreatorsSet {
+            mint: record.mint,
+            creators: record.creators,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_sign_creator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creators_info = next_account_info(account_info_iter)?; // [writable]
+        let creator_info = next_account_info(account_info_iter)?; // [signer]
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+
+        if !creator_info.is_signer {
+            msg!("Creator is not a signer");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut record = Self::load_creators(program_id, creators_info)?;
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        if !cmp_pubkeys(&record.mint, &metadata.mint) {
+            msg!("Creators account mint does not match metadata mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+        Self::check_revision(metadata.revision, expected_revision)?;
+        let Some(creator) = record
+            .creators
+            .iter_mut()
+            .find(|c| cmp_pubkeys(&c.address, creator_info.key))
+        else {
+            msg!("Signer is not a listed creator");
+            return Err(MetadataError::CreatorNotFound.into());
+        };
+
+        creator.verified = true;
+        record.pack_into_slice(&mut creators_info.data.borrow_mut());
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::CreatorSigned {
+            mint: record.mint,
+            creator: *creator_info.key,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_remove_creator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        creator: Pubkey,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creators_info = next_account_info(account_info_iter)?; // [writable]
+        let signer_info = next_account_info(account_info_iter)?; // [signer] or multisig authority
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        let mut record = Self::load_creators(program_id, creators_info)?;
+        if !cmp_pubkeys(&record.mint, &metadata.mint) {
+            msg!("Creators account mint does not match metadata mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        // The update authority can remove anyone; a creator can always remove itself
+        let is_authority = metadata
+            .update_authority
+            .is_some_and(|auth| cmp_pubkeys(&auth, signer_info.key));
+        if !is_authority && !cmp_pubkeys(&creator, signer_info.key) {
+            msg!("Signer is neither the update authority nor the creator");
+            return Err(MetadataError::InvalidAuthority.into());
+        }
+        check_signed(program_id, signer_info, multisig_signer_infos)?;
+
+        let Some(index) = record
+            .creators
+            .iter()
+            .position(|c| cmp_pubkeys(&c.address, &creator))
+        else {
+            msg!("Creator is not listed");
+            return Err(MetadataError::CreatorNotFound.into());
+        };
+        record.creators.remove(index);
+        record.pack_into_slice(&mut creators_info.data.borrow_mut());
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::CreatorRemoved {
+            mint: record.mint,
+            creator,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_create_extension(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        extension_type: u16,
+        data: Vec<u8>,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let extension_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        if !payer_info.is_signer {
+            msg!("Payer is not a signer");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        if ExtensionType::from_u16(extension_type).is_none() {
+            msg!("Unknown extension type: {}", extension_type);
+            return Err(MetadataError::UnknownExtensionType.into());
+        }
+        if let Err(err) = validate_extension_data(&data) {
+            msg!("Extension data is too long: {}", data.len());
+            return Err(err.into());
+        }
+
+        let (expected_ext_pda, ext_bump) =
+            find_extension_pda_with_program(program_id, &metadata.mint, extension_type);
+        if !cmp_pubkeys(&expected_ext_pda, extension_info.key) {
+            msg!("Extension PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        // Allocate only what the payload needs; updates resize the account
+        let required_space = extension_account_len(data.len());
+
+        // Closed extensions are handed back to the system program, so one owned by this
+        // program is live
+        if extension_info.owner == program_id {
+            return Err(MetadataError::MetadataAlreadyExists.into());
+        }
+        if *system_program_info.key != Pubkey::system_program() {
+            msg!("System program id does not match expected system program id");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        invoke_signed(
+            &create_account(
+                payer_info.key,
+                extension_info.key,
+                minimum_rent(required_space),
+                required_space as u64,
+                program_id,
+            ),
+            &[
+                payer_info.clone(),
+                extension_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                EXTENSION_SEED,
+                metadata.mint.as_ref(),
+                &extension_type.to_le_bytes(),
+                &[ext_bump],
+            ]],
+        )?;
+
+        let mut extension = TokenMetadataExtension {
+            account_type: AccountType::Extension,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: metadata.mint,
+            extension_type,
+            data,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: ext_bump,
+        };
+        write_extension(
+            extension_info,
+            Some((payer_info, system_program_info)),
+            None,
+            &mut extension,
+        )?;
+
+        metadata.extension_count = metadata
+            .extension_count
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::ExtensionCreated {
+            mint: metadata.mint,
+            extension_type,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_update_extension(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: Vec<u8>,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let extension_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
+
+        // Optional [payer (writable, signer), system_program], needed only to grow the account;
+        // the rest are multisig member signers
+        let (funding, multisig_signer_infos) = split_funding(account_info_iter.as_slice());
+
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+        let mut extension = Self::load_extension(program_id, extension_info, &metadata.mint)?;
+        Self::check_revision(extension.revision, expected_revision)?;
+
+        if let Err(err) = validate_extension_data(&data) {
+            msg!("Extension data is too long: {}", data.len());
+            return Err(err.into());
+        }
+
+        extension.data = data;
+        let refund_info =
+            rent_refund_recipient(program_id, &metadata, update_authority_info, funding);
+        write_extension(extension_info, funding, refund_info, &mut extension)?;
+        MetadataEvent::ExtensionUpdated {
+            mint: extension.mint,
+            extension_type: extension.extension_type,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_close_extension(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let extension_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let destination_info = next_account_info(account_info_iter)?; // [writable]
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+        let extension = Self::load_extension(program_id, extension_info, &metadata.mint)?;
+        Self::check_revision(extension.revision, expected_revision)?;
+
+        close_program_account(extension_info, destination_info)?;
+        metadata.extension_count = metadata.extension_count.saturating_sub(1);
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::ExtensionClosed {
+            mint: extension.mint,
+            extension_type: extension.extension_type,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_get_metadata(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        set_return_data(&Self::get_metadata_return_data(program_id, accounts)?);
+        Ok(())
+    }
+
+    /// Validate `GetMetadata`'s accounts and encode the `TokenMetadataSummary` it returns
+    fn get_metadata_return_data(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> Result<Vec<u8>, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
+        let mint_info = next_account_info(account_info_iter)?; // [] (readonly)
+
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        if !cmp_pubkeys(&metadata.mint, mint_info.key) {
+            msg!("Metadata does not belong to the provided mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        borsh::to_vec(&TokenMetadataSummary::from(&metadata))
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn process_add_delegate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        delegate: Pubkey,
+        scope: u16,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let delegate_record_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if scope == 0 || scope & !LOCK_ALL != 0 {
+            msg!("Invalid delegate scope: {:#06x}", scope);
+            return Err(MetadataError::InvalidInstructionData.into());
+        }
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        // Existing records (including ones voided by an authority change) are re-granted and
+        // carry their bump; only a new record searches for it
+        let stored = if delegate_record_info.owner == program_id {
+            Some(TokenMetadataDelegate::unpack_unchecked(
+                &delegate_record_info.data.borrow(),
+            )?)
+        } else {
+            None
+        };
+        let (expected_delegate_pda, delegate_bump) = match stored.as_ref().and_then(|r| r.bump) {
+            Some(bump) => (
+                create_delegate_pda_with_bump(program_id, &metadata.mint, &delegate, bump)?,
+                bump,
+            ),
+            None => find_delegate_pda_with_program(program_id, &metadata.mint, &delegate),
+        };
+        if !cmp_pubkeys(&expected_delegate_pda, delegate_record_info.key) {
+            msg!("Delegate PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        let was_open = if let Some(stored) = stored {
+            stored.is_initialized()
+        } else {
+            if *system_program_info.key != Pubkey::system_program() {
+                msg!("System program id does not match expected system program id");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            invoke_signed(
+                &create_account(
+                    payer_info.key,
+                    delegate_record_info.key,
+                    minimum_rent(TokenMetadataDelegate::LEN),
+                    TokenMetadataDelegate::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_info.clone(),
+                    delegate_record_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    DELEGATE_SEED, //
+                    metadata.mint.as_ref(),
+                    delegate.as_ref(),
+                    &[delegate_bump],
+                ]],
+            )?;
+            false
+        };
+
+        let record = TokenMetadataDelegate {
+            account_type: AccountType::Delegate,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: metadata.mint,
+            delegate,
+            authority: *update_authority_info.key,
+            scope,
+            bump: Some(delegate_bump),
+        };
+        record.pack_into_slice(&mut delegate_record_info.data.borrow_mut());
+        if !was_open {
+            metadata.delegate_count = metadata
+                .delegate_count
+                .checked_add(1)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::DelegateAdded {
+            mint: record.mint,
+            delegate,
+            scope,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_revoke_delegate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let delegate_record_info = next_account_info(account_info_iter)?; // [writable]
+        let signer_info = next_account_info(account_info_iter)?; // [signer] or multisig authority
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let destination_info = next_account_info(account_info_iter)?; // [writable]
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        let record = Self::load_delegate(program_id, delegate_record_info, &metadata.mint)?;
+
+        // The update authority can revoke any delegate; a delegate can renounce its own record
+        let is_authority = metadata
+            .update_authority
+            .is_some_and(|auth| cmp_pubkeys(&auth, signer_info.key));
+        if !is_authority && !cmp_pubkeys(&record.delegate, signer_info.key) {
+            msg!("Signer is neither the update authority nor the delegate");
+            return Err(MetadataError::InvalidAuthority.into());
+        }
+        check_signed(program_id, signer_info, multisig_signer_infos)?;
+
+        // The authority's side funded the record, so a renouncing delegate cannot pick where the
+        // rent goes: it returns to the update authority (the granting one once immutable)
+        if !is_authority {
+            let owner = metadata.update_authority.unwrap_or(record.authority);
+            if !cmp_pubkeys(&owner, destination_info.key) {
+                msg!("A renouncing delegate must return the rent to the update authority");
+                return Err(MetadataError::InvalidAuthority.into());
+            }
+        }
+
+        close_program_account(delegate_record_info, destination_info)?;
+        metadata.delegate_count = metadata.delegate_count.saturating_sub(1);
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::DelegateRevoked {
+            mint: metadata.mint,
+            delegate: record.delegate,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_create_multisig(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        m: u8,
+        signers: Vec<Pubkey>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let multisig_info = next_account_info(account_info_iter)?; // [writable, signer]
+
+        if !payer_info.is_signer || !multisig_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let multisig = TokenMetadataMultisig {
+            account_type: AccountType::Multisig,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            m,
+            signers,
+        };
+        if let Err(err) = multisig.validate() {
+            msg!(
+                "Invalid multisig: m={} n={} (max {})",
+                m,
+                multisig.signers.len(),
+                MAX_MULTISIG_SIGNERS
+            );
+            return Err(err.into());
+        }
+
+        if multisig_info.owner != program_id {
+            if *system_program_info.key != Pubkey::system_program() {
+                msg!("System program id does not match expected system program id");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            invoke(
+                &create_account(
+                    payer_info.key,
+                    multisig_info.key,
+                    minimum_rent(TokenMetadataMultisig::LEN),
+                    TokenMetadataMultisig::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_info.clone(),
+                    multisig_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        } else {
+            let data = multisig_info.data.borrow();
+            if data.len() != TokenMetadataMultisig::LEN {
+                msg!("Multisig account size mismatch");
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if data[0] != 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+        }
+
+        multisig.pack_into_slice(&mut multisig_info.data.borrow_mut());
+        MetadataEvent::MultisigCreated {
+            multisig: *multisig_info.key,
+            m,
+            signers: multisig.signers,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_add_multisig_signer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signer: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let multisig_info = next_account_info(account_info_iter)?; // [writable]
+        let member_signer_infos = account_info_iter.as_slice(); // [signer] current members
+
+        let mut multisig = Self::load_multisig(program_id, multisig_info)?;
+        check_multisig_signers(&multisig, member_signer_infos)?;
+
+        multisig.signers.push(signer);
+        if let Err(err) = multisig.validate() {
+            msg!("Signer already a member or multisig is full");
+            return Err(err.into());
+        }
+
+        multisig.pack_into_slice(&mut multisig_info.data.borrow_mut());
+        MetadataEvent::MultisigSignerAdded {
+            multisig: *multisig_info.key,
+            signer,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_remove_multisig_signer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signer: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let multisig_info = next_account_info(account_info_iter)?; // [writable]
+        let member_signer_infos = account_info_iter.as_slice(); // [signer] current members
+
+        let mut multisig = Self::load_multisig(program_id, multisig_info)?;
+        check_multisig_signers(&multisig, member_signer_infos)?;
+
+        let Some(index) = multisig
+            .signers
+            .iter()
+            .position(|member| cmp_pubkeys(member, &signer))
+        else {
+            msg!("Signer is not a member of this multisig");
+            return Err(MetadataError::InvalidMultisig.into());
+        };
+        multisig.signers.remove(index);
+        if let Err(err) = multisig.validate() {
+            msg!("Removing this member would leave fewer members than required signatures");
+            return Err(err.into());
+        }
+
+        multisig.pack_into_slice(&mut multisig_info.data.borrow_mut());
+        MetadataEvent::MultisigSignerRemoved {
+            multisig: *multisig_info.key,
+            signer,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_migrate_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let account_info = next_account_info(account_info_iter)?; // [writable]
+
+        if !payer_info.is_signer {
+            msg!("Payer is not a signer");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if *system_program_info.key != Pubkey::system_program() {
+            msg!("System program id does not match expected system program id");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if account_info.owner != program_id {
+            msg!("Account is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        let (account_type, upgraded) = {
+            let data = account_info.data.borrow();
+            if data.len() < ACCOUNT_HEADER_LEN + 1 + 32 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if data[0] != LEGACY_V0_INITIALIZED {
+                let account_type = match data[0] {
+                    t if t == AccountType::Metadata as u8 => AccountType::Metadata,
+                    t if t == AccountType::Attributes as u8 => AccountType::Attributes,
+                    t if t == AccountType::Touch as u8 => AccountType::Touch,
+                    t if t == AccountType::Delegate as u8 => AccountType::Delegate,
+                    t if t == AccountType::Multisig as u8 => AccountType::Multisig,
+                    t if t == AccountType::Creators as u8 => AccountType::Creators,
+                    t if t == AccountType::Extension as u8 => AccountType::Extension,
+                    _ => {
+                        msg!("Account is not a legacy account");
+                        return Err(MetadataError::InvalidAccountType.into());
+                    }
+                };
+                if data[1] != CURRENT_ACCOUNT_VERSION {
+                    msg!("Unsupported account version: {}", data[1]);
+                    return Err(MetadataError::UnsupportedAccountVersion.into());
+                }
+                // Attributes and extension accounts are sized to their contents, so any size
+                // is current
+                if data.len() >= account_len(account_type)
+                    || account_type == AccountType::Attributes
+                    || account_type == AccountType::Extension
+                {
+                    msg!("Account already uses the current layout");
+                    return Ok(());
+                }
+                // Allocated before trailing fields were appended: grow in place, new fields
+                // decode as their zero defaults
+                (account_type, data.to_vec())
+            } else {
+                // Both v0 layouts store the mint right after `is_initialized`; the account
+                // type is whichever PDA for that mint matches this account.
+                let mint = Pubkey::from_slice(&data[1..33]);
+                let account_type = if cmp_pubkeys(
+                    &find_metadata_pda_with_program(program_id, &mint).0,
+                    account_info.key,
+                ) {
+                    AccountType::Metadata
+                } else if cmp_pubkeys(
+                    &find_attributes_pda_with_program(program_id, &mint).0,
+                    account_info.key,
+                ) {
+                    AccountType::Attributes
+                } else {
+                    msg!("Legacy account does not match a PDA for its mint");
+                    return Err(MetadataError::InvalidPda.into());
+                };
+
+                (
+                    account_type,
+                    upgrade_legacy_account_data(&data, account_type)?,
+                )
+            }
+        };
+
+        // Attributes are sized to their entries (the upgraded image already is), everything
+        // else to its full layout
+        let new_len = match account_type {
+            AccountType::Attributes => upgraded.len(),
+            _ => account_len(account_type),
+        };
+
+        // The payer tops up rent for a larger account and receives what a smaller one frees
+        if cmp_pubkeys(account_info.key, payer_info.key) {
+            msg!("Payer must differ from the account being migrated");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let required_lamports = minimum_rent(new_len);
+        let current_lamports = account_info.lamports();
+        match required_lamports.cmp(&current_lamports) {
+            Ordering::Greater => {
+                invoke(
+                    &transfer(
+                        payer_info.key,
+                        account_info.key,
+                        required_lamports - current_lamports,
+                    ),
+                    &[
+                        payer_info.clone(),
+                        account_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+            }
+            Ordering::Less => {
+                let refund_lamports = payer_info
+                    .lamports()
+                    .checked_add(current_lamports - required_lamports)
+                    .ok_or(ProgramError::InvalidArgument)?;
+                **payer_info.lamports.borrow_mut() = refund_lamports;
+                **account_info.lamports.borrow_mut() = required_lamports;
+            }
+            Ordering::Equal => {}
+        }
+        account_info.realloc(new_len, true)?;
+
+        let mut data = account_info.data.borrow_mut();
+        let copy_len = upgraded.len().min(new_len);
+        data[..copy_len].copy_from_slice(&upgraded[..copy_len]);
+        for b in &mut data[copy_len..] {
+            *b = 0;
+        }
+        MetadataEvent::AccountMigrated {
+            account: *account_info.key,
+            account_type,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_close_attributes(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let attributes_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let metadata_info = next_account_info(account_info_iter)?; // [] (readonly)
+        let destination_info = next_account_info(account_info_iter)?; // [writable]
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+        Self::check_unlocked(&metadata, LOCK_ATTRIBUTES, "attributes")?;
+        let attrs = Self::load_attributes(program_id, attributes_info, &metadata.mint)?;
+        Self::check_revision(attrs.revision, expected_revision)?;
+
+        close_program_account(attributes_info, destination_info)?;
+        MetadataEvent::AttributesClosed {
+            mint: metadata.mint,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_close_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let mint_info = next_account_info(account_info_iter)?; // []
+        let attributes_info = next_account_info(account_info_iter)?; // []
+        let destination_info = next_account_info(account_info_iter)?; // [writable]
+        let touch_info = next_account_info(account_info_iter)?; // [writable]
+        let creators_info = next_account_info(account_info_iter)?; // [writable]
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        let metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+
+        // Only tokens with no circulating supply may lose their metadata
+        if mint_info.owner != &apl_token::id() {
+            msg!("Mint is not owned by the token program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if !cmp_pubkeys(&metadata.mint, mint_info.key) {
+            msg!("Metadata mint does not match provided mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+        let mint = Mint::unpack(&mint_info.data.borrow())?;
+        if mint.supply != 0 {
+            msg!("Mint supply is not zero: {}", mint.supply);
+            return Err(MetadataError::MintSupplyNotZero.into());
+        }
+
+        // Attributes must be closed first so no orphaned account survives the metadata
+        let (expected_attrs_pda, _bump) =
+            find_attributes_pda_with_program(program_id, &metadata.mint);
+        if !cmp_pubkeys(&expected_attrs_pda, attributes_info.key) {
+            msg!("Attributes PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+        if attributes_info.owner == program_id
+            && attributes_info.data.borrow().iter().any(|b| *b != 0)
+        {
+            msg!("Attributes account must be closed before metadata");
+            return Err(MetadataError::AttributesStillOpen.into());
+        }
+
+        // Delegate records and extensions cannot be enumerated here, so their counts must be
+        // zero; a record that outlived its metadata would reattach to metadata re-created for
+        // the same mint
+        if metadata.delegate_count != 0 {
+            msg!(
+                "{} delegate records must be revoked first",
+                metadata.delegate_count
+            );
+            return Err(MetadataError::DelegatesStillOpen.into());
+        }
+        if metadata.extension_count != 0 {
+            msg!(
+                "{} extensions must be closed first",
+                metadata.extension_count
+            );
+            return Err(MetadataError::ExtensionsStillOpen.into());
+        }
+
+        // The touch and creators PDAs have no close instruction of their own; close them here.
+        // Live accounts carry their bump, the search is only for ones that never existed.
+        let touch_bump = (touch_info.owner == program_id)
+            .then(|| TokenMetadataTouch::unpack_from_slice(&touch_info.data.borrow()).ok())
+            .flatten()
+            .and_then(|t| t.bump);
+        let expected_touch_pda = match touch_bump {
+            Some(bump) => create_touch_pda_with_bump(program_id, &metadata.mint, bump)?,
+            None => find_touch_pda_with_program(program_id, &metadata.mint).0,
+        };
+        if !cmp_pubkeys(&expected_touch_pda, touch_info.key) {
+            msg!("Touch PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+        let creators_bump = (creators_info.owner == program_id)
+            .then(|| TokenMetadataCreators::unpack_from_slice(&creators_info.data.borrow()).ok())
+            .flatten()
+            .and_then(|r| r.bump);
+        let expected_creators_pda = match creators_bump {
+            Some(bump) => create_creators_pda_with_bump(program_id, &metadata.mint, bump)?,
+            None => find_creators_pda_with_program(program_id, &metadata.mint).0,
+        };
+        if !cmp_pubkeys(&expected_creators_pda, creators_info.key) {
+            msg!("Creators PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+        for info in [touch_info, creators_info] {
+            if info.owner == program_id && info.lamports() > 0 {
+                close_program_account(info, destination_info)?;
+            }
+        }
+
+        close_program_account(metadata_info, destination_info)?;
+        MetadataEvent::MetadataClosed {
+            mint: metadata.mint,
+        }
+        .emit();
+        Ok(())
+    }
+
+    fn process_touch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?; // [writable, signer]
+        let system_program_info = next_account_info(account_info_iter)?; // []
+        let metadata_info = next_account_info(account_info_iter)?; // [writable]
+        let touch_info = next_account_info(account_info_iter)?; // [writable]
+        let update_authority_info = next_account_info(account_info_iter)?; // [signer] or multisig
+        let multisig_signer_infos = account_info_iter.as_slice(); // [signer] multisig members
+
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut metadata = Self::load_metadata(program_id, metadata_info)?;
+        Self::check_revision(metadata.revision, expected_revision)?;
+        Self::check_update_authority_signers(
+            program_id,
+            &metadata,
+            update_authority_info,
+            multisig_signer_infos,
+        )?;
+
+        // An existing account carries its bump; only the first touch searches for it
+        let stored = if touch_info.owner == program_id {
+            Some(
+                TokenMetadataTouch::unpack_unchecked(&touch_info.data.borrow())
+                    .map_err(|_| ProgramError::InvalidAccountData)?,
+            )
+        } else {
+            None
+        };
+        let (expected_touch_pda, touch_bump) = match stored.as_ref().and_then(|t| t.bump) {
+            Some(bump) => (
+                create_touch_pda_with_bump(program_id, &metadata.mint, bump)?,
+                bump,
+            ),
+            None => find_touch_pda_with_program(program_id, &metadata.mint),
+        };
+        if !cmp_pubkeys(&expected_touch_pda, touch_info.key) {
+            msg!("Touch PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        // First touch creates the PDA
+        if stored.is_none() {
+            if *system_program_info.key != Pubkey::system_program() {
+                msg!("System program id does not match expected system program id");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            invoke_signed(
+                &create_account(
+                    payer_info.key,
+                    touch_info.key,
+                    minimum_rent(TokenMetadataTouch::LEN),
+                    TokenMetadataTouch::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    payer_info.clone(),
+                    touch_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    TOUCH_SEED, //
+                    metadata.mint.as_ref(),
+                    &[touch_bump],
+                ]],
+            )?;
+        }
+
+        let mut touch = match stored {
+            Some(touch) if touch.is_initialized() => touch,
+            _ => TokenMetadataTouch {
+                account_type: AccountType::Touch,
+                version: CURRENT_ACCOUNT_VERSION,
+                is_initialized: true,
+                mint: metadata.mint,
+                last_touched_slot: 0,
+                touch_count: 0,
+                bump: None,
+            },
+        };
+        touch.bump = Some(touch_bump);
+
+        touch.last_touched_slot = get_clock().slot;
+        touch.touch_count = touch
+            .touch_count
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        touch.pack_into_slice(&mut touch_info.data.borrow_mut());
+        write_metadata(metadata_info, &mut metadata)?;
+        MetadataEvent::Touched {
+            mint: touch.mint,
+            slot: touch.last_touched_slot,
+            touch_count: touch.touch_count,
+        }
+        .emit();
+        Ok(())
+    }
+
+    /// Ensure the signer is the stored update authority (metadata must not be immutable).
+    fn check_update_authority(
+        metadata: &TokenMetadata,
+        update_authority_info: &AccountInfo,
+    ) -> ProgramResult {
+        match metadata.update_authority {
+            Some(current_auth) => {
+                if !cmp_pubkeys(&current_auth, update_authority_info.key) {
+                    msg!("Update authority does not match");
+                    return Err(MetadataError::InvalidAuthority.into());
                 }
             }
             None => {
-                msg!("Metadata is immutable; cannot transfer authority");
+                msg!("Metadata is immutable");
                 return Err(MetadataError::InvalidAuthority.into());
             }
         }
-
-        metadata.update_authority = Some(new_authority);
-        metadata.pack_into_slice(&mut metadata_info.data.borrow_mut());
         Ok(())
     }
 
-    fn process_make_immutable(accounts: &[AccountInfo]) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let metadata_info = next_account_info(account_info_iter)?; // [writable]
-        let current_authority_info = next_account_info(account_info_iter)?; // [signer]
+    /// Ensure `authority_info` is the stored update authority and has signed, or is a multisig
+    /// authority with at least `m` of its members among `multisig_signer_infos`.
+    fn check_update_authority_signers(
+        program_id: &Pubkey,
+        metadata: &TokenMetadata,
+        authority_info: &AccountInfo,
+        multisig_signer_infos: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::check_update_authority(metadata, authority_info)?;
+        check_signed(program_id, authority_info, multisig_signer_infos)
+    }
 
-        if !current_authority_info.is_signer {
-            msg!("Current authority is not a signer");
+    /// Ensure the signer is the stored update authority (single key or multisig, see
+    /// `check_update_authority_signers`) or, when a delegate record is supplied as the only
+    /// remaining account, a delegate granted by the current update authority whose scope
+    /// covers `required`.
+    ///
+    /// A single-key signer takes either no remaining accounts or exactly one delegate record;
+    /// anything else is rejected rather than guessed at.
+    fn check_authority_or_delegate(
+        program_id: &Pubkey,
+        metadata: &TokenMetadata,
+        signer_info: &AccountInfo,
+        remaining: &[AccountInfo],
+        required: u16,
+    ) -> ProgramResult {
+        if is_multisig(program_id, signer_info) {
+            return Self::check_update_authority_signers(
+                program_id,
+                metadata,
+                signer_info,
+                remaining,
+            );
+        }
+        let delegate_record_info = match remaining {
+            [] => {
+                return Self::check_update_authority_signers(program_id, metadata, signer_info, &[])
+            }
+            [info] => info,
+            _ => {
+                msg!("Expected at most one delegate record after a single-key signer");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+        if delegate_record_info.owner != program_id
+            || delegate_record_info.data.borrow().first() != Some(&(AccountType::Delegate as u8))
+        {
+            msg!("Trailing account is not a delegate record");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !signer_info.is_signer {
+            msg!("Delegate is not a signer");
             return Err(ProgramError::MissingRequiredSignature);
         }
+        let Some(current_auth) = metadata.update_authority else {
+            msg!("Metadata is immutable");
+            return Err(MetadataError::InvalidAuthority.into());
+        };
+
+        let record = Self::load_delegate(program_id, delegate_record_info, &metadata.mint)?;
+        if !cmp_pubkeys(&record.delegate, signer_info.key) {
+            msg!("Signer is not the delegate of this record");
+            return Err(MetadataError::InvalidAuthority.into());
+        }
+        if !cmp_pubkeys(&record.authority, &current_auth) {
+            msg!("Delegate was granted by a previous update authority");
+            return Err(MetadataError::DelegateNotAuthorized.into());
+        }
+        if !record.covers(required) {
+            msg!(
+                "Delegate scope {:#06x} does not cover {:#06x}",
+                record.scope,
+                required
+            );
+            return Err(MetadataError::DelegateNotAuthorized.into());
+        }
+        Ok(())
+    }
+
+    fn check_unlocked(metadata: &TokenMetadata, mask: u16, field: &str) -> ProgramResult {
+        if metadata.is_locked(mask) {
+            msg!("Field is locked: {}", field);
+            return Err(MetadataError::FieldLocked.into());
+        }
+        Ok(())
+    }
+
+    /// Optimistic concurrency check: when the caller names an `expected_revision`, the
+    /// account must not have been written since the caller read it.
+    fn check_revision(revision: u64, expected_revision: Option<u64>) -> ProgramResult {
+        if let Some(expected) = expected_revision {
+            if expected != revision {
+                msg!("Stale revision: expected {}, found {}", expected, revision);
+                return Err(MetadataError::StaleRevision.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Load an initialized `TokenMetadata` account, enforcing that it is owned by this
+    /// program and lives at the canonical PDA for the mint it references.
+    fn load_metadata(
+        program_id: &Pubkey,
+        metadata_info: &AccountInfo,
+    ) -> Result<TokenMetadata, ProgramError> {
+        if metadata_info.owner != program_id {
+            msg!("Metadata account is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
 
         let mut metadata = TokenMetadata::unpack(&metadata_info.data.borrow())
             .map_err(|_| ProgramError::InvalidAccountData)?;
@@ -555,26 +2446,640 @@ impl Processor {
             return Err(ProgramError::UninitializedAccount);
         }
 
-        match metadata.update_authority {
-            Some(current_auth) => {
-                if !cmp_pubkeys(&current_auth, current_authority_info.key) {
-                    msg!("Signer is not current update authority");
-                    return Err(MetadataError::InvalidAuthority.into());
-                }
+        // The stored mint must derive back to this exact account. The bump was stored by this
+        // program at creation; older accounts search for it here and keep it on their next write.
+        let expected_md_pda = match metadata.bump {
+            Some(bump) => create_metadata_pda_with_bump(program_id, &metadata.mint, bump)?,
+            None => {
+                let (pda, bump) = find_metadata_pda_with_program(program_id, &metadata.mint);
+                metadata.bump = Some(bump);
+                pda
             }
+        };
+        if !cmp_pubkeys(&expected_md_pda, metadata_info.key) {
+            msg!("Metadata account does not match the PDA for its mint");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        Ok(metadata)
+    }
+
+    /// Load an initialized `TokenMetadataAttributes` account for `mint`, enforcing that it is
+    /// owned by this program, lives at the canonical attributes PDA, and references `mint`.
+    fn load_attributes(
+        program_id: &Pubkey,
+        attributes_info: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<TokenMetadataAttributes, ProgramError> {
+        if attributes_info.owner != program_id {
+            msg!("Attributes account is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        // Sized to its entries, so `unpack`'s exact-length check does not apply
+        let mut attrs = TokenMetadataAttributes::unpack_from_slice(&attributes_info.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if !attrs.is_initialized() {
+            msg!("Attributes not initialized");
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if !cmp_pubkeys(&attrs.mint, mint) {
+            msg!("Attributes mint does not match metadata mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        // Same stored-bump shortcut as `load_metadata`
+        let expected_attrs_pda = match attrs.bump {
+            Some(bump) => create_attributes_pda_with_bump(program_id, mint, bump)?,
             None => {
-                msg!("Metadata already immutable");
-                return Err(MetadataError::InvalidAuthority.into());
+                let (pda, bump) = find_attributes_pda_with_program(program_id, mint);
+                attrs.bump = Some(bump);
+                pda
             }
+        };
+        if !cmp_pubkeys(&expected_attrs_pda, attributes_info.key) {
+            msg!("Attributes PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
         }
 
-        metadata.update_authority = None;
-        metadata.pack_into_slice(&mut metadata_info.data.borrow_mut());
-        Ok(())
+        Ok(attrs)
+    }
+
+    /// Load an initialized delegate record for `mint`, enforcing that it is owned by this
+    /// program and lives at the canonical PDA for the delegate it names.
+    fn load_delegate(
+        program_id: &Pubkey,
+        delegate_record_info: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<TokenMetadataDelegate, ProgramError> {
+        if delegate_record_info.owner != program_id {
+            msg!("Delegate record is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        let record = TokenMetadataDelegate::unpack(&delegate_record_info.data.borrow())?;
+        if !cmp_pubkeys(&record.mint, mint) {
+            msg!("Delegate record mint does not match metadata mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        // Same stored-bump shortcut as `load_metadata`; records are only written on re-grant
+        let expected_delegate_pda = match record.bump {
+            Some(bump) => create_delegate_pda_with_bump(program_id, mint, &record.delegate, bump)?,
+            None => find_delegate_pda_with_program(program_id, mint, &record.delegate).0,
+        };
+        if !cmp_pubkeys(&expected_delegate_pda, delegate_record_info.key) {
+            msg!("Delegate PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        Ok(record)
+    }
+
+    /// Load an initialized creators account, enforcing that it is owned by this program and
+    /// lives at the canonical creators PDA for its stored mint.
+    fn load_creators(
+        program_id: &Pubkey,
+        creators_info: &AccountInfo,
+    ) -> Result<TokenMetadataCreators, ProgramError> {
+        if creators_info.owner != program_id {
+            msg!("Creators account is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        let mut record = TokenMetadataCreators::unpack(&creators_info.data.borrow())?;
+        // Same stored-bump shortcut as `load_metadata`
+        let expected_creators_pda = match record.bump {
+            Some(bump) => create_creators_pda_with_bump(program_id, &record.mint, bump)?,
+            None => {
+                let (pda, bump) = find_creators_pda_with_program(program_id, &record.mint);
+                record.bump = Some(bump);
+                pda
+            }
+        };
+        if !cmp_pubkeys(&expected_creators_pda, creators_info.key) {
+            msg!("Creators PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        Ok(record)
+    }
+
+    /// Load an initialized extension account for `mint`, enforcing that it is owned by this
+    /// program, references `mint`, and lives at the PDA for its stored type id and bump.
+    fn load_extension(
+        program_id: &Pubkey,
+        extension_info: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<TokenMetadataExtension, ProgramError> {
+        if extension_info.owner != program_id {
+            msg!("Extension account is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+
+        // Sized to its payload, so `unpack`'s exact-length check does not apply
+        let extension = TokenMetadataExtension::unpack_from_slice(&extension_info.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if !extension.is_initialized() {
+            msg!("Extension not initialized");
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if !cmp_pubkeys(&extension.mint, mint) {
+            msg!("Extension mint does not match metadata mint");
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        let expected_ext_pda = create_extension_pda_with_bump(
+            program_id,
+            mint,
+            extension.extension_type,
+            extension.bump,
+        )?;
+        if !cmp_pubkeys(&expected_ext_pda, extension_info.key) {
+            msg!("Extension PDA does not match expected PDA");
+            return Err(MetadataError::InvalidPda.into());
+        }
+
+        Ok(extension)
+    }
+
+    fn load_multisig(
+        program_id: &Pubkey,
+        multisig_info: &AccountInfo,
+    ) -> Result<TokenMetadataMultisig, ProgramError> {
+        if multisig_info.owner != program_id {
+            msg!("Multisig account is not owned by this program");
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+        TokenMetadataMultisig::unpack(&multisig_info.data.borrow())
+    }
+}
+
+/// Returns true if `info` is an initialized multisig account owned by this program.
+fn is_multisig(program_id: &Pubkey, info: &AccountInfo) -> bool {
+    info.owner == program_id && info.data.borrow().first() == Some(&(AccountType::Multisig as u8))
+}
+
+/// Require `info` to have signed or, if it is a multisig account, at least `m` of its members
+/// to have signed among `multisig_signer_infos`.
+///
+/// A multisig account never authorizes anything by its own signature: its key was only needed
+/// to create it.
+fn check_signed(
+    program_id: &Pubkey,
+    info: &AccountInfo,
+    multisig_signer_infos: &[AccountInfo],
+) -> ProgramResult {
+    if is_multisig(program_id, info) {
+        let multisig = TokenMetadataMultisig::unpack(&info.data.borrow())?;
+        return check_multisig_signers(&multisig, multisig_signer_infos);
+    }
+    if !info.is_signer {
+        msg!("Authority is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Require at least `m` distinct members of `multisig` to have signed among `signer_infos`.
+fn check_multisig_signers(
+    multisig: &TokenMetadataMultisig,
+    signer_infos: &[AccountInfo],
+) -> ProgramResult {
+    let mut matched = [false; MAX_MULTISIG_SIGNERS];
+    let mut num_signers: u8 = 0;
+    for signer_info in signer_infos {
+        for (position, member) in multisig.signers.iter().enumerate() {
+            if cmp_pubkeys(member, signer_info.key) && !matched[position] {
+                if !signer_info.is_signer {
+                    msg!("Multisig member is not a signer");
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                matched[position] = true;
+                num_signers += 1;
+            }
+        }
+    }
+    if num_signers < multisig.m {
+        msg!(
+            "Multisig requires {} signatures, got {}",
+            multisig.m,
+            num_signers
+        );
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Size of a freshly allocated account of the given type under the current layout.
+fn account_len(account_type: AccountType) -> usize {
+    match account_type {
+        AccountType::Metadata => TokenMetadata::LEN,
+        AccountType::Attributes => TokenMetadataAttributes::LEN,
+        AccountType::Touch => TokenMetadataTouch::LEN,
+        AccountType::Delegate => TokenMetadataDelegate::LEN,
+        AccountType::Multisig => TokenMetadataMultisig::LEN,
+        AccountType::Creators => TokenMetadataCreators::LEN,
+        AccountType::Extension => TokenMetadataExtension::LEN,
+        AccountType::Uninitialized => 0,
+    }
+}
+
+/// Record a write on `metadata` (revision and slot) and store it back to its account.
+///
+/// Accounts allocated for an older, shorter layout may not fit the current one; those must be
+/// grown with `MigrateAccount` first. A failed write aborts the instruction, so the partial
+/// bytes are never committed.
+fn write_metadata(metadata_info: &AccountInfo, metadata: &mut TokenMetadata) -> ProgramResult {
+    metadata.record_write(get_clock().slot);
+    metadata
+        .try_pack_into_slice(&mut metadata_info.data.borrow_mut())
+        .map_err(|_| {
+            msg!("Metadata account is too small for the current layout; run MigrateAccount");
+            MetadataError::AccountNeedsMigration.into()
+        })
+}
+
+/// Record a write on `attrs` (revision and slot), resize the account to fit and store it.
+fn write_attributes<'a>(
+    attributes_info: &AccountInfo<'a>,
+    funding: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    refund_info: Option<&AccountInfo<'a>>,
+    attrs: &mut TokenMetadataAttributes,
+) -> ProgramResult {
+    attrs.record_write(get_clock().slot);
+    resize_account(
+        attributes_info,
+        funding,
+        refund_info,
+        attributes_account_len(&attrs.data),
+    )?;
+    attrs.try_pack_into_slice(&mut attributes_info.data.borrow_mut())
+}
+
+/// Record a write on `extension` (revision and slot), resize the account to fit and store it.
+fn write_extension<'a>(
+    extension_info: &AccountInfo<'a>,
+    funding: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    refund_info: Option<&AccountInfo<'a>>,
+    extension: &mut TokenMetadataExtension,
+) -> ProgramResult {
+    extension.record_write(get_clock().slot);
+    resize_account(
+        extension_info,
+        funding,
+        refund_info,
+        extension_account_len(extension.data.len()),
+    )?;
+    extension.try_pack_into_slice(&mut extension_info.data.borrow_mut())
+}
+
+/// Split the optional `[payer, system_program]` accounts that fund account growth off the
+/// front of `remaining`; they are recognized by the system program in second position.
+fn split_funding<'a, 'b>(
+    remaining: &'b [AccountInfo<'a>],
+) -> (
+    Option<(&'b AccountInfo<'a>, &'b AccountInfo<'a>)>,
+    &'b [AccountInfo<'a>],
+) {
+    match remaining {
+        [payer_info, system_program_info, rest @ ..]
+            if *system_program_info.key == Pubkey::system_program() =>
+        {
+            (Some((payer_info, system_program_info)), rest)
+        }
+        _ => (None, remaining),
+    }
+}
+
+/// The account that receives rent freed by shrinking: the payer, when one was passed writable
+/// and signing, and otherwise the signer itself if it is the stored single-key update
+/// authority and was passed writable. With neither, the rent stays in the account until the
+/// update authority closes it.
+fn rent_refund_recipient<'a, 'b>(
+    program_id: &Pubkey,
+    metadata: &TokenMetadata,
+    authority_info: &'b AccountInfo<'a>,
+    funding: Option<(&'b AccountInfo<'a>, &'b AccountInfo<'a>)>,
+) -> Option<&'b AccountInfo<'a>> {
+    if let Some((payer_info, _)) = funding {
+        if payer_info.is_signer && payer_info.is_writable {
+            return Some(payer_info);
+        }
+    }
+    let is_authority = metadata
+        .update_authority
+        .is_some_and(|auth| cmp_pubkeys(&auth, authority_info.key));
+    (is_authority && authority_info.is_writable && !is_multisig(program_id, authority_info))
+        .then_some(authority_info)
+}
+
+/// Resize a program-owned account to exactly `new_len` bytes.
+///
+/// `funding` (payer, system program) pays for any rent increase and is only required when the
+/// account grows. Rent freed by shrinking goes to `refund_info` if given, and otherwise stays
+/// in the account.
+fn resize_account<'a>(
+    account_info: &AccountInfo<'a>,
+    funding: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    refund_info: Option<&AccountInfo<'a>>,
+    new_len: usize,
+) -> ProgramResult {
+    let current_len = account_info.data.borrow().len();
+    if new_len == current_len {
+        return Ok(());
+    }
+    if new_len.saturating_sub(current_len) > MAX_PERMITTED_DATA_INCREASE {
+        msg!(
+            "Account growth exceeds the per-instruction limit: {} -> {}",
+            current_len,
+            new_len
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let required_lamports = minimum_rent(new_len);
+    let current_lamports = account_info.lamports();
+    match required_lamports.cmp(&current_lamports) {
+        Ordering::Greater => {
+            let Some((payer_info, system_program_info)) = funding else {
+                msg!("Account must grow; pass the payer and system program");
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            if cmp_pubkeys(account_info.key, payer_info.key) {
+                msg!("Payer must differ from the account being resized");
+                return Err(ProgramError::InvalidArgument);
+            }
+            if *system_program_info.key != Pubkey::system_program() {
+                msg!("System program id does not match expected system program id");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            if !payer_info.is_signer {
+                msg!("Payer is not a signer");
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            invoke(
+                &transfer(
+                    payer_info.key,
+                    account_info.key,
+                    required_lamports - current_lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    account_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+        Ordering::Less => {
+            if let Some(refund_info) = refund_info {
+                if cmp_pubkeys(account_info.key, refund_info.key) {
+                    msg!("Refund recipient must differ from the account being resized");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                let refund = current_lamports - required_lamports;
+                let refund_lamports = refund_info
+                    .lamports()
+                    .checked_add(refund)
+                    .ok_or(ProgramError::InvalidArgument)?;
+                **refund_info.lamports.borrow_mut() = refund_lamports;
+                **account_info.lamports.borrow_mut() = required_lamports;
+            }
+        }
+        Ordering::Equal => {}
+    }
+
+    account_info.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Validate attribute entries against the caps in `state` and sort them into canonical
+/// (by key, duplicate-free) order.
+fn validate_attributes(data: &mut [(String, String)]) -> ProgramResult {
+    if data.len() > MAX_ATTRIBUTES {
+        msg!("Too many attributes: {} > {}", data.len(), MAX_ATTRIBUTES);
+        return Err(MetadataError::TooManyAttributes.into());
+    }
+    for (k, v) in data.iter() {
+        if k.is_empty() || v.is_empty() {
+            msg!("Attribute key and value must be non-empty");
+            return Err(MetadataError::InvalidInstructionData.into());
+        }
+        if k.len() > MAX_KEY_LENGTH || v.len() > MAX_VALUE_LENGTH {
+            msg!(
+                "Attribute key or value is too long: key={}/{}, value={}/{}",
+                k.len(),
+                MAX_KEY_LENGTH,
+                v.len(),
+                MAX_VALUE_LENGTH,
+            );
+            return Err(MetadataError::StringTooLong.into());
+        }
+    }
+    if let Err(err) = sort_attributes(data) {
+        msg!("Attribute keys must be unique");
+        return Err(err.into());
     }
+    Ok(())
+}
+
+/// Move all lamports of a program-owned account to `destination_info` and hand the emptied
+/// account back to the system program.
+///
+/// The account ends up exactly like one that never existed (system-owned, no data, no lamports),
+/// so re-creating it goes through `create_account` and is funded to rent exemption again.
+fn close_program_account(
+    account_info: &AccountInfo,
+    destination_info: &AccountInfo,
+) -> ProgramResult {
+    if cmp_pubkeys(account_info.key, destination_info.key) {
+        msg!("Destination must differ from the account being closed");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let lamports = account_info.lamports();
+    let destination_lamports = destination_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **destination_info.lamports.borrow_mut() = destination_lamports;
+    **account_info.lamports.borrow_mut() = 0;
+
+    account_info.data.borrow_mut().fill(0);
+    account_info.realloc(0, false)?;
+    account_info.assign(&Pubkey::system_program());
+    Ok(())
 }
 
 /// Checks two pubkeys for equality using a cheap memcmp
 fn cmp_pubkeys(a: &Pubkey, b: &Pubkey) -> bool {
     arch_program::program_memory::sol_memcmp(a.as_ref(), b.as_ref(), 32) == 0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_program::utxo::UtxoMeta;
+
+    fn test_metadata(mint: Pubkey, authority: Pubkey, bump: u8) -> TokenMetadata {
+        TokenMetadata {
+            account_type: AccountType::Metadata,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint,
+            name: "Name".to_string(),
+            symbol: "SYM".to_string(),
+            image: "https://i".to_string(),
+            description: "d".to_string(),
+            update_authority: Some(authority),
+            pending_authority: None,
+            locked_fields: LOCK_NAME,
+            collection: None,
+            uri: String::new(),
+            content_hash: [0u8; 32],
+            content_hash_algorithm: ContentHashAlgorithm::None,
+            revision: 4,
+            last_updated_slot: 0,
+            bump: Some(bump),
+            delegate_count: 0,
+            extension_count: 0,
+        }
+    }
+
+    #[test]
+    fn get_metadata_returns_a_decodable_summary() {
+        let program_id = Pubkey::from_slice(&[1u8; 32]);
+        let mint = Pubkey::from_slice(&[2u8; 32]);
+        let authority = Pubkey::from_slice(&[3u8; 32]);
+        let (metadata_key, bump) = find_metadata_pda_with_program(&program_id, &mint);
+
+        let metadata = test_metadata(mint, authority, bump);
+        let mut metadata_data = vec![0u8; TokenMetadata::LEN];
+        metadata.pack_into_slice(&mut metadata_data);
+        let mut mint_data = vec![0u8; Mint::LEN];
+        let (mut metadata_lamports, mut mint_lamports) = (0u64, 0u64);
+        let token_program = apl_token::id();
+        let utxo = UtxoMeta::default();
+        let accounts = [
+            AccountInfo::new(
+                &metadata_key,
+                &mut metadata_lamports,
+                &mut metadata_data,
+                &program_id,
+                &utxo,
+                false,
+                false,
+                false,
+            ),
+            AccountInfo::new(
+                &mint,
+                &mut mint_lamports,
+                &mut mint_data,
+                &token_program,
+                &utxo,
+                false,
+                false,
+                false,
+            ),
+        ];
+
+        let returned = Processor::get_metadata_return_data(&program_id, &accounts).unwrap();
+        let summary = TokenMetadataSummary::decode(&returned).unwrap();
+        assert_eq!(summary, TokenMetadataSummary::from(&metadata));
+        assert_eq!(summary.mint, mint);
+        assert_eq!(summary.name, "Name");
+        assert_eq!(summary.symbol, "SYM");
+        assert_eq!(summary.update_authority, Some(authority));
+        assert_eq!(summary.locked_fields, LOCK_NAME);
+        assert_eq!(summary.revision, 4);
+
+        // Metadata owned by another program is rejected before anything is returned
+        let other_program = Pubkey::from_slice(&[4u8; 32]);
+        assert_eq!(
+            Processor::get_metadata_return_data(&other_program, &accounts).unwrap_err(),
+            MetadataError::IncorrectOwner.into()
+        );
+    }
+
+    fn refund_key<'a>(
+        program_id: &Pubkey,
+        metadata: &TokenMetadata,
+        authority_info: &AccountInfo<'a>,
+        funding: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    ) -> Option<Pubkey> {
+        rent_refund_recipient(program_id, metadata, authority_info, funding).map(|info| *info.key)
+    }
+
+    #[test]
+    fn rent_refund_goes_to_the_payer_before_the_authority() {
+        let program_id = Pubkey::from_slice(&[1u8; 32]);
+        let authority = Pubkey::from_slice(&[3u8; 32]);
+        let payer = Pubkey::from_slice(&[5u8; 32]);
+        let system_program = Pubkey::system_program();
+        let metadata = test_metadata(Pubkey::from_slice(&[2u8; 32]), authority, 255);
+
+        let utxo = UtxoMeta::default();
+        let [mut authority_lamports, mut payer_lamports, mut system_lamports] = [0u64; 3];
+        let [mut authority_data, mut payer_data, mut system_data] = [[0u8; 0]; 3];
+        let authority_info = AccountInfo::new(
+            &authority,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program,
+            &utxo,
+            true,
+            true,
+            false,
+        );
+        let payer_info = AccountInfo::new(
+            &payer,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program,
+            &utxo,
+            true,
+            true,
+            false,
+        );
+        let system_info = AccountInfo::new(
+            &system_program,
+            &mut system_lamports,
+            &mut system_data,
+            &system_program,
+            &utxo,
+            false,
+            false,
+            false,
+        );
+        let recipient = |authority_info, payer_info: Option<_>| {
+            refund_key(
+                &program_id,
+                &metadata,
+                authority_info,
+                payer_info.map(|payer_info| (payer_info, &system_info)),
+            )
+        };
+
+        assert_eq!(recipient(&authority_info, Some(&payer_info)), Some(payer));
+        assert_eq!(recipient(&authority_info, None), Some(authority));
+
+        // A payer that did not sign is never credited
+        let mut unsigned_payer_info = payer_info.clone();
+        unsigned_payer_info.is_signer = false;
+        assert_eq!(
+            recipient(&authority_info, Some(&unsigned_payer_info)),
+            Some(authority)
+        );
+
+        // Without a payer, a readonly authority leaves the rent in the account
+        let mut readonly_authority_info = authority_info.clone();
+        readonly_authority_info.is_writable = false;
+        assert_eq!(recipient(&readonly_authority_info, None), None);
+        assert_eq!(
+            recipient(&readonly_authority_info, Some(&payer_info)),
+            Some(payer)
+        );
+    }
+}
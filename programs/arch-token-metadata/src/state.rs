@@ -1,6 +1,20 @@
 //! State types and serialization sizes
+//!
+//! # Account versions
+//!
+//! Every account starts with a header of `[account_type, version]`. Version 0 is the legacy
+//! headerless layout, which `MigrateAccount` rewrites. Version 1 (`CURRENT_ACCOUNT_VERSION`)
+//! marks a zero-extendable layout: new fields are only ever appended, and an account written
+//! before a field existed is simply shorter. Readers treat the missing tail as zeroes, which
+//! decodes to each field's default, so the account's size rather than its version tells which
+//! fields it holds. Metadata writes need the full layout and report `AccountNeedsMigration`
+//! until `MigrateAccount` grows the account.
+//!
+//! The version is bumped only for a change that appending cannot express (reordering,
+//! removing or retyping a field); such a change needs its own migration.
 
 use {
+    crate::error::MetadataError,
     arch_program::{
         program_error::ProgramError,
         program_pack::{IsInitialized, Pack, Sealed},
@@ -21,6 +35,13 @@ pub const IMAGE_MAX_LEN: usize = 512;
 /// Maximum length for description
 pub const DESCRIPTION_MAX_LEN: usize = 512;
 
+/// Maximum length for the off-chain JSON URI
+pub const URI_MAX_LEN: usize = 200;
+
+/// URI scheme prefixes accepted for `image` and the off-chain JSON URI (matched
+/// case-insensitively)
+pub const ALLOWED_URI_SCHEMES: [&str; 4] = ["https://", "ipfs://", "ar://", "data:"];
+
 /// Maximum length for attribute key
 pub const MAX_KEY_LENGTH: usize = 64;
 
@@ -30,28 +51,203 @@ pub const MAX_VALUE_LENGTH: usize = 240;
 /// Maximum number of attributes
 pub const MAX_ATTRIBUTES: usize = 32;
 
+/// Maximum number of members in a multisig authority
+pub const MAX_MULTISIG_SIGNERS: usize = 11;
+
+/// Maximum number of creators
+pub const MAX_CREATORS: usize = 5;
+
+/// Upper bound for the sum of creator shares (percent)
+pub const MAX_CREATOR_SHARE_TOTAL: u8 = 100;
+
+/// Maximum length of an extension payload
+pub const MAX_EXTENSION_DATA_LEN: usize = 1024;
+
+/// Current layout version written into every program account header. Appending fields keeps
+/// this version; see the module docs.
+pub const CURRENT_ACCOUNT_VERSION: u8 = 1;
+
+/// Size (in bytes) of the account header: account type + layout version
+pub const ACCOUNT_HEADER_LEN: usize = 2;
+
+/// First byte of an initialized legacy (v0) account, which has no header and starts
+/// directly with `is_initialized == true`
+pub const LEGACY_V0_INITIALIZED: u8 = 1;
+
+/// `TokenMetadata::locked_fields` bit: `name` can no longer be updated
+pub const LOCK_NAME: u16 = 1 << 0;
+
+/// `TokenMetadata::locked_fields` bit: `symbol` can no longer be updated
+pub const LOCK_SYMBOL: u16 = 1 << 1;
+
+/// `TokenMetadata::locked_fields` bit: `image` can no longer be updated
+pub const LOCK_IMAGE: u16 = 1 << 2;
+
+/// `TokenMetadata::locked_fields` bit: `description` can no longer be updated
+pub const LOCK_DESCRIPTION: u16 = 1 << 3;
+
+/// `TokenMetadata::locked_fields` bit: the attributes account can no longer be created,
+/// modified or closed
+pub const LOCK_ATTRIBUTES: u16 = 1 << 4;
+
+/// `TokenMetadata::locked_fields` bit: `uri` and its content hash can no longer be updated
+pub const LOCK_URI: u16 = 1 << 5;
+
+/// All currently defined lock bits
+pub const LOCK_ALL: u16 =
+    LOCK_NAME | LOCK_SYMBOL | LOCK_IMAGE | LOCK_DESCRIPTION | LOCK_ATTRIBUTES | LOCK_URI;
+
+/// Discriminator stored in the first byte of every program account.
+///
+/// `1` is deliberately unused so headered accounts never collide with legacy v0 accounts.
+#[repr(u8)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[borsh(use_discriminant = true)]
+pub enum AccountType {
+    /// Zero-initialized account not yet written by the program
+    Uninitialized = 0,
+    /// `TokenMetadata` account
+    Metadata = 2,
+    /// `TokenMetadataAttributes` account
+    Attributes = 3,
+    /// `TokenMetadataTouch` account
+    Touch = 4,
+    /// `TokenMetadataDelegate` account
+    Delegate = 5,
+    /// `TokenMetadataMultisig` account
+    Multisig = 6,
+    /// `TokenMetadataCreators` account
+    Creators = 7,
+    /// `TokenMetadataExtension` account
+    Extension = 8,
+}
+
 /// Calculate the maximum serialized length (in bytes) for the TokenMetadata account using Borsh
 /// String layout: 4-byte LE length prefix + bytes
 /// Option<Pubkey> worst-case: 1-byte tag + 32 bytes
-pub const TOKEN_METADATA_MAX_LEN: usize = 1 + // is_initialized (bool)
+pub const TOKEN_METADATA_MAX_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
     32 + // mint
     (4 + NAME_MAX_LEN) +
     (4 + SYMBOL_MAX_LEN) +
     (4 + IMAGE_MAX_LEN) +
     (4 + DESCRIPTION_MAX_LEN) +
-    (1 + 32); // update_authority = Some(Pubkey)
+    (1 + 32) + // update_authority = Some(Pubkey)
+    (1 + 32) + // pending_authority = Some(Pubkey)
+    2 + // locked_fields (u16)
+    (1 + 32 + 1) + // collection = Some(Collection)
+    (4 + URI_MAX_LEN) +
+    32 + // content_hash
+    1 + // content_hash_algorithm
+    8 + // revision
+    8 + // last_updated_slot
+    (1 + 1) + // bump = Some(u8)
+    4 + // delegate_count
+    2; // extension_count
 
 /// Calculate the maximum serialized length (in bytes) for the TokenMetadataAttributes account
 /// Vec layout: 4-byte LE length + elements; each element is a tuple of two Strings
 /// Tuple(String, String) layout: (4+key_len) + (4+value_len)
-pub const TOKEN_METADATA_ATTRIBUTES_MAX_LEN: usize = 1 + // is_initialized (bool)
+pub const TOKEN_METADATA_ATTRIBUTES_MAX_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
+    32 + // mint
+    4 + // vec length prefix
+    (MAX_ATTRIBUTES * ((4 + MAX_KEY_LENGTH) + (4 + MAX_VALUE_LENGTH))) +
+    8 + // revision
+    8 + // last_updated_slot
+    (1 + 1); // bump = Some(u8)
+
+/// Runtime cap on how many bytes an account may grow by within a single instruction
+pub const MAX_PERMITTED_DATA_INCREASE: usize = 10 * 1024;
+
+// A full attributes account can be reached from empty in one resize
+const _: () = assert!(TOKEN_METADATA_ATTRIBUTES_MAX_LEN <= MAX_PERMITTED_DATA_INCREASE);
+
+/// Serialized length (in bytes) of a TokenMetadataAttributes account holding exactly `data`.
+///
+/// Attributes accounts are sized to their contents and resized on every write.
+pub fn attributes_account_len(data: &[(String, String)]) -> usize {
+    ACCOUNT_HEADER_LEN +
+        1 + // is_initialized (bool)
+        32 + // mint
+        4 + // vec length prefix
+        data.iter().map(|(k, v)| (4 + k.len()) + (4 + v.len())).sum::<usize>() +
+        8 + // revision
+        8 + // last_updated_slot
+        (1 + 1) // bump = Some(u8)
+}
+
+/// Serialized length (in bytes) of the TokenMetadataTouch account (fixed size)
+pub const TOKEN_METADATA_TOUCH_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
+    32 + // mint
+    8 + // last_touched_slot
+    8 + // touch_count
+    (1 + 1); // bump = Some(u8)
+
+/// Serialized length (in bytes) of the TokenMetadataDelegate account (fixed size)
+pub const TOKEN_METADATA_DELEGATE_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
+    32 + // mint
+    32 + // delegate
+    32 + // authority
+    2 + // scope (u16)
+    (1 + 1); // bump = Some(u8)
+
+/// Calculate the maximum serialized length (in bytes) for the TokenMetadataMultisig account
+/// Vec<Pubkey> layout: 4-byte LE length + 32 bytes per member
+pub const TOKEN_METADATA_MULTISIG_MAX_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
+    1 + // m
+    4 + // vec length prefix
+    (MAX_MULTISIG_SIGNERS * 32);
+
+/// Serialized length (in bytes) of a single `Creator` entry
+pub const CREATOR_LEN: usize = 32 + // address
+    1 + // verified (bool)
+    1; // share (u8)
+
+/// Calculate the maximum serialized length (in bytes) for the TokenMetadataCreators account
+/// Vec<Creator> layout: 4-byte LE length + `CREATOR_LEN` bytes per creator
+pub const TOKEN_METADATA_CREATORS_MAX_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
     32 + // mint
     4 + // vec length prefix
-    (MAX_ATTRIBUTES * ((4 + MAX_KEY_LENGTH) + (4 + MAX_VALUE_LENGTH)));
+    (MAX_CREATORS * CREATOR_LEN) +
+    (1 + 1); // bump = Some(u8)
+
+/// Serialized length (in bytes) of a TokenMetadataExtension account holding `data_len` payload
+/// bytes. Extension accounts are sized to their payload and resized on every write.
+pub fn extension_account_len(data_len: usize) -> usize {
+    ACCOUNT_HEADER_LEN +
+        1 + // is_initialized (bool)
+        32 + // mint
+        2 + // extension_type (TLV type)
+        4 + // data length prefix (TLV length)
+        data_len +
+        8 + // revision
+        8 + // last_updated_slot
+        1 // bump
+}
+
+/// Maximum serialized length (in bytes) of a TokenMetadataExtension account
+pub const TOKEN_METADATA_EXTENSION_MAX_LEN: usize = ACCOUNT_HEADER_LEN +
+    1 + // is_initialized (bool)
+    32 + // mint
+    2 + // extension_type (TLV type)
+    4 + // data length prefix (TLV length)
+    MAX_EXTENSION_DATA_LEN +
+    8 + // revision
+    8 + // last_updated_slot
+    1; // bump
 
 /// Core metadata account - always present, optimized for performance
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct TokenMetadata {
+    /// Account type discriminator (`AccountType::Metadata` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
     /// Initialization flag
     pub is_initialized: bool,
     /// The mint address this metadata belongs to
@@ -66,6 +262,174 @@ pub struct TokenMetadata {
     pub description: String,
     /// Optional update authority for the metadata
     pub update_authority: Option<Pubkey>,
+    /// Authority proposed via `ProposeAuthority`, awaiting `AcceptAuthority`
+    pub pending_authority: Option<Pubkey>,
+    /// Bitmask of `LOCK_*` flags; once set, a bit can never be cleared
+    pub locked_fields: u16,
+    /// Collection this mint claims to belong to, set via `SetCollection`
+    pub collection: Option<Collection>,
+    /// URI of the off-chain JSON document; empty if none
+    pub uri: String,
+    /// Digest of the document at `uri`, computed with `content_hash_algorithm`
+    pub content_hash: [u8; 32],
+    /// Algorithm used for `content_hash`; `None` means the document is not committed to
+    pub content_hash_algorithm: ContentHashAlgorithm,
+    /// Number of writes, counting creation; checked against `expected_revision`. Accounts
+    /// created before this field existed read as 0 until their next write.
+    pub revision: u64,
+    /// Slot of the most recent write
+    pub last_updated_slot: u64,
+    /// Canonical bump of this account's PDA, stored at creation so validation can skip the
+    /// bump search. `None` on older accounts until their next write fills it in.
+    pub bump: Option<u8>,
+    /// Number of open delegate records for this mint. `CloseMetadata` requires it to be zero,
+    /// since the records cannot be enumerated from here.
+    pub delegate_count: u32,
+    /// Number of open extension accounts for this mint; `CloseMetadata` requires it to be zero
+    pub extension_count: u16,
+}
+
+/// Reference from a member's metadata to its collection
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Collection {
+    /// Mint of the collection; the collection's own metadata lives in this program
+    pub key: Pubkey,
+    /// True once the collection's update authority has signed `VerifyCollectionMember`
+    pub verified: bool,
+}
+
+/// Hash algorithm used for the off-chain JSON commitment
+#[repr(u8)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[borsh(use_discriminant = true)]
+pub enum ContentHashAlgorithm {
+    /// No commitment; `content_hash` must be all zeros
+    #[default]
+    None = 0,
+    /// SHA-256 over the raw document bytes
+    Sha256 = 1,
+}
+
+/// Off-chain JSON URI together with a commitment to its contents, as set by `CreateMetadata`
+/// and `UpdateMetadata`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ContentUri {
+    /// URI of the JSON document; empty clears it
+    pub uri: String,
+    /// Digest of the document bytes
+    pub content_hash: [u8; 32],
+    /// Algorithm used for `content_hash`
+    pub hash_algorithm: ContentHashAlgorithm,
+}
+
+impl ContentUri {
+    /// Checks the length limit, the URI policy (see `validate_uri`) and that the hash is
+    /// consistent with the algorithm: no algorithm means an all-zero hash, and an empty URI
+    /// cannot carry a commitment
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        if self.uri.len() > URI_MAX_LEN {
+            return Err(MetadataError::StringTooLong);
+        }
+        validate_uri(&self.uri)?;
+        let has_hash = self.content_hash != [0u8; 32];
+        match self.hash_algorithm {
+            ContentHashAlgorithm::None if has_hash => Err(MetadataError::InvalidInstructionData),
+            ContentHashAlgorithm::Sha256 if self.uri.is_empty() || !has_hash => {
+                Err(MetadataError::InvalidInstructionData)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TokenMetadata {
+    /// Stores `content` as this metadata's URI and commitment
+    pub fn set_content_uri(&mut self, content: ContentUri) {
+        self.uri = content.uri;
+        self.content_hash = content.content_hash;
+        self.content_hash_algorithm = content.hash_algorithm;
+    }
+
+    /// Returns true if any of the `LOCK_*` bits in `mask` are set
+    pub fn is_locked(&self, mask: u16) -> bool {
+        self.locked_fields & mask != 0
+    }
+
+    /// Advance `revision` and stamp `slot` as the slot of the last write
+    pub fn record_write(&mut self, slot: u64) {
+        self.revision = self.revision.saturating_add(1);
+        self.last_updated_slot = slot;
+    }
+
+    /// Serialize into `dst` without allocating, zeroing the bytes after the encoding.
+    /// Fails with `AccountTooSmall` if `dst` cannot hold it.
+    pub fn try_pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_zero_padded(self, dst)
+    }
+}
+
+/// Maximum borsh size of a `TokenMetadataSummary`; stays under the 1024-byte return data limit
+pub const TOKEN_METADATA_SUMMARY_MAX_LEN: usize = 32 + // mint
+    (4 + NAME_MAX_LEN) +
+    (4 + SYMBOL_MAX_LEN) +
+    (1 + 32) + // update_authority = Some(Pubkey)
+    2 + // locked_fields (u16)
+    (1 + 32 + 1) + // collection = Some(Collection)
+    (4 + URI_MAX_LEN) +
+    32 + // content_hash
+    1 + // content_hash_algorithm
+    8; // revision
+
+/// Compact view of `TokenMetadata` returned by `GetMetadata` for CPI consumers.
+///
+/// `image` and `description` are left out so the summary fits in return data. Fields are
+/// only ever appended; `decode` ignores trailing bytes so older callers keep working.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadataSummary {
+    /// The mint address this metadata belongs to
+    pub mint: Pubkey,
+    /// The name of the token
+    pub name: String,
+    /// The symbol of the token
+    pub symbol: String,
+    /// Update authority; `None` once immutable
+    pub update_authority: Option<Pubkey>,
+    /// Bitmask of `LOCK_*` flags
+    pub locked_fields: u16,
+    /// Collection reference, if any
+    pub collection: Option<Collection>,
+    /// URI of the off-chain JSON document; empty if none
+    pub uri: String,
+    /// Digest of the document at `uri`
+    pub content_hash: [u8; 32],
+    /// Algorithm used for `content_hash`
+    pub content_hash_algorithm: ContentHashAlgorithm,
+    /// Revision of the metadata account, for callers that pass `expected_revision` later
+    pub revision: u64,
+}
+
+impl TokenMetadataSummary {
+    /// Decode a summary from return data, ignoring fields appended by newer program versions
+    pub fn decode(mut data: &[u8]) -> Result<Self, ProgramError> {
+        Self::deserialize(&mut data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+impl From<&TokenMetadata> for TokenMetadataSummary {
+    fn from(metadata: &TokenMetadata) -> Self {
+        Self {
+            mint: metadata.mint,
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            update_authority: metadata.update_authority,
+            locked_fields: metadata.locked_fields,
+            collection: metadata.collection,
+            uri: metadata.uri.clone(),
+            content_hash: metadata.content_hash,
+            content_hash_algorithm: metadata.content_hash_algorithm,
+            revision: metadata.revision,
+        }
+    }
 }
 
 impl Sealed for TokenMetadata {}
@@ -79,33 +443,678 @@ impl Pack for TokenMetadata {
     const LEN: usize = TOKEN_METADATA_MAX_LEN;
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(src, AccountType::Metadata)?;
         // Use streaming deserialization so trailing zero padding is ignored
-        let mut slice_ref: &[u8] = src;
-        BorshDeserialize::deserialize(&mut slice_ref).map_err(|_| ProgramError::InvalidAccountData)
+        deserialize_zero_extended(src, Self::LEN)
     }
 
+    /// Panics if `dst` is too small; on-chain writes use `try_pack_into_slice`.
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let data = borsh::to_vec(self).unwrap();
-        // Copy serialized data into destination; assume destination has been sized to LEN
-        dst[..data.len()].copy_from_slice(&data);
-        // Zero the remainder for determinism
-        if data.len() < dst.len() {
-            for b in &mut dst[data.len()..] {
-                *b = 0;
-            }
+        self.try_pack_into_slice(dst)
+            .expect("destination too small for TokenMetadata");
+    }
+}
+
+/// Borrowed, allocation-free view of a `TokenMetadata` account.
+///
+/// `new` walks the account once, checking the header, every length prefix, option tag and
+/// UTF-8 string up front, so the accessors cannot fail. Strings borrow from the account data;
+/// everything else is `Copy`. Like `unpack`, fields past the end of an older, shorter account
+/// read as their defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenMetadataRef<'a> {
+    mint: Pubkey,
+    name: &'a str,
+    symbol: &'a str,
+    image: &'a str,
+    description: &'a str,
+    update_authority: Option<Pubkey>,
+    pending_authority: Option<Pubkey>,
+    locked_fields: u16,
+    collection: Option<Collection>,
+    uri: &'a str,
+    content_hash: [u8; 32],
+    content_hash_algorithm: ContentHashAlgorithm,
+    revision: u64,
+    last_updated_slot: u64,
+    bump: Option<u8>,
+    delegate_count: u32,
+    extension_count: u16,
+}
+
+impl<'a> TokenMetadataRef<'a> {
+    /// Validate `data` as an initialized metadata account and borrow its fields
+    pub fn new(data: &'a [u8]) -> Result<Self, ProgramError> {
+        check_account_header(data, AccountType::Metadata)?;
+        let mut reader = ZeroExtendedReader {
+            data,
+            pos: ACCOUNT_HEADER_LEN,
+        };
+        if !reader.bool()? {
+            return Err(ProgramError::UninitializedAccount);
         }
+        Ok(Self {
+            mint: reader.pubkey(),
+            name: reader.str(NAME_MAX_LEN)?,
+            symbol: reader.str(SYMBOL_MAX_LEN)?,
+            image: reader.str(IMAGE_MAX_LEN)?,
+            description: reader.str(DESCRIPTION_MAX_LEN)?,
+            update_authority: reader.option_pubkey()?,
+            pending_authority: reader.option_pubkey()?,
+            locked_fields: u16::from_le_bytes(reader.array()),
+            collection: match reader.option_tag()? {
+                false => None,
+                true => Some(Collection {
+                    key: reader.pubkey(),
+                    verified: reader.bool()?,
+                }),
+            },
+            uri: reader.str(URI_MAX_LEN)?,
+            content_hash: reader.array(),
+            content_hash_algorithm: match reader.u8() {
+                0 => ContentHashAlgorithm::None,
+                1 => ContentHashAlgorithm::Sha256,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+            revision: u64::from_le_bytes(reader.array()),
+            last_updated_slot: u64::from_le_bytes(reader.array()),
+            bump: match reader.option_tag()? {
+                false => None,
+                true => Some(reader.u8()),
+            },
+            delegate_count: u32::from_le_bytes(reader.array()),
+            extension_count: u16::from_le_bytes(reader.array()),
+        })
+    }
+
+    /// The mint address this metadata belongs to
+    pub fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    /// The name of the token
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The symbol of the token
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+
+    /// The image URI for the token
+    pub fn image(&self) -> &'a str {
+        self.image
+    }
+
+    /// The description of the token
+    pub fn description(&self) -> &'a str {
+        self.description
+    }
+
+    /// Update authority; `None` once immutable
+    pub fn update_authority(&self) -> Option<Pubkey> {
+        self.update_authority
+    }
+
+    /// Authority proposed via `ProposeAuthority`, awaiting `AcceptAuthority`
+    pub fn pending_authority(&self) -> Option<Pubkey> {
+        self.pending_authority
+    }
+
+    /// Bitmask of `LOCK_*` flags
+    pub fn locked_fields(&self) -> u16 {
+        self.locked_fields
+    }
+
+    /// Returns true if any of the `LOCK_*` bits in `mask` are set
+    pub fn is_locked(&self, mask: u16) -> bool {
+        self.locked_fields & mask != 0
+    }
+
+    /// Collection this mint claims to belong to
+    pub fn collection(&self) -> Option<Collection> {
+        self.collection
+    }
+
+    /// URI of the off-chain JSON document; empty if none
+    pub fn uri(&self) -> &'a str {
+        self.uri
+    }
+
+    /// Digest of the document at `uri`
+    pub fn content_hash(&self) -> [u8; 32] {
+        self.content_hash
+    }
+
+    /// Algorithm used for `content_hash`
+    pub fn content_hash_algorithm(&self) -> ContentHashAlgorithm {
+        self.content_hash_algorithm
+    }
+
+    /// Number of writes, counting creation
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Slot of the most recent write
+    pub fn last_updated_slot(&self) -> u64 {
+        self.last_updated_slot
+    }
+
+    /// Canonical bump of the metadata PDA, if stored
+    pub fn bump(&self) -> Option<u8> {
+        self.bump
+    }
+
+    /// Number of open delegate records for this mint
+    pub fn delegate_count(&self) -> u32 {
+        self.delegate_count
+    }
+
+    /// Number of open extension accounts for this mint
+    pub fn extension_count(&self) -> u16 {
+        self.extension_count
+    }
+}
+
+/// Cursor over borsh-encoded account bytes that reads past the end as zeros, matching
+/// `deserialize_zero_extended` while borrowing strings from the account.
+struct ZeroExtendedReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ZeroExtendedReader<'a> {
+    fn u8(&mut self) -> u8 {
+        let b = self.data.get(self.pos).copied().unwrap_or(0);
+        self.pos += 1;
+        b
+    }
+
+    fn array<const N: usize>(&mut self) -> [u8; N] {
+        let mut out = [0u8; N];
+        let start = self.pos.min(self.data.len());
+        let end = (self.pos + N).min(self.data.len());
+        out[..end - start].copy_from_slice(&self.data[start..end]);
+        self.pos += N;
+        out
+    }
+
+    fn bool(&mut self) -> Result<bool, ProgramError> {
+        match self.u8() {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Borsh encodes an `Option` tag exactly like a bool
+    fn option_tag(&mut self) -> Result<bool, ProgramError> {
+        self.bool()
+    }
+
+    fn pubkey(&mut self) -> Pubkey {
+        Pubkey::from_slice(&self.array::<32>())
+    }
+
+    fn option_pubkey(&mut self) -> Result<Option<Pubkey>, ProgramError> {
+        Ok(match self.option_tag()? {
+            false => None,
+            true => Some(self.pubkey()),
+        })
+    }
+
+    /// A length-prefixed string of at most `max_len` bytes that lies entirely inside the data
+    fn str(&mut self, max_len: usize) -> Result<&'a str, ProgramError> {
+        let len = u32::from_le_bytes(self.array()) as usize;
+        if len > max_len {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if len == 0 {
+            return Ok("");
+        }
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        self.pos += len;
+        core::str::from_utf8(bytes).map_err(|_| ProgramError::InvalidAccountData)
     }
 }
 
 /// Optional metadata attributes account - linked to core metadata
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct TokenMetadataAttributes {
+    /// Account type discriminator (`AccountType::Attributes` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
     /// Initialization flag
     pub is_initialized: bool,
     /// The mint address this attributes belong to
     pub mint: Pubkey,
-    /// Key-value pairs for extensible attributes
+    /// Key-value pairs for extensible attributes, sorted by key with unique keys
     pub data: Vec<(String, String)>, // Key-value pairs for extensibility
+    /// Number of writes, counting creation; checked against `expected_revision`
+    pub revision: u64,
+    /// Slot of the most recent write
+    pub last_updated_slot: u64,
+    /// Canonical bump of this account's PDA; `None` on older accounts until their next write
+    pub bump: Option<u8>,
+}
+
+impl TokenMetadataAttributes {
+    /// Look up the value stored under `key`.
+    ///
+    /// Relies on the canonical (sorted by key) order the program writes.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.data
+            .binary_search_by(|(k, _)| k.as_str().cmp(key))
+            .ok()
+            .map(|i| self.data[i].1.as_str())
+    }
+
+    /// Bring entries written before map semantics were enforced into canonical order.
+    ///
+    /// Sorting is stable and the first occurrence of a duplicated key wins, matching what a
+    /// front-to-back scan of the old vector would have returned.
+    pub fn normalize(&mut self) {
+        self.data.sort_by(|a, b| a.0.cmp(&b.0));
+        self.data.dedup_by(|later, earlier| later.0 == earlier.0);
+    }
+
+    /// Advance `revision` and stamp `slot` as the slot of the last write
+    pub fn record_write(&mut self, slot: u64) {
+        self.revision = self.revision.saturating_add(1);
+        self.last_updated_slot = slot;
+    }
+
+    /// Serialize into `dst` without allocating, zeroing the bytes after the encoding.
+    /// Fails with `AccountTooSmall` if `dst` cannot hold it.
+    pub fn try_pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_zero_padded(self, dst)
+    }
+}
+
+/// Touch account - bumped by the update authority to tell indexers to re-read a mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadataTouch {
+    /// Account type discriminator (`AccountType::Touch` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
+    /// Initialization flag
+    pub is_initialized: bool,
+    /// The mint address this touch account belongs to
+    pub mint: Pubkey,
+    /// Slot of the most recent `Touch`
+    pub last_touched_slot: u64,
+    /// Number of `Touch` instructions processed; strictly increasing
+    pub touch_count: u64,
+    /// Canonical bump of this account's PDA; `None` on older accounts until their next touch
+    pub bump: Option<u8>,
+}
+
+impl Sealed for TokenMetadataTouch {}
+impl IsInitialized for TokenMetadataTouch {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TokenMetadataTouch {
+    const LEN: usize = TOKEN_METADATA_TOUCH_LEN;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(src, AccountType::Touch)?;
+        deserialize_zero_extended(src, Self::LEN)
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_zero_padded(self, dst).expect("destination too small for TokenMetadataTouch");
+    }
+}
+
+/// Delegate record - grants a key the right to edit a subset of a mint's metadata
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadataDelegate {
+    /// Account type discriminator (`AccountType::Delegate` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
+    /// Initialization flag
+    pub is_initialized: bool,
+    /// The mint address this delegation applies to
+    pub mint: Pubkey,
+    /// Key allowed to sign edits within `scope`
+    pub delegate: Pubkey,
+    /// Update authority that granted the delegation; the record is void once the metadata's
+    /// update authority changes
+    pub authority: Pubkey,
+    /// Fields the delegate may edit, using the `LOCK_*` bit layout
+    pub scope: u16,
+    /// Canonical bump of this account's PDA; `None` on older records until they are re-granted
+    pub bump: Option<u8>,
+}
+
+impl TokenMetadataDelegate {
+    /// Returns true if every bit in `required` is within this delegate's scope
+    pub fn covers(&self, required: u16) -> bool {
+        self.scope & required == required
+    }
+}
+
+impl Sealed for TokenMetadataDelegate {}
+impl IsInitialized for TokenMetadataDelegate {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TokenMetadataDelegate {
+    const LEN: usize = TOKEN_METADATA_DELEGATE_LEN;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(src, AccountType::Delegate)?;
+        deserialize_zero_extended(src, Self::LEN)
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        pack_zero_padded(self, dst).expect("destination too small for TokenMetadataDelegate");
+    }
+}
+
+/// M-of-N multisig that can act as a metadata update authority
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadataMultisig {
+    /// Account type discriminator (`AccountType::Multisig` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
+    /// Initialization flag
+    pub is_initialized: bool,
+    /// Number of member signatures required
+    pub m: u8,
+    /// Member keys (unique; at most `MAX_MULTISIG_SIGNERS`)
+    pub signers: Vec<Pubkey>,
+}
+
+impl TokenMetadataMultisig {
+    /// Check `m`/`n` bounds and that members are unique.
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        let n = self.signers.len();
+        if self.m == 0 || self.m as usize > n || n > MAX_MULTISIG_SIGNERS {
+            return Err(MetadataError::InvalidMultisig);
+        }
+        for (i, signer) in self.signers.iter().enumerate() {
+            if self.signers[i + 1..].contains(signer) {
+                return Err(MetadataError::InvalidMultisig);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Sealed for TokenMetadataMultisig {}
+impl IsInitialized for TokenMetadataMultisig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TokenMetadataMultisig {
+    const LEN: usize = TOKEN_METADATA_MULTISIG_MAX_LEN;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(src, AccountType::Multisig)?;
+        deserialize_zero_extended(src, Self::LEN)
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        // Zeroes the remainder, so a shrunk member list leaves no stale keys
+        pack_zero_padded(self, dst).expect("destination too small for TokenMetadataMultisig");
+    }
+}
+
+/// A creator of a token; `verified` can only be set by the creator's own signature
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Creator {
+    /// Creator key
+    pub address: Pubkey,
+    /// True once `address` has signed `SignCreator`
+    pub verified: bool,
+    /// Share in percent; shares across all creators sum to at most `MAX_CREATOR_SHARE_TOTAL`
+    pub share: u8,
+}
+
+/// Optional creators extension account - linked to core metadata
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadataCreators {
+    /// Account type discriminator (`AccountType::Creators` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
+    /// Initialization flag
+    pub is_initialized: bool,
+    /// The mint address these creators belong to
+    pub mint: Pubkey,
+    /// Creators in the order set by the update authority (unique addresses)
+    pub creators: Vec<Creator>,
+    /// Canonical bump of this account's PDA; `None` on older accounts until their next write
+    pub bump: Option<u8>,
+}
+
+impl TokenMetadataCreators {
+    /// Look up the entry for `address`.
+    pub fn get(&self, address: &Pubkey) -> Option<&Creator> {
+        self.creators.iter().find(|c| c.address == *address)
+    }
+}
+
+impl Sealed for TokenMetadataCreators {}
+impl IsInitialized for TokenMetadataCreators {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TokenMetadataCreators {
+    const LEN: usize = TOKEN_METADATA_CREATORS_MAX_LEN;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(src, AccountType::Creators)?;
+        deserialize_zero_extended(src, Self::LEN)
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        // Zeroes the remainder, so a shrunk creator list leaves no stale entries
+        pack_zero_padded(self, dst).expect("destination too small for TokenMetadataCreators");
+    }
+}
+
+/// Optional typed extension account - one per mint and registered `ExtensionType`
+///
+/// `extension_type` and the length prefix of `data` form a TLV header in front of the payload,
+/// so readers can skip or decode a payload without knowing every type.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadataExtension {
+    /// Account type discriminator (`AccountType::Extension` once initialized)
+    pub account_type: AccountType,
+    /// Layout version of this account
+    pub version: u8,
+    /// Initialization flag
+    pub is_initialized: bool,
+    /// The mint address this extension belongs to
+    pub mint: Pubkey,
+    /// Registered `ExtensionType` id; also a PDA seed
+    pub extension_type: u16,
+    /// Type-specific payload (at most `MAX_EXTENSION_DATA_LEN` bytes)
+    pub data: Vec<u8>,
+    /// Number of writes, counting creation; checked against `expected_revision`
+    pub revision: u64,
+    /// Slot of the most recent write
+    pub last_updated_slot: u64,
+    /// Canonical bump of this account's PDA
+    pub bump: u8,
+}
+
+impl TokenMetadataExtension {
+    /// Advance `revision` and stamp `slot` as the slot of the last write
+    pub fn record_write(&mut self, slot: u64) {
+        self.revision = self.revision.saturating_add(1);
+        self.last_updated_slot = slot;
+    }
+
+    /// Serialize into `dst` without allocating, zeroing the bytes after the encoding.
+    /// Fails with `AccountTooSmall` if `dst` cannot hold it.
+    pub fn try_pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        pack_zero_padded(self, dst)
+    }
+}
+
+impl Sealed for TokenMetadataExtension {}
+impl IsInitialized for TokenMetadataExtension {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TokenMetadataExtension {
+    const LEN: usize = TOKEN_METADATA_EXTENSION_MAX_LEN;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(src, AccountType::Extension)?;
+        deserialize_zero_extended(src, Self::LEN)
+    }
+
+    /// Panics if `dst` is too small; on-chain writes use `try_pack_into_slice`.
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        self.try_pack_into_slice(dst)
+            .expect("destination too small for TokenMetadataExtension");
+    }
+}
+
+/// Extension payload policy: within `MAX_EXTENSION_DATA_LEN`
+pub fn validate_extension_data(data: &[u8]) -> Result<(), MetadataError> {
+    if data.len() > MAX_EXTENSION_DATA_LEN {
+        return Err(MetadataError::ExtensionDataTooLong);
+    }
+    Ok(())
+}
+
+/// Name policy: within `NAME_MAX_LEN`, not blank, and free of control characters
+pub fn validate_name(name: &str) -> Result<(), MetadataError> {
+    if name.len() > NAME_MAX_LEN {
+        return Err(MetadataError::StringTooLong);
+    }
+    if name.trim().is_empty() {
+        return Err(MetadataError::EmptyName);
+    }
+    if name.chars().any(char::is_control) {
+        return Err(MetadataError::ControlCharacter);
+    }
+    Ok(())
+}
+
+/// Symbol policy: within `SYMBOL_MAX_LEN`, non-empty, and printable ASCII without whitespace
+pub fn validate_symbol(symbol: &str) -> Result<(), MetadataError> {
+    if symbol.len() > SYMBOL_MAX_LEN {
+        return Err(MetadataError::StringTooLong);
+    }
+    if symbol.is_empty() {
+        return Err(MetadataError::EmptySymbol);
+    }
+    if !symbol.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(MetadataError::InvalidSymbol);
+    }
+    Ok(())
+}
+
+/// Image policy: within `IMAGE_MAX_LEN` and either empty or a URI (see `validate_uri`)
+pub fn validate_image(image: &str) -> Result<(), MetadataError> {
+    if image.len() > IMAGE_MAX_LEN {
+        return Err(MetadataError::StringTooLong);
+    }
+    validate_uri(image)
+}
+
+/// Description policy: within `DESCRIPTION_MAX_LEN` and free of control characters other
+/// than line breaks and tabs
+pub fn validate_description(description: &str) -> Result<(), MetadataError> {
+    if description.len() > DESCRIPTION_MAX_LEN {
+        return Err(MetadataError::StringTooLong);
+    }
+    if description
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        return Err(MetadataError::ControlCharacter);
+    }
+    Ok(())
+}
+
+/// Run the name, symbol, image and description policies in that order
+pub fn validate_metadata_fields(
+    name: &str,
+    symbol: &str,
+    image: &str,
+    description: &str,
+) -> Result<(), MetadataError> {
+    validate_name(name)?;
+    validate_symbol(symbol)?;
+    validate_image(image)?;
+    validate_description(description)
+}
+
+/// URI policy: empty (no URI), or free of control characters and starting with one of
+/// `ALLOWED_URI_SCHEMES`
+pub fn validate_uri(uri: &str) -> Result<(), MetadataError> {
+    if uri.is_empty() {
+        return Ok(());
+    }
+    if uri.chars().any(char::is_control) {
+        return Err(MetadataError::ControlCharacter);
+    }
+    let allowed = ALLOWED_URI_SCHEMES.iter().any(|scheme| {
+        uri.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    if !allowed {
+        return Err(MetadataError::UnsupportedUriScheme);
+    }
+    Ok(())
+}
+
+/// Check creator caps: at most `MAX_CREATORS`, unique addresses, and shares summing to at
+/// most `MAX_CREATOR_SHARE_TOTAL`.
+pub fn validate_creators(creators: &[Creator]) -> Result<(), MetadataError> {
+    if creators.len() > MAX_CREATORS {
+        return Err(MetadataError::TooManyCreators);
+    }
+    let mut total: u16 = 0;
+    for (i, creator) in creators.iter().enumerate() {
+        if creators[i + 1..]
+            .iter()
+            .any(|c| c.address == creator.address)
+        {
+            return Err(MetadataError::DuplicateCreator);
+        }
+        total += creator.share as u16;
+    }
+    if total > MAX_CREATOR_SHARE_TOTAL as u16 {
+        return Err(MetadataError::InvalidCreatorShares);
+    }
+    Ok(())
+}
+
+/// Sort attribute entries by key (byte order) and reject duplicate keys.
+pub fn sort_attributes(data: &mut [(String, String)]) -> Result<(), MetadataError> {
+    data.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    if data.windows(2).any(|w| w[0].0 == w[1].0) {
+        return Err(MetadataError::DuplicateAttributeKey);
+    }
+    Ok(())
 }
 
 impl Sealed for TokenMetadataAttributes {}
@@ -119,24 +1128,142 @@ impl Pack for TokenMetadataAttributes {
     const LEN: usize = TOKEN_METADATA_ATTRIBUTES_MAX_LEN;
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut slice_ref: &[u8] = src;
-        BorshDeserialize::deserialize(&mut slice_ref).map_err(|_| ProgramError::InvalidAccountData)
+        check_account_header(src, AccountType::Attributes)?;
+        deserialize_zero_extended(src, Self::LEN)
     }
 
+    /// Panics if `dst` is too small; on-chain writes use `try_pack_into_slice`.
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let data = borsh::to_vec(self).unwrap();
-        dst[..data.len()].copy_from_slice(&data);
-        if data.len() < dst.len() {
-            for b in &mut dst[data.len()..] {
-                *b = 0;
+        self.try_pack_into_slice(dst)
+            .expect("destination too small for TokenMetadataAttributes");
+    }
+}
+
+/// Validate the account header at the start of `src` against the expected account type.
+///
+/// Zeroed buffers pass so that callers can rely on `IsInitialized` for uninitialized accounts.
+fn check_account_header(src: &[u8], expected: AccountType) -> Result<(), ProgramError> {
+    if src.len() < ACCOUNT_HEADER_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (account_type, version) = (src[0], src[1]);
+    if account_type == AccountType::Uninitialized as u8 {
+        return Ok(());
+    }
+    if account_type == LEGACY_V0_INITIALIZED {
+        return Err(MetadataError::AccountNeedsMigration.into());
+    }
+    if account_type != expected as u8 {
+        return Err(MetadataError::InvalidAccountType.into());
+    }
+    if version != CURRENT_ACCOUNT_VERSION {
+        return Err(MetadataError::UnsupportedAccountVersion.into());
+    }
+    Ok(())
+}
+
+/// Borsh-serialize `value` directly into `dst` and zero whatever follows the encoding.
+///
+/// The writer is bounded by `dst` itself, so nothing is allocated on the heap; running out of
+/// room fails with `AccountTooSmall` (the bytes already written are left in place).
+fn pack_zero_padded<T: BorshSerialize>(value: &T, dst: &mut [u8]) -> Result<(), ProgramError> {
+    let mut writer: &mut [u8] = dst;
+    value
+        .serialize(&mut writer)
+        .map_err(|_| MetadataError::AccountTooSmall)?;
+    writer.fill(0);
+    Ok(())
+}
+
+/// Deserialize an account body, treating bytes past the end of `src` as zero.
+///
+/// Fields are only ever appended to the end of a layout, and an appended field's default must
+/// encode as zeros. Accounts allocated before a field existed therefore decode it as its default
+/// (e.g. `None`) without a version bump; `MigrateAccount` grows them to the current size.
+fn deserialize_zero_extended<T: BorshDeserialize>(
+    src: &[u8],
+    len: usize,
+) -> Result<T, ProgramError> {
+    let mut reader = ZeroPaddedReader {
+        data: src,
+        zeros: len.saturating_sub(src.len()),
+    };
+    T::deserialize_reader(&mut reader).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// `Read` over account bytes followed by `zeros` zero bytes, so a short account decodes
+/// without being copied into a padded buffer.
+struct ZeroPaddedReader<'a> {
+    data: &'a [u8],
+    zeros: usize,
+}
+
+impl borsh::io::Read for ZeroPaddedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> borsh::io::Result<usize> {
+        if !self.data.is_empty() {
+            let n = buf.len().min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            return Ok(n);
+        }
+        let n = buf.len().min(self.zeros);
+        buf[..n].fill(0);
+        self.zeros -= n;
+        Ok(n)
+    }
+}
+
+/// Rewrite a legacy (v0) account body into the current layout.
+///
+/// The current layout is the v0 body prefixed with `[account_type, version]`, so the
+/// upgrade is a pure prefix; the result is unpacked to make sure it decodes cleanly.
+/// Metadata keeps the v0 length (the caller grows the account, and the appended fields
+/// decode as their zero defaults meanwhile). Attributes are re-packed at
+/// `attributes_account_len` of their normalized entries.
+pub fn upgrade_legacy_account_data(
+    legacy: &[u8],
+    account_type: AccountType,
+) -> Result<Vec<u8>, ProgramError> {
+    if legacy.first() != Some(&LEGACY_V0_INITIALIZED) {
+        return Err(MetadataError::InvalidAccountType.into());
+    }
+
+    let mut upgraded = Vec::with_capacity(ACCOUNT_HEADER_LEN + legacy.len());
+    upgraded.push(account_type as u8);
+    upgraded.push(CURRENT_ACCOUNT_VERSION);
+    upgraded.extend_from_slice(legacy);
+
+    match account_type {
+        AccountType::Metadata => {
+            if !TokenMetadata::unpack_from_slice(&upgraded)?.is_initialized() {
+                return Err(ProgramError::UninitializedAccount);
             }
         }
+        AccountType::Attributes => {
+            // The current layout appends fields the legacy body never had room for, so the
+            // normalized entries get a buffer sized for them rather than the legacy length
+            let mut attrs = TokenMetadataAttributes::unpack_from_slice(&upgraded)?;
+            if !attrs.is_initialized() {
+                return Err(ProgramError::UninitializedAccount);
+            }
+            attrs.normalize();
+            upgraded = vec![0u8; attributes_account_len(&attrs.data)];
+            attrs.try_pack_into_slice(&mut upgraded)?;
+        }
+        AccountType::Uninitialized
+        | AccountType::Touch
+        | AccountType::Delegate
+        | AccountType::Multisig
+        | AccountType::Creators
+        | AccountType::Extension => return Err(MetadataError::InvalidAccountType.into()),
     }
+    Ok(upgraded)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extension::ExtensionType;
     use arch_program::program_pack::Pack;
 
     fn pk(byte: u8) -> Pubkey {
@@ -147,6 +1274,8 @@ mod tests {
     #[test]
     fn token_metadata_pack_unpack_roundtrip() {
         let md = TokenMetadata {
+            account_type: AccountType::Metadata,
+            version: CURRENT_ACCOUNT_VERSION,
             is_initialized: true,
             mint: pk(1),
             name: "Arch Pioneer Token".to_string(),
@@ -154,13 +1283,26 @@ mod tests {
             image: "https://arweave.net/abc123.png".to_string(),
             description: "The first token launched on Arch Network".to_string(),
             update_authority: Some(pk(2)),
+            pending_authority: None,
+            locked_fields: 0,
+            collection: None,
+            uri: String::new(),
+            content_hash: [0u8; 32],
+            content_hash_algorithm: ContentHashAlgorithm::None,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
+            delegate_count: 0,
+            extension_count: 0,
         };
 
         let mut buf = vec![0u8; TokenMetadata::LEN];
         TokenMetadata::pack_into_slice(&md, &mut buf);
 
-        // First byte reflects is_initialized (borsh bool -> u8)
-        assert_eq!(buf[0], 1);
+        // Header: account type, layout version, then is_initialized (borsh bool -> u8)
+        assert_eq!(buf[0], AccountType::Metadata as u8);
+        assert_eq!(buf[1], CURRENT_ACCOUNT_VERSION);
+        assert_eq!(buf[2], 1);
 
         let md2 = TokenMetadata::unpack_from_slice(&buf).expect("unpack md");
         assert_eq!(md, md2);
@@ -169,6 +1311,8 @@ mod tests {
     #[test]
     fn token_metadata_unpack_ignores_trailing_zeros() {
         let md = TokenMetadata {
+            account_type: AccountType::Metadata,
+            version: CURRENT_ACCOUNT_VERSION,
             is_initialized: true,
             mint: pk(9),
             name: "Name".to_string(),
@@ -176,6 +1320,17 @@ mod tests {
             image: "img".to_string(),
             description: "desc".to_string(),
             update_authority: None,
+            pending_authority: None,
+            locked_fields: 0,
+            collection: None,
+            uri: String::new(),
+            content_hash: [0u8; 32],
+            content_hash_algorithm: ContentHashAlgorithm::None,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
+            delegate_count: 0,
+            extension_count: 0,
         };
 
         let mut packed = borsh::to_vec(&md).unwrap();
@@ -189,17 +1344,23 @@ mod tests {
     #[test]
     fn token_metadata_attributes_pack_unpack_roundtrip() {
         let attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
             is_initialized: true,
             mint: pk(3),
             data: vec![
                 ("key1".to_string(), "value1".to_string()),
                 ("k".to_string(), "v".to_string()),
             ],
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
         };
 
         let mut buf = vec![0u8; TokenMetadataAttributes::LEN];
         TokenMetadataAttributes::pack_into_slice(&attrs, &mut buf);
-        assert_eq!(buf[0], 1);
+        assert_eq!(buf[0], AccountType::Attributes as u8);
+        assert_eq!(buf[2], 1);
 
         let attrs2 = TokenMetadataAttributes::unpack_from_slice(&buf).expect("unpack attrs");
         assert_eq!(attrs, attrs2);
@@ -208,9 +1369,14 @@ mod tests {
     #[test]
     fn token_metadata_attributes_unpack_ignores_trailing_zeros() {
         let attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
             is_initialized: true,
             mint: pk(7),
             data: vec![("alpha".into(), "beta".into())],
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
         };
 
         let mut packed = borsh::to_vec(&attrs).unwrap();
@@ -219,4 +1385,752 @@ mod tests {
         let unpacked = TokenMetadataAttributes::unpack_from_slice(&packed).expect("unpack attrs");
         assert_eq!(attrs, unpacked);
     }
+
+    #[test]
+    fn attributes_account_len_matches_packed_size() {
+        let mut attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(7),
+            data: vec![],
+            revision: 0,
+            last_updated_slot: 0,
+            bump: Some(254),
+        };
+        for data in [
+            vec![],
+            vec![("website".to_string(), "https://arch.network".to_string())],
+            (0..MAX_ATTRIBUTES)
+                .map(|i| (format!("{i:0>64}"), "v".repeat(MAX_VALUE_LENGTH)))
+                .collect(),
+        ] {
+            attrs.data = data;
+            let len = attributes_account_len(&attrs.data);
+            assert_eq!(len, borsh::object_length(&attrs).unwrap());
+            let mut buf = vec![0u8; len];
+            attrs.pack_into_slice(&mut buf);
+            assert_eq!(
+                TokenMetadataAttributes::unpack_from_slice(&buf).unwrap(),
+                attrs
+            );
+        }
+        // Growing from empty to full fits in one instruction
+        assert_eq!(
+            attributes_account_len(&attrs.data),
+            TOKEN_METADATA_ATTRIBUTES_MAX_LEN
+        );
+    }
+
+    fn sample_metadata() -> TokenMetadata {
+        TokenMetadata {
+            account_type: AccountType::Metadata,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(4),
+            name: "Name".to_string(),
+            symbol: "SYM".to_string(),
+            image: "img".to_string(),
+            description: "desc".to_string(),
+            update_authority: Some(pk(5)),
+            pending_authority: None,
+            locked_fields: 0,
+            collection: None,
+            uri: String::new(),
+            content_hash: [0u8; 32],
+            content_hash_algorithm: ContentHashAlgorithm::None,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
+            delegate_count: 0,
+            extension_count: 0,
+        }
+    }
+
+    #[test]
+    fn unpack_defaults_fields_missing_from_shorter_accounts() {
+        let md = TokenMetadata {
+            name: "n".repeat(NAME_MAX_LEN),
+            symbol: "s".repeat(SYMBOL_MAX_LEN),
+            image: "i".repeat(IMAGE_MAX_LEN),
+            description: "d".repeat(DESCRIPTION_MAX_LEN),
+            ..sample_metadata()
+        };
+        let packed = borsh::to_vec(&md).unwrap();
+        assert_eq!(packed.len(), TokenMetadata::LEN - 32 - 33 - URI_MAX_LEN - 1);
+
+        // An account sized before `pending_authority`, `locked_fields`, `collection`, the
+        // content URI, the revision, the bump and the open-record counts existed ends right
+        // after update_authority
+        let short = &packed[..packed.len() - 4 - (4 + 32 + 1) - (8 + 8) - 1 - (4 + 2)];
+        assert_eq!(TokenMetadata::unpack_from_slice(short).unwrap(), md);
+    }
+
+    #[test]
+    fn token_metadata_ref_matches_unpack() {
+        let md = TokenMetadata {
+            pending_authority: Some(pk(6)),
+            locked_fields: LOCK_NAME | LOCK_URI,
+            collection: Some(Collection {
+                key: pk(7),
+                verified: true,
+            }),
+            uri: "https://example.com/token.json".to_string(),
+            content_hash: [9u8; 32],
+            content_hash_algorithm: ContentHashAlgorithm::Sha256,
+            revision: 3,
+            last_updated_slot: 42,
+            bump: Some(254),
+            delegate_count: 2,
+            extension_count: 1,
+            ..sample_metadata()
+        };
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        md.pack_into_slice(&mut buf);
+
+        let view = TokenMetadataRef::new(&buf).unwrap();
+        assert_eq!(view.mint(), md.mint);
+        assert_eq!(
+            (view.name(), view.symbol(), view.image(), view.description()),
+            ("Name", "SYM", "img", "desc")
+        );
+        assert_eq!(view.update_authority(), md.update_authority);
+        assert_eq!(view.pending_authority(), md.pending_authority);
+        assert!(view.is_locked(LOCK_URI) && !view.is_locked(LOCK_SYMBOL));
+        assert_eq!(view.collection(), md.collection);
+        assert_eq!(view.uri(), md.uri);
+        assert_eq!(view.content_hash(), md.content_hash);
+        assert_eq!(view.content_hash_algorithm(), md.content_hash_algorithm);
+        assert_eq!(
+            (view.revision(), view.last_updated_slot(), view.bump()),
+            (3, 42, Some(254))
+        );
+        assert_eq!((view.delegate_count(), view.extension_count()), (2, 1));
+
+        // Shorter accounts read missing trailing fields as defaults, as `unpack` does
+        let packed = borsh::to_vec(&sample_metadata()).unwrap();
+        let short = &packed[..packed.len() - 4 - (4 + 32 + 1) - (8 + 8) - 1 - (4 + 2)];
+        let view = TokenMetadataRef::new(short).unwrap();
+        assert_eq!(view.name(), "Name");
+        assert_eq!((view.pending_authority(), view.uri()), (None, ""));
+        assert_eq!((view.revision(), view.bump()), (0, None));
+    }
+
+    #[test]
+    fn token_metadata_ref_validates_up_front() {
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        sample_metadata().pack_into_slice(&mut buf);
+        let name_len_at = ACCOUNT_HEADER_LEN + 1 + 32;
+
+        // Zeroed account
+        assert_eq!(
+            TokenMetadataRef::new(&[0u8; TokenMetadata::LEN]).unwrap_err(),
+            ProgramError::UninitializedAccount
+        );
+
+        // Another account type
+        let mut wrong_type = buf.clone();
+        wrong_type[0] = AccountType::Attributes as u8;
+        assert!(TokenMetadataRef::new(&wrong_type).is_err());
+
+        // Name longer than NAME_MAX_LEN
+        let mut too_long = buf.clone();
+        too_long[name_len_at..name_len_at + 4]
+            .copy_from_slice(&(NAME_MAX_LEN as u32 + 1).to_le_bytes());
+        assert!(TokenMetadataRef::new(&too_long).is_err());
+
+        // Name running past the end of the data
+        assert!(TokenMetadataRef::new(&buf[..name_len_at + 4 + 2]).is_err());
+
+        // Invalid UTF-8
+        let mut bad_utf8 = buf.clone();
+        bad_utf8[name_len_at + 4] = 0xff;
+        assert!(TokenMetadataRef::new(&bad_utf8).is_err());
+    }
+
+    #[test]
+    fn record_write_advances_revision_and_slot() {
+        let mut md = sample_metadata();
+        md.record_write(100);
+        md.record_write(105);
+        assert_eq!((md.revision, md.last_updated_slot), (2, 105));
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        md.pack_into_slice(&mut buf);
+        assert_eq!(TokenMetadata::unpack_from_slice(&buf).unwrap(), md);
+
+        let mut attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(4),
+            data: vec![("k".into(), "v".into())],
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
+        };
+        attrs.record_write(7);
+        let mut buf = vec![0u8; attributes_account_len(&attrs.data)];
+        attrs.pack_into_slice(&mut buf);
+        assert_eq!(
+            TokenMetadataAttributes::unpack_from_slice(&buf).unwrap(),
+            attrs
+        );
+
+        // Attributes written before the revision (and the bump after it) existed read as 0
+        let legacy = &buf[..buf.len() - 2 - 16];
+        let decoded = TokenMetadataAttributes::unpack_from_slice(legacy).unwrap();
+        assert_eq!((decoded.revision, decoded.last_updated_slot), (0, 0));
+        assert_eq!(decoded.data, attrs.data);
+    }
+
+    #[test]
+    fn try_pack_into_slice_is_bounded_by_the_destination() {
+        let md = sample_metadata();
+        let len = borsh::object_length(&md).unwrap();
+
+        // Stale bytes past the encoding are zeroed
+        let mut buf = vec![0xAA; len + 8];
+        md.try_pack_into_slice(&mut buf).unwrap();
+        assert_eq!(buf[..len], borsh::to_vec(&md).unwrap()[..]);
+        assert!(buf[len..].iter().all(|b| *b == 0));
+
+        let mut short = vec![0u8; len - 1];
+        assert_eq!(
+            md.try_pack_into_slice(&mut short).unwrap_err(),
+            MetadataError::AccountTooSmall.into()
+        );
+
+        let attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(4),
+            data: vec![("k".into(), "v".into())],
+            revision: 1,
+            last_updated_slot: 9,
+            bump: Some(255),
+        };
+        let mut short = vec![0u8; attributes_account_len(&attrs.data) - 1];
+        assert_eq!(
+            attrs.try_pack_into_slice(&mut short).unwrap_err(),
+            MetadataError::AccountTooSmall.into()
+        );
+    }
+
+    #[test]
+    fn token_metadata_collection_roundtrip() {
+        let md = TokenMetadata {
+            collection: Some(Collection {
+                key: pk(8),
+                verified: true,
+            }),
+            ..sample_metadata()
+        };
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        md.pack_into_slice(&mut buf);
+        assert_eq!(TokenMetadata::unpack_from_slice(&buf).unwrap(), md);
+    }
+
+    #[test]
+    fn content_uri_roundtrip_and_validate() {
+        let content = ContentUri {
+            uri: "https://example.com/token.json".to_string(),
+            content_hash: [0xab; 32],
+            hash_algorithm: ContentHashAlgorithm::Sha256,
+        };
+        assert!(content.validate().is_ok());
+        let mut md = sample_metadata();
+        md.set_content_uri(content.clone());
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        md.pack_into_slice(&mut buf);
+        assert_eq!(TokenMetadata::unpack_from_slice(&buf).unwrap(), md);
+
+        let bad = [
+            ContentUri {
+                uri: "u".repeat(URI_MAX_LEN + 1),
+                ..content.clone()
+            },
+            ContentUri {
+                hash_algorithm: ContentHashAlgorithm::None,
+                ..content.clone()
+            },
+            ContentUri {
+                uri: String::new(),
+                ..content.clone()
+            },
+            ContentUri {
+                content_hash: [0u8; 32],
+                ..content
+            },
+        ];
+        for content in bad {
+            assert!(content.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn content_policy_rejects_with_precise_errors() {
+        assert!(validate_metadata_fields("Arch Pioneer", "APT", "", "Line one\nline two").is_ok());
+        for image in [
+            "https://arweave.net/a.png",
+            "IPFS://bafy",
+            "ar://tx",
+            "data:image/png;base64,AA==",
+        ] {
+            assert_eq!(validate_image(image), Ok(()));
+        }
+
+        assert_eq!(validate_name(""), Err(MetadataError::EmptyName));
+        assert_eq!(validate_name("  "), Err(MetadataError::EmptyName));
+        assert_eq!(
+            validate_name("a\u{0}b"),
+            Err(MetadataError::ControlCharacter)
+        );
+        assert_eq!(validate_symbol(""), Err(MetadataError::EmptySymbol));
+        assert_eq!(validate_symbol("A B"), Err(MetadataError::InvalidSymbol));
+        assert_eq!(validate_symbol("APT\n"), Err(MetadataError::InvalidSymbol));
+        assert_eq!(validate_symbol("ÄPT"), Err(MetadataError::InvalidSymbol));
+        assert_eq!(
+            validate_symbol(&"S".repeat(SYMBOL_MAX_LEN + 1)),
+            Err(MetadataError::StringTooLong)
+        );
+        assert_eq!(
+            validate_image("http://example.com/a.png"),
+            Err(MetadataError::UnsupportedUriScheme)
+        );
+        assert_eq!(
+            validate_image("javascript:alert(1)"),
+            Err(MetadataError::UnsupportedUriScheme)
+        );
+        assert_eq!(
+            validate_image("https://a\u{7}"),
+            Err(MetadataError::ControlCharacter)
+        );
+        assert_eq!(
+            validate_description("a\u{1b}[31m"),
+            Err(MetadataError::ControlCharacter)
+        );
+        assert_eq!(
+            ContentUri {
+                uri: "ftp://example.com/t.json".to_string(),
+                content_hash: [0u8; 32],
+                hash_algorithm: ContentHashAlgorithm::None,
+            }
+            .validate(),
+            Err(MetadataError::UnsupportedUriScheme)
+        );
+    }
+
+    #[test]
+    fn summary_fits_return_data_and_tolerates_appended_fields() {
+        let md = TokenMetadata {
+            name: "n".repeat(NAME_MAX_LEN),
+            symbol: "s".repeat(SYMBOL_MAX_LEN),
+            collection: Some(Collection {
+                key: pk(8),
+                verified: false,
+            }),
+            uri: "u".repeat(URI_MAX_LEN),
+            ..sample_metadata()
+        };
+        let summary = TokenMetadataSummary::from(&md);
+        let mut encoded = borsh::to_vec(&summary).unwrap();
+        assert_eq!(encoded.len(), TOKEN_METADATA_SUMMARY_MAX_LEN);
+        assert!(encoded.len() <= 1024);
+
+        encoded.extend_from_slice(&[7u8; 8]);
+        assert_eq!(TokenMetadataSummary::decode(&encoded).unwrap(), summary);
+        assert!(TokenMetadataSummary::decode(&encoded[..10]).is_err());
+    }
+
+    #[test]
+    fn token_metadata_creators_pack_unpack_and_validate() {
+        let creator = |byte, share| Creator {
+            address: pk(byte),
+            verified: false,
+            share,
+        };
+        let record = TokenMetadataCreators {
+            account_type: AccountType::Creators,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(1),
+            creators: vec![creator(2, 60), creator(3, 40)],
+            bump: Some(253),
+        };
+        let mut buf = vec![0xffu8; TokenMetadataCreators::LEN];
+        record.pack_into_slice(&mut buf);
+        assert_eq!(
+            TokenMetadataCreators::unpack_from_slice(&buf).unwrap(),
+            record
+        );
+        assert_eq!(record.get(&pk(3)).map(|c| c.share), Some(40));
+        assert!(record.get(&pk(4)).is_none());
+
+        assert_eq!(validate_creators(&record.creators), Ok(()));
+        assert_eq!(validate_creators(&[]), Ok(()));
+        assert_eq!(
+            validate_creators(&[creator(2, 60), creator(3, 41)]),
+            Err(MetadataError::InvalidCreatorShares)
+        );
+        assert_eq!(
+            validate_creators(&[creator(2, 10), creator(2, 10)]),
+            Err(MetadataError::DuplicateCreator)
+        );
+        let too_many: Vec<_> = (0..=MAX_CREATORS as u8).map(|b| creator(b, 1)).collect();
+        assert_eq!(
+            validate_creators(&too_many),
+            Err(MetadataError::TooManyCreators)
+        );
+    }
+
+    #[test]
+    fn token_metadata_extension_tlv_layout_and_sizing() {
+        let ext = TokenMetadataExtension {
+            account_type: AccountType::Extension,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(1),
+            extension_type: ExtensionType::Royalty.id(),
+            data: vec![0xab; 34],
+            revision: 1,
+            last_updated_slot: 9,
+            bump: 254,
+        };
+        let mut buf = vec![0xffu8; extension_account_len(ext.data.len())];
+        ext.pack_into_slice(&mut buf);
+        assert_eq!(
+            TokenMetadataExtension::unpack_from_slice(&buf).unwrap(),
+            ext
+        );
+
+        // TLV header: type id then payload length, both little-endian, ahead of the payload
+        let tlv = ACCOUNT_HEADER_LEN + 1 + 32;
+        assert_eq!(buf[tlv..tlv + 2], 1u16.to_le_bytes());
+        assert_eq!(buf[tlv + 2..tlv + 6], 34u32.to_le_bytes());
+        assert!(buf[tlv + 6..tlv + 6 + 34].iter().all(|b| *b == 0xab));
+
+        let mut short = vec![0u8; extension_account_len(ext.data.len()) - 1];
+        assert_eq!(
+            ext.try_pack_into_slice(&mut short).unwrap_err(),
+            MetadataError::AccountTooSmall.into()
+        );
+        assert_eq!(
+            extension_account_len(MAX_EXTENSION_DATA_LEN),
+            TokenMetadataExtension::LEN
+        );
+
+        assert_eq!(
+            validate_extension_data(&[0; MAX_EXTENSION_DATA_LEN]),
+            Ok(())
+        );
+        assert_eq!(
+            validate_extension_data(&[0; MAX_EXTENSION_DATA_LEN + 1]),
+            Err(MetadataError::ExtensionDataTooLong)
+        );
+        for ty in ExtensionType::ALL {
+            assert_eq!(ExtensionType::from_u16(ty.id()), Some(ty));
+        }
+        assert_eq!(ExtensionType::from_u16(0), None);
+    }
+
+    #[test]
+    fn is_locked_checks_any_bit_in_mask() {
+        let md = TokenMetadata {
+            locked_fields: LOCK_NAME | LOCK_SYMBOL,
+            ..sample_metadata()
+        };
+        assert!(md.is_locked(LOCK_NAME));
+        assert!(md.is_locked(LOCK_SYMBOL | LOCK_IMAGE));
+        assert!(!md.is_locked(LOCK_IMAGE | LOCK_DESCRIPTION));
+        assert!(!sample_metadata().is_locked(LOCK_ALL));
+    }
+
+    #[test]
+    fn unpack_rejects_wrong_account_type() {
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        sample_metadata().pack_into_slice(&mut buf);
+
+        let err = TokenMetadataAttributes::unpack_from_slice(&buf).unwrap_err();
+        assert_eq!(err, MetadataError::InvalidAccountType.into());
+    }
+
+    #[test]
+    fn unpack_rejects_unknown_version() {
+        let mut buf = vec![0u8; TokenMetadata::LEN];
+        sample_metadata().pack_into_slice(&mut buf);
+        buf[1] = CURRENT_ACCOUNT_VERSION + 1;
+
+        let err = TokenMetadata::unpack_from_slice(&buf).unwrap_err();
+        assert_eq!(err, MetadataError::UnsupportedAccountVersion.into());
+    }
+
+    /// Size of a deployed v0 metadata account (the v0 `TOKEN_METADATA_MAX_LEN`)
+    const LEGACY_METADATA_LEN: usize = 1 + 32 + 260 + 20 + 516 + 516 + 33;
+
+    /// Size of a deployed v0 attributes account (the v0 `TOKEN_METADATA_ATTRIBUTES_MAX_LEN`)
+    const LEGACY_ATTRIBUTES_LEN: usize =
+        1 + 32 + 4 + MAX_ATTRIBUTES * ((4 + MAX_KEY_LENGTH) + (4 + MAX_VALUE_LENGTH));
+
+    /// `md` as the v0 program wrote it: no header and none of the appended fields
+    fn legacy_metadata_body(md: &TokenMetadata) -> Vec<u8> {
+        let mut body = borsh::to_vec(&(
+            md.is_initialized,
+            md.mint,
+            &md.name,
+            &md.symbol,
+            &md.image,
+            &md.description,
+            md.update_authority,
+        ))
+        .unwrap();
+        body.resize(LEGACY_METADATA_LEN, 0);
+        body
+    }
+
+    /// `attrs` as the v0 program wrote it: no header and none of the appended fields
+    fn legacy_attributes_body(attrs: &TokenMetadataAttributes) -> Vec<u8> {
+        let mut body = borsh::to_vec(&(attrs.is_initialized, attrs.mint, &attrs.data)).unwrap();
+        body.resize(LEGACY_ATTRIBUTES_LEN, 0);
+        body
+    }
+
+    #[test]
+    fn unpack_flags_legacy_accounts_for_migration() {
+        let legacy = legacy_metadata_body(&sample_metadata());
+
+        let err = TokenMetadata::unpack_from_slice(&legacy).unwrap_err();
+        assert_eq!(err, MetadataError::AccountNeedsMigration.into());
+    }
+
+    #[test]
+    fn upgrade_legacy_metadata_and_attributes() {
+        let md = TokenMetadata {
+            name: "n".repeat(NAME_MAX_LEN),
+            update_authority: Some(pk(5)),
+            ..sample_metadata()
+        };
+        let legacy = legacy_metadata_body(&md);
+        let upgraded =
+            upgrade_legacy_account_data(&legacy, AccountType::Metadata).expect("upgrade md");
+        assert_eq!(upgraded.len(), ACCOUNT_HEADER_LEN + LEGACY_METADATA_LEN);
+        assert_eq!(TokenMetadata::unpack_from_slice(&upgraded).unwrap(), md);
+
+        let attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(6),
+            data: vec![("k".into(), "v".into())],
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
+        };
+        let upgraded =
+            upgrade_legacy_account_data(&legacy_attributes_body(&attrs), AccountType::Attributes)
+                .expect("upgrade attrs");
+        assert_eq!(upgraded.len(), attributes_account_len(&attrs.data));
+        assert_eq!(
+            TokenMetadataAttributes::unpack_from_slice(&upgraded).unwrap(),
+            attrs
+        );
+
+        // Legacy attributes are brought into canonical order
+        let legacy_attrs = TokenMetadataAttributes {
+            data: vec![
+                ("b".into(), "2".into()),
+                ("a".into(), "1".into()),
+                ("b".into(), "3".into()),
+            ],
+            ..attrs.clone()
+        };
+        let upgraded = upgrade_legacy_account_data(
+            &legacy_attributes_body(&legacy_attrs),
+            AccountType::Attributes,
+        )
+        .expect("upgrade unsorted attrs");
+        let upgraded = TokenMetadataAttributes::unpack_from_slice(&upgraded).unwrap();
+        assert_eq!(
+            upgraded.data,
+            vec![("a".to_string(), "1".to_string()), ("b".into(), "2".into())]
+        );
+
+        // A full legacy account grows to make room for every appended field
+        let full = TokenMetadataAttributes {
+            data: (0..MAX_ATTRIBUTES)
+                .map(|i| (format!("{i:0>64}"), "v".repeat(MAX_VALUE_LENGTH)))
+                .collect(),
+            ..attrs
+        };
+        let legacy = legacy_attributes_body(&full);
+        assert_eq!(
+            borsh::to_vec(&(true, full.mint, &full.data)).unwrap().len(),
+            legacy.len()
+        );
+        let upgraded = upgrade_legacy_account_data(&legacy, AccountType::Attributes)
+            .expect("upgrade full attrs");
+        assert_eq!(upgraded.len(), attributes_account_len(&full.data));
+        assert_eq!(
+            TokenMetadataAttributes::unpack_from_slice(&upgraded).unwrap(),
+            full
+        );
+
+        // A legacy metadata body does not decode as attributes
+        assert!(
+            upgrade_legacy_account_data(&legacy_metadata_body(&md), AccountType::Attributes)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn token_metadata_touch_pack_unpack_roundtrip() {
+        let touch = TokenMetadataTouch {
+            account_type: AccountType::Touch,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(10),
+            last_touched_slot: 1234,
+            touch_count: 7,
+            bump: Some(255),
+        };
+
+        let mut buf = vec![0u8; TokenMetadataTouch::LEN];
+        touch.pack_into_slice(&mut buf);
+        assert_eq!(
+            borsh::to_vec(&touch).unwrap().len(),
+            TokenMetadataTouch::LEN
+        );
+        assert_eq!(TokenMetadataTouch::unpack_from_slice(&buf).unwrap(), touch);
+
+        // A zeroed account decodes as uninitialized
+        let zeroed = TokenMetadataTouch::unpack_from_slice(&[0u8; TOKEN_METADATA_TOUCH_LEN])
+            .expect("unpack zeroed");
+        assert!(!zeroed.is_initialized());
+    }
+
+    #[test]
+    fn token_metadata_delegate_pack_unpack_roundtrip() {
+        let delegate = TokenMetadataDelegate {
+            account_type: AccountType::Delegate,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(11),
+            delegate: pk(12),
+            authority: pk(13),
+            scope: LOCK_IMAGE | LOCK_DESCRIPTION,
+            bump: Some(254),
+        };
+
+        let mut buf = vec![0u8; TokenMetadataDelegate::LEN];
+        delegate.pack_into_slice(&mut buf);
+        assert_eq!(
+            borsh::to_vec(&delegate).unwrap().len(),
+            TokenMetadataDelegate::LEN
+        );
+        assert_eq!(
+            TokenMetadataDelegate::unpack_from_slice(&buf).unwrap(),
+            delegate
+        );
+
+        // Records written before the bump was stored read as `None`
+        let legacy = &buf[..TokenMetadataDelegate::LEN - 2];
+        assert_eq!(
+            TokenMetadataDelegate::unpack_from_slice(legacy).map(|d| d.bump),
+            Ok(None)
+        );
+
+        assert!(delegate.covers(LOCK_IMAGE));
+        assert!(delegate.covers(LOCK_IMAGE | LOCK_DESCRIPTION));
+        assert!(!delegate.covers(LOCK_IMAGE | LOCK_NAME));
+    }
+
+    #[test]
+    fn token_metadata_multisig_pack_unpack_and_validate() {
+        let multisig = TokenMetadataMultisig {
+            account_type: AccountType::Multisig,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            m: 2,
+            signers: vec![pk(20), pk(21), pk(22)],
+        };
+        assert_eq!(multisig.validate(), Ok(()));
+
+        let mut buf = vec![0xffu8; TokenMetadataMultisig::LEN];
+        multisig.pack_into_slice(&mut buf);
+        assert_eq!(
+            TokenMetadataMultisig::unpack_from_slice(&buf).unwrap(),
+            multisig
+        );
+
+        let full = TokenMetadataMultisig {
+            signers: (0..MAX_MULTISIG_SIGNERS as u8).map(pk).collect(),
+            ..multisig.clone()
+        };
+        assert_eq!(
+            borsh::to_vec(&full).unwrap().len(),
+            TokenMetadataMultisig::LEN
+        );
+
+        for bad in [
+            TokenMetadataMultisig {
+                m: 0,
+                ..multisig.clone()
+            },
+            TokenMetadataMultisig {
+                m: 4,
+                ..multisig.clone()
+            },
+            TokenMetadataMultisig {
+                signers: vec![pk(20), pk(20)],
+                ..multisig.clone()
+            },
+            TokenMetadataMultisig {
+                m: 1,
+                signers: (0..=MAX_MULTISIG_SIGNERS as u8).map(pk).collect(),
+                ..multisig.clone()
+            },
+        ] {
+            assert_eq!(bad.validate(), Err(MetadataError::InvalidMultisig));
+        }
+    }
+
+    #[test]
+    fn sort_attributes_orders_by_key_and_rejects_duplicates() {
+        let mut data = vec![
+            ("website".to_string(), "w".to_string()),
+            ("category".into(), "c".into()),
+            ("twitter".into(), "t".into()),
+        ];
+        sort_attributes(&mut data).unwrap();
+        let keys: Vec<&str> = data.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["category", "twitter", "website"]);
+
+        let mut dup = vec![("a".to_string(), "1".to_string()), ("a".into(), "2".into())];
+        assert_eq!(
+            sort_attributes(&mut dup),
+            Err(MetadataError::DuplicateAttributeKey)
+        );
+    }
+
+    #[test]
+    fn attributes_get_finds_values_by_key() {
+        let mut data = vec![
+            ("website".to_string(), "https://arch.network".to_string()),
+            ("category".into(), "defi".into()),
+        ];
+        sort_attributes(&mut data).unwrap();
+        let attrs = TokenMetadataAttributes {
+            account_type: AccountType::Attributes,
+            version: CURRENT_ACCOUNT_VERSION,
+            is_initialized: true,
+            mint: pk(8),
+            data,
+            revision: 0,
+            last_updated_slot: 0,
+            bump: None,
+        };
+        assert_eq!(attrs.get("category"), Some("defi"));
+        assert_eq!(attrs.get("website"), Some("https://arch.network"));
+        assert_eq!(attrs.get("twitter"), None);
+    }
 }
@@ -1,8 +1,12 @@
 //! Arch Token Metadata – Rust SDK (client-side helpers)
 //!
 //! This crate provides:
-//! - PDA helpers for core metadata and attributes accounts
+//! - PDA helpers for core metadata, attributes, creators, delegate record and extension accounts
+//! - Multisig update authority helpers
+//! - Event decoding for indexers (`decode_events`)
+//! - Off-chain JSON content hash helpers (`sha256_content_uri`, `verify_content_hash`)
 //! - Instruction builders with correct account ordering and client-side validation
+//! - Typed encode/decode helpers for extension payloads (`extensions`)
 //! - Transaction builders for common flows (compose Vec<Instruction>)
 //!
 //! The builders mirror the on-chain program invariants documented in `docs/SECURITY.md`.
@@ -15,17 +19,28 @@ use arch_program::{
     account::AccountMeta, instruction::Instruction, pubkey::Pubkey, system_instruction,
 };
 
-use apl_token;
+use bitcoin::hashes::{sha256, Hash as _};
 
 use arch_token_metadata as program;
+use program::error::MetadataError;
 use program::state::{
-    DESCRIPTION_MAX_LEN, IMAGE_MAX_LEN, MAX_ATTRIBUTES, MAX_KEY_LENGTH, MAX_VALUE_LENGTH,
-    NAME_MAX_LEN, SYMBOL_MAX_LEN,
+    validate_description, validate_image, validate_name, validate_symbol, ContentHashAlgorithm,
+    ContentUri, MAX_ATTRIBUTES, MAX_CREATORS, MAX_CREATOR_SHARE_TOTAL, MAX_EXTENSION_DATA_LEN,
+    MAX_KEY_LENGTH, MAX_MULTISIG_SIGNERS, MAX_VALUE_LENGTH,
+};
+
+pub use program::event::{MetadataEvent, EVENT_VERSION};
+pub use program::extension::ExtensionType;
+pub use program::state::{
+    attributes_account_len, extension_account_len, TokenMetadataRef, TokenMetadataSummary,
 };
 
 // Reader support
 use anyhow::Context as _;
-use program::state::{TokenMetadata, TokenMetadataAttributes};
+use program::state::{
+    TokenMetadata, TokenMetadataAttributes, TokenMetadataCreators, TokenMetadataDelegate,
+    TokenMetadataExtension, TokenMetadataMultisig, TokenMetadataTouch,
+};
 
 /// Thin client for building PDAs and instructions for the Arch Token Metadata program.
 ///
@@ -61,29 +76,1152 @@ impl TokenMetadataClient {
         program::find_attributes_pda_with_program(&self.program_id, mint)
     }
 
-    /// Build a CreateMetadata instruction.
+    /// Derive the touch PDA for a given mint.
+    pub fn touch_pda(&self, mint: &Pubkey) -> Pubkey {
+        let (pda, _bump) = program::find_touch_pda_with_program(&self.program_id, mint);
+        pda
+    }
+
+    /// Derive the touch PDA for a given mint, with the bump.
+    pub fn touch_pda_and_bump(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        program::find_touch_pda_with_program(&self.program_id, mint)
+    }
+
+    /// Derive the delegate record PDA for a given mint and delegate.
+    pub fn delegate_pda(&self, mint: &Pubkey, delegate: &Pubkey) -> Pubkey {
+        let (pda, _bump) =
+            program::find_delegate_pda_with_program(&self.program_id, mint, delegate);
+        pda
+    }
+
+    /// Derive the delegate record PDA for a given mint and delegate, with the bump.
+    pub fn delegate_pda_and_bump(&self, mint: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+        program::find_delegate_pda_with_program(&self.program_id, mint, delegate)
+    }
+
+    /// Derive the creators PDA for a given mint.
+    pub fn creators_pda(&self, mint: &Pubkey) -> Pubkey {
+        let (pda, _bump) = program::find_creators_pda_with_program(&self.program_id, mint);
+        pda
+    }
+
+    /// Derive the creators PDA for a given mint, with the bump.
+    pub fn creators_pda_and_bump(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        program::find_creators_pda_with_program(&self.program_id, mint)
+    }
+
+    /// Derive the extension PDA for a given mint and extension type.
+    pub fn extension_pda(&self, mint: &Pubkey, extension_type: ExtensionType) -> Pubkey {
+        let (pda, _bump) = self.extension_pda_and_bump(mint, extension_type);
+        pda
+    }
+
+    /// Derive the extension PDA for a given mint and extension type, with the bump.
+    pub fn extension_pda_and_bump(
+        &self,
+        mint: &Pubkey,
+        extension_type: ExtensionType,
+    ) -> (Pubkey, u8) {
+        program::find_extension_pda_with_program(&self.program_id, mint, extension_type.id())
+    }
+
+    /// Build a CreateMetadata instruction.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - mint (readonly)
+    /// - metadata_pda (writable)
+    /// - mint_or_freeze_authority (readonly, signer)
+    pub fn create_metadata_ix(&self, params: CreateMetadataParams) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        self.validate_metadata_fields(
+            &params.name,
+            &params.symbol,
+            &params.image,
+            &params.description,
+        )?;
+        if let Some(ref content) = params.uri {
+            self.validate_content_uri(content)?;
+        }
+
+        let data = program::instruction::MetadataInstruction::CreateMetadata {
+            name: params.name,
+            symbol: params.symbol,
+            image: params.image,
+            description: params.description,
+            immutable: params.immutable,
+            uri: params.uri,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(params.mint, false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.mint_or_freeze_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a CreateMetadataWithAttributes instruction: `CreateMetadata` and
+    /// `CreateAttributes` in one step, authorized by a single mint authority check.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - mint (readonly)
+    /// - metadata_pda (writable)
+    /// - mint_or_freeze_authority (readonly, signer)
+    /// - attributes_pda (writable)
+    pub fn create_metadata_with_attributes_ix(
+        &self,
+        params: CreateMetadataParams,
+        attributes: Vec<(String, String)>,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let attributes_pda = self.attributes_pda(&params.mint);
+        self.validate_metadata_fields(
+            &params.name,
+            &params.symbol,
+            &params.image,
+            &params.description,
+        )?;
+        if let Some(ref content) = params.uri {
+            self.validate_content_uri(content)?;
+        }
+        self.validate_attributes(&attributes)?;
+
+        let data = program::instruction::MetadataInstruction::CreateMetadataWithAttributes {
+            name: params.name,
+            symbol: params.symbol,
+            image: params.image,
+            description: params.description,
+            immutable: params.immutable,
+            uri: params.uri,
+            data: attributes,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(params.mint, false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.mint_or_freeze_authority, true),
+                AccountMeta::new(attributes_pda, false),
+            ],
+            data,
+        })
+    }
+
+    /// Build an UpdateMetadata instruction.
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - update_authority (readonly, signer)
+    pub fn update_metadata_ix(&self, params: UpdateMetadataParams) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        self.validate_optional_metadata_fields(
+            params.name.as_ref(),
+            params.symbol.as_ref(),
+            params.image.as_ref(),
+            params.description.as_ref(),
+        )?;
+        if let Some(ref content) = params.uri {
+            self.validate_content_uri(content)?;
+        }
+        let data = program::instruction::MetadataInstruction::UpdateMetadata {
+            name: params.name,
+            symbol: params.symbol,
+            image: params.image,
+            description: params.description,
+            uri: params.uri,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a CreateAttributes instruction.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - mint (readonly)
+    /// - attributes_pda (writable)
+    /// - update_authority (readonly, signer)
+    /// - metadata_pda (readonly)
+    pub fn create_attributes_ix(
+        &self,
+        params: CreateAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let attributes_pda = self.attributes_pda(&params.mint);
+        self.validate_attributes(&params.data)?;
+
+        let data = program::instruction::MetadataInstruction::CreateAttributes {
+            data: params.data,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new_readonly(params.mint, false),
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+            ],
+            data,
+        })
+    }
+
+    /// Build a ReplaceAttributes instruction.
+    ///
+    /// Accounts (strict order):
+    /// - attributes_pda (writable)
+    /// - update_authority (readonly, signer)
+    /// - metadata_pda (readonly)
+    /// - payer (writable, signer): funds growth and receives the rent freed by shrinking
+    /// - system_program (readonly)
+    pub fn replace_attributes_ix(
+        &self,
+        params: ReplaceAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let attributes_pda = self.attributes_pda(&params.mint);
+        self.validate_attributes(&params.data)?;
+
+        let data = program::instruction::MetadataInstruction::ReplaceAttributes {
+            data: params.data,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+            ],
+            data,
+        })
+    }
+
+    /// Build an UpsertAttributes instruction (insert or overwrite individual keys).
+    ///
+    /// Accounts (strict order):
+    /// - attributes_pda (writable)
+    /// - update_authority (readonly, signer)
+    /// - metadata_pda (readonly)
+    /// - payer (writable, signer): funds growth and receives the rent freed by shrinking
+    /// - system_program (readonly)
+    pub fn upsert_attributes_ix(
+        &self,
+        params: UpsertAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let attributes_pda = self.attributes_pda(&params.mint);
+        self.validate_attributes(&params.data)?;
+
+        let data = program::instruction::MetadataInstruction::UpsertAttributes {
+            data: params.data,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+            ],
+            data,
+        })
+    }
+
+    /// Build a RemoveAttributes instruction (remove individual keys; unknown keys are ignored).
+    ///
+    /// Accounts (strict order):
+    /// - attributes_pda (writable)
+    /// - update_authority (readonly, signer)
+    /// - metadata_pda (readonly)
+    /// - payer (writable, signer): receives the rent freed by shrinking
+    /// - system_program (readonly)
+    ///
+    /// Removal only ever shrinks the account, so the payer never pays here; it is passed so
+    /// delegate- and multisig-governed tokens recover the freed rent.
+    pub fn remove_attributes_ix(
+        &self,
+        params: RemoveAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let attributes_pda = self.attributes_pda(&params.mint);
+        self.validate_attribute_keys(&params.keys)?;
+
+        let data = program::instruction::MetadataInstruction::RemoveAttributes {
+            keys: params.keys,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(attributes_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+            ],
+            data,
+        })
+    }
+
+    /// Build an UpdateMetadata instruction signed by a delegate instead of the update authority.
+    ///
+    /// `params.update_authority` is the delegate key; its delegate record is appended so the
+    /// program can check the delegate's scope covers every field being changed.
+    pub fn update_metadata_as_delegate_ix(
+        &self,
+        params: UpdateMetadataParams,
+    ) -> anyhow::Result<Instruction> {
+        let (mint, delegate) = (params.mint, params.update_authority);
+        let ix = self.update_metadata_ix(params)?;
+        Ok(self.with_delegate_record(ix, &mint, &delegate))
+    }
+
+    /// Build a ReplaceAttributes instruction signed by an attributes-scoped delegate.
+    ///
+    /// `params.update_authority` is the delegate key; its delegate record is appended.
+    pub fn replace_attributes_as_delegate_ix(
+        &self,
+        params: ReplaceAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let (mint, delegate) = (params.mint, params.update_authority);
+        let ix = self.replace_attributes_ix(params)?;
+        Ok(self.with_delegate_record(ix, &mint, &delegate))
+    }
+
+    /// Build an UpsertAttributes instruction signed by an attributes-scoped delegate.
+    ///
+    /// `params.update_authority` is the delegate key; its delegate record is appended.
+    pub fn upsert_attributes_as_delegate_ix(
+        &self,
+        params: UpsertAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let (mint, delegate) = (params.mint, params.update_authority);
+        let ix = self.upsert_attributes_ix(params)?;
+        Ok(self.with_delegate_record(ix, &mint, &delegate))
+    }
+
+    /// Build a RemoveAttributes instruction signed by an attributes-scoped delegate.
+    ///
+    /// `params.update_authority` is the delegate key; its delegate record is appended.
+    pub fn remove_attributes_as_delegate_ix(
+        &self,
+        params: RemoveAttributesParams,
+    ) -> anyhow::Result<Instruction> {
+        let (mint, delegate) = (params.mint, params.update_authority);
+        let ix = self.remove_attributes_ix(params)?;
+        Ok(self.with_delegate_record(ix, &mint, &delegate))
+    }
+
+    /// Build an AddDelegate instruction granting `delegate` the right to edit the fields in
+    /// `scope` (see [`lock_fields`] for the bit layout). Re-adding an existing delegate
+    /// replaces its scope.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - metadata_pda (writable): tracks the number of open delegate records
+    /// - delegate_pda (writable)
+    /// - update_authority (readonly, signer)
+    pub fn add_delegate_ix(&self, params: AddDelegateParams) -> anyhow::Result<Instruction> {
+        anyhow::ensure!(params.scope != 0, "delegate scope must be non-zero");
+        anyhow::ensure!(
+            params.scope & !lock_fields::LOCK_ALL == 0,
+            "unknown scope bits: {:#06x}",
+            params.scope & !lock_fields::LOCK_ALL
+        );
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let delegate_pda = self.delegate_pda(&params.mint, &params.delegate);
+        let data = program::instruction::MetadataInstruction::AddDelegate {
+            delegate: params.delegate,
+            scope: params.scope,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(delegate_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a RevokeDelegate instruction closing a delegate record.
+    ///
+    /// Accounts (strict order):
+    /// - delegate_pda (writable)
+    /// - signer (readonly, signer): update authority, or the delegate renouncing its rights
+    /// - metadata_pda (writable)
+    /// - destination (writable): any account for the update authority; a renouncing delegate
+    ///   must pass the update authority
+    pub fn revoke_delegate_ix(&self, params: RevokeDelegateParams) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let delegate_pda = self.delegate_pda(&params.mint, &params.delegate);
+        let data = program::instruction::MetadataInstruction::RevokeDelegate {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(delegate_pda, false),
+                AccountMeta::new_readonly(params.signer, true),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(params.destination, false),
+            ],
+            data,
+        })
+    }
+
+    /// Build a CreateMultisig instruction for an M-of-N multisig usable as an update authority.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - multisig (writable, signer): fresh keypair for the multisig account
+    pub fn create_multisig_ix(&self, params: CreateMultisigParams) -> anyhow::Result<Instruction> {
+        let n = params.signers.len();
+        anyhow::ensure!(
+            n <= MAX_MULTISIG_SIGNERS,
+            "too many multisig signers (max {})",
+            MAX_MULTISIG_SIGNERS
+        );
+        anyhow::ensure!(
+            params.m >= 1 && params.m as usize <= n,
+            "m must be between 1 and the number of signers"
+        );
+        for (i, signer) in params.signers.iter().enumerate() {
+            anyhow::ensure!(
+                !params.signers[..i].contains(signer),
+                "duplicate multisig signer: {}",
+                signer
+            );
+        }
+        let data = program::instruction::MetadataInstruction::CreateMultisig {
+            m: params.m,
+            signers: params.signers,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(params.multisig, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build an AddMultisigSigner instruction.
+    ///
+    /// Accounts (strict order):
+    /// - multisig (writable)
+    /// - member_signers... (readonly, signer): at least `m` current members
+    pub fn add_multisig_signer_ix(
+        &self,
+        params: MultisigSignerParams,
+    ) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::AddMultisigSigner {
+            signer: params.signer,
+        }
+        .pack();
+        Ok(self.multisig_member_ix(params.multisig, &params.member_signers, data))
+    }
+
+    /// Build a RemoveMultisigSigner instruction.
+    ///
+    /// Accounts (strict order):
+    /// - multisig (writable)
+    /// - member_signers... (readonly, signer): at least `m` current members
+    pub fn remove_multisig_signer_ix(
+        &self,
+        params: MultisigSignerParams,
+    ) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::RemoveMultisigSigner {
+            signer: params.signer,
+        }
+        .pack();
+        Ok(self.multisig_member_ix(params.multisig, &params.member_signers, data))
+    }
+
+    /// Build an UpdateMetadata instruction for metadata whose update authority is a multisig.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the member keys
+    /// signing this transaction.
+    pub fn update_metadata_multisig_ix(
+        &self,
+        params: UpdateMetadataParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.update_metadata_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a ReplaceAttributes instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn replace_attributes_multisig_ix(
+        &self,
+        params: ReplaceAttributesParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.replace_attributes_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build an UpsertAttributes instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn upsert_attributes_multisig_ix(
+        &self,
+        params: UpsertAttributesParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.upsert_attributes_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a RemoveAttributes instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn remove_attributes_multisig_ix(
+        &self,
+        params: RemoveAttributesParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.remove_attributes_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a TransferAuthority instruction for a multisig update authority.
+    ///
+    /// `params.current_update_authority` is the multisig account; `signers` are the signing
+    /// members.
+    pub fn transfer_authority_multisig_ix(
+        &self,
+        params: TransferAuthorityParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.current_update_authority;
+        let ix = self.transfer_authority_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a MakeImmutable instruction for a multisig update authority.
+    ///
+    /// `params.current_update_authority` is the multisig account; `signers` are the signing
+    /// members.
+    pub fn make_immutable_multisig_ix(
+        &self,
+        params: MakeImmutableParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.current_update_authority;
+        let ix = self.make_immutable_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a CreateAttributes instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn create_attributes_multisig_ix(
+        &self,
+        params: CreateAttributesParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.create_attributes_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a ProposeAuthority instruction for a multisig update authority.
+    ///
+    /// `params.current_update_authority` is the multisig account; `signers` are the signing
+    /// members.
+    pub fn propose_authority_multisig_ix(
+        &self,
+        params: ProposeAuthorityParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.current_update_authority;
+        let ix = self.propose_authority_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build an AcceptAuthority instruction for a proposed authority that is a multisig.
+    ///
+    /// `params.pending_authority` is the multisig account; `signers` are the signing members.
+    pub fn accept_authority_multisig_ix(
+        &self,
+        params: AcceptAuthorityParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.pending_authority;
+        let ix = self.accept_authority_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a CancelProposal instruction for a multisig update authority.
+    ///
+    /// `params.current_update_authority` is the multisig account; `signers` are the signing
+    /// members.
+    pub fn cancel_proposal_multisig_ix(
+        &self,
+        params: CancelProposalParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.current_update_authority;
+        let ix = self.cancel_proposal_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a LockFields instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn lock_fields_multisig_ix(
+        &self,
+        params: LockFieldsParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.lock_fields_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a SetCollection instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn set_collection_multisig_ix(
+        &self,
+        params: SetCollectionParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.set_collection_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a VerifyCollectionMember instruction for a multisig collection authority.
+    ///
+    /// `params.collection_authority` is the multisig account; `signers` are the signing members.
+    pub fn verify_collection_member_multisig_ix(
+        &self,
+        params: CollectionMemberParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.collection_authority;
+        let ix = self.verify_collection_member_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build an UnverifyCollectionMember instruction for a multisig authority (the collection's
+    /// or the member's own).
+    ///
+    /// `params.collection_authority` is the multisig account; `signers` are the signing members.
+    pub fn unverify_collection_member_multisig_ix(
+        &self,
+        params: CollectionMemberParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.collection_authority;
+        let ix = self.unverify_collection_member_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a SetCreators instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn set_creators_multisig_ix(
+        &self,
+        params: SetCreatorsParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.set_creators_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a RemoveCreator instruction signed by a multisig update authority.
+    ///
+    /// `params.signer` is the multisig account; `signers` are the signing members.
+    pub fn remove_creator_multisig_ix(
+        &self,
+        params: RemoveCreatorParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.signer;
+        let ix = self.remove_creator_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build an AddDelegate instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn add_delegate_multisig_ix(
+        &self,
+        params: AddDelegateParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.add_delegate_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a RevokeDelegate instruction signed by a multisig update authority.
+    ///
+    /// `params.signer` is the multisig account; `signers` are the signing members.
+    pub fn revoke_delegate_multisig_ix(
+        &self,
+        params: RevokeDelegateParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.signer;
+        let ix = self.revoke_delegate_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a CloseAttributes instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn close_attributes_multisig_ix(
+        &self,
+        params: CloseAttributesParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.close_attributes_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a CloseMetadata instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn close_metadata_multisig_ix(
+        &self,
+        params: CloseMetadataParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.close_metadata_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a Touch instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn touch_multisig_ix(
+        &self,
+        params: TouchParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.touch_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a CreateExtension instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn create_extension_multisig_ix(
+        &self,
+        params: CreateExtensionParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.create_extension_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build an UpdateExtension instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members,
+    /// appended after the payer and system program.
+    pub fn update_extension_multisig_ix(
+        &self,
+        params: UpdateExtensionParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.update_extension_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a CloseExtension instruction for a multisig update authority.
+    ///
+    /// `params.update_authority` is the multisig account; `signers` are the signing members.
+    pub fn close_extension_multisig_ix(
+        &self,
+        params: CloseExtensionParams,
+        signers: &[Pubkey],
+    ) -> anyhow::Result<Instruction> {
+        let multisig = params.update_authority;
+        let ix = self.close_extension_ix(params)?;
+        Ok(self.with_multisig_signers(ix, &multisig, signers))
+    }
+
+    /// Build a TransferAuthority instruction.
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - current_update_authority (readonly, signer)
+    pub fn transfer_authority_ix(
+        &self,
+        params: TransferAuthorityParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::TransferAuthority {
+            new_authority: params.new_authority,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.current_update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a ProposeAuthority instruction (first step of a two-step handover).
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - current_update_authority (readonly, signer)
+    pub fn propose_authority_ix(
+        &self,
+        params: ProposeAuthorityParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::ProposeAuthority {
+            new_authority: params.new_authority,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.current_update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build an AcceptAuthority instruction (second step; signed by the proposed authority).
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - pending_authority (readonly, signer)
+    pub fn accept_authority_ix(
+        &self,
+        params: AcceptAuthorityParams,
+    ) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::AcceptAuthority {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.pending_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a CancelProposal instruction that withdraws a pending authority proposal.
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - current_update_authority (readonly, signer)
+    pub fn cancel_proposal_ix(&self, params: CancelProposalParams) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::CancelProposal {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.current_update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a LockFields instruction that permanently locks the fields in `mask`
+    /// (see [`lock_fields`]). Bits already locked stay locked.
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - update_authority (readonly, signer)
+    pub fn lock_fields_ix(&self, params: LockFieldsParams) -> anyhow::Result<Instruction> {
+        anyhow::ensure!(params.mask != 0, "lock mask must be non-zero");
+        anyhow::ensure!(
+            params.mask & !lock_fields::LOCK_ALL == 0,
+            "unknown lock bits: {:#06x}",
+            params.mask & !lock_fields::LOCK_ALL
+        );
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::LockFields {
+            mask: params.mask,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a SetCollection instruction pointing the mint's metadata at `collection_mint`
+    /// (or clearing the reference with `None`). The reference starts unverified.
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - update_authority (readonly, signer)
+    /// - collection_metadata_pda (readonly): only when `collection_mint` is `Some`
+    pub fn set_collection_ix(&self, params: SetCollectionParams) -> anyhow::Result<Instruction> {
+        anyhow::ensure!(
+            params.collection_mint != Some(params.mint),
+            "a mint cannot be its own collection"
+        );
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let mut accounts = vec![
+            AccountMeta::new(metadata_pda, false),
+            AccountMeta::new_readonly(params.update_authority, true),
+        ];
+        if let Some(collection_mint) = params.collection_mint.as_ref() {
+            accounts.push(AccountMeta::new_readonly(
+                self.metadata_pda(collection_mint),
+                false,
+            ));
+        }
+        let data = program::instruction::MetadataInstruction::SetCollection {
+            collection_mint: params.collection_mint,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Build a VerifyCollectionMember instruction.
+    ///
+    /// Accounts (strict order):
+    /// - member_metadata_pda (writable)
+    /// - collection_metadata_pda (readonly)
+    /// - collection_authority (readonly, signer): the collection's update authority
+    pub fn verify_collection_member_ix(
+        &self,
+        params: CollectionMemberParams,
+    ) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::VerifyCollectionMember {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+        Ok(self.collection_member_ix(&params, data))
+    }
+
+    /// Build an UnverifyCollectionMember instruction. Accounts as in
+    /// [`Self::verify_collection_member_ix`], except that `params.collection_authority` may
+    /// also be the member's own update authority.
+    pub fn unverify_collection_member_ix(
+        &self,
+        params: CollectionMemberParams,
+    ) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::UnverifyCollectionMember {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+        Ok(self.collection_member_ix(&params, data))
+    }
+
+    /// Build a SetCreators instruction replacing the creators list. Entries whose address and
+    /// share are unchanged keep their verification; all others must sign again.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - metadata_pda (writable)
+    /// - creators_pda (writable)
+    /// - update_authority (readonly, signer)
+    pub fn set_creators_ix(&self, params: SetCreatorsParams) -> anyhow::Result<Instruction> {
+        anyhow::ensure!(
+            params.creators.len() <= MAX_CREATORS,
+            "too many creators (max {})",
+            MAX_CREATORS
+        );
+        let mut total: u16 = 0;
+        for (i, (address, share)) in params.creators.iter().enumerate() {
+            anyhow::ensure!(
+                !params.creators[..i].iter().any(|(a, _)| a == address),
+                "duplicate creator: {}",
+                address
+            );
+            total += *share as u16;
+        }
+        anyhow::ensure!(
+            total <= MAX_CREATOR_SHARE_TOTAL as u16,
+            "creator shares sum to {} (max {})",
+            total,
+            MAX_CREATOR_SHARE_TOTAL
+        );
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let creators_pda = self.creators_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::SetCreators {
+            creators: params.creators,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(creators_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+            ],
+            data,
+        })
+    }
+
+    /// Build a SignCreator instruction verifying `creator` in the mint's creators list.
+    ///
+    /// Accounts (strict order):
+    /// - creators_pda (writable)
+    /// - creator (readonly, signer)
+    /// - metadata_pda (writable)
+    pub fn sign_creator_ix(&self, params: SignCreatorParams) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::SignCreator {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.creators_pda(&params.mint), false),
+                AccountMeta::new_readonly(params.creator, true),
+                AccountMeta::new(self.metadata_pda(&params.mint), false),
+            ],
+            data,
+        })
+    }
+
+    /// Build a RemoveCreator instruction.
+    ///
+    /// Accounts (strict order):
+    /// - creators_pda (writable)
+    /// - signer (readonly, signer): update authority, or the creator removing itself
+    /// - metadata_pda (writable)
+    pub fn remove_creator_ix(&self, params: RemoveCreatorParams) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::RemoveCreator {
+            creator: params.creator,
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.creators_pda(&params.mint), false),
+                AccountMeta::new_readonly(params.signer, true),
+                AccountMeta::new(self.metadata_pda(&params.mint), false),
+            ],
+            data,
+        })
+    }
+
+    /// Build a CreateExtension instruction. Use [`extensions::TypedExtension::encode`] to
+    /// produce `params.data` for a registered type.
     ///
     /// Accounts (strict order):
     /// - payer (writable, signer)
     /// - system_program (readonly)
-    /// - mint (readonly)
-    /// - metadata_pda (writable)
-    /// - mint_or_freeze_authority (readonly, signer)
-    pub fn create_metadata_ix(&self, params: CreateMetadataParams) -> anyhow::Result<Instruction> {
+    /// - metadata_pda (writable): tracks the number of open extensions
+    /// - extension_pda (writable)
+    /// - update_authority (readonly, signer)
+    pub fn create_extension_ix(
+        &self,
+        params: CreateExtensionParams,
+    ) -> anyhow::Result<Instruction> {
+        self.validate_extension_data(&params.data)?;
         let metadata_pda = self.metadata_pda(&params.mint);
-        self.validate_metadata_fields(
-            &params.name,
-            &params.symbol,
-            &params.image,
-            &params.description,
-        )?;
-
-        let data = program::instruction::MetadataInstruction::CreateMetadata {
-            name: params.name,
-            symbol: params.symbol,
-            image: params.image,
-            description: params.description,
-            immutable: params.immutable,
+        let extension_pda = self.extension_pda(&params.mint, params.extension_type);
+        let data = program::instruction::MetadataInstruction::CreateExtension {
+            extension_type: params.extension_type.id(),
+            data: params.data,
+            expected_revision: params.expected_revision,
         }
         .pack();
 
@@ -92,97 +1230,152 @@ impl TokenMetadataClient {
             accounts: vec![
                 AccountMeta::new(params.payer, true),
                 AccountMeta::new_readonly(Pubkey::system_program(), false),
-                AccountMeta::new_readonly(params.mint, false),
                 AccountMeta::new(metadata_pda, false),
-                AccountMeta::new_readonly(params.mint_or_freeze_authority, true),
+                AccountMeta::new(extension_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
             ],
             data,
         })
     }
 
-    /// Build an UpdateMetadata instruction.
+    /// Build an UpdateExtension instruction replacing the extension's payload.
     ///
     /// Accounts (strict order):
-    /// - metadata_pda (writable)
+    /// - extension_pda (writable)
     /// - update_authority (readonly, signer)
-    pub fn update_metadata_ix(&self, params: UpdateMetadataParams) -> anyhow::Result<Instruction> {
+    /// - metadata_pda (readonly)
+    /// - payer (writable, signer): funds growth and receives the rent freed by shrinking
+    /// - system_program (readonly)
+    ///
+    /// As with the attribute writes, the program only requires the payer and system program
+    /// when the account grows; they are always included here so a shrink refunds the payer.
+    pub fn update_extension_ix(
+        &self,
+        params: UpdateExtensionParams,
+    ) -> anyhow::Result<Instruction> {
+        self.validate_extension_data(&params.data)?;
         let metadata_pda = self.metadata_pda(&params.mint);
-        self.validate_optional_metadata_fields(
-            params.name.as_ref(),
-            params.symbol.as_ref(),
-            params.image.as_ref(),
-            params.description.as_ref(),
-        )?;
-        let data = program::instruction::MetadataInstruction::UpdateMetadata {
-            name: params.name,
-            symbol: params.symbol,
-            image: params.image,
-            description: params.description,
+        let extension_pda = self.extension_pda(&params.mint, params.extension_type);
+        let data = program::instruction::MetadataInstruction::UpdateExtension {
+            data: params.data,
+            expected_revision: params.expected_revision,
         }
         .pack();
 
         Ok(Instruction {
             program_id: self.program_id,
             accounts: vec![
-                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(extension_pda, false),
                 AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
             ],
             data,
         })
     }
 
-    /// Build a CreateAttributes instruction.
+    /// Build a CloseExtension instruction. Lamports are returned to `destination`.
     ///
     /// Accounts (strict order):
-    /// - payer (writable, signer)
-    /// - system_program (readonly)
-    /// - mint (readonly)
-    /// - attributes_pda (writable)
+    /// - extension_pda (writable)
     /// - update_authority (readonly, signer)
+    /// - metadata_pda (writable)
+    /// - destination (writable)
+    pub fn close_extension_ix(&self, params: CloseExtensionParams) -> anyhow::Result<Instruction> {
+        let metadata_pda = self.metadata_pda(&params.mint);
+        let extension_pda = self.extension_pda(&params.mint, params.extension_type);
+        let data = program::instruction::MetadataInstruction::CloseExtension {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(extension_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new(params.destination, false),
+            ],
+            data,
+        })
+    }
+
+    /// Build a read-only GetMetadata instruction. The program returns a borsh
+    /// `TokenMetadataSummary` as return data; decode it with `TokenMetadataSummary::decode`.
+    ///
+    /// Accounts (strict order):
     /// - metadata_pda (readonly)
-    pub fn create_attributes_ix(
-        &self,
-        params: CreateAttributesParams,
-    ) -> anyhow::Result<Instruction> {
+    /// - mint (readonly)
+    pub fn get_metadata_ix(&self, mint: Pubkey) -> anyhow::Result<Instruction> {
+        Ok(program::instruction::get_metadata_instruction(
+            &self.program_id,
+            &self.metadata_pda(&mint),
+            &mint,
+        ))
+    }
+
+    /// Build a MakeImmutable instruction.
+    ///
+    /// Accounts (strict order):
+    /// - metadata_pda (writable)
+    /// - current_update_authority (readonly, signer)
+    pub fn make_immutable_ix(&self, params: MakeImmutableParams) -> anyhow::Result<Instruction> {
         let metadata_pda = self.metadata_pda(&params.mint);
-        let attributes_pda = self.attributes_pda(&params.mint);
-        self.validate_attributes(&params.data)?;
+        let data = program::instruction::MetadataInstruction::MakeImmutable {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(metadata_pda, false),
+                AccountMeta::new_readonly(params.current_update_authority, true),
+            ],
+            data,
+        })
+    }
 
-        let data =
-            program::instruction::MetadataInstruction::CreateAttributes { data: params.data }
-                .pack();
+    /// Build a MigrateAccount instruction that upgrades a legacy (v0) metadata or
+    /// attributes account to the current layout. Permissionless; no-op if already current.
+    ///
+    /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
+    /// - account (writable): metadata or attributes PDA
+    pub fn migrate_account_ix(&self, params: MigrateAccountParams) -> anyhow::Result<Instruction> {
+        let data = program::instruction::MetadataInstruction::MigrateAccount.pack();
 
         Ok(Instruction {
             program_id: self.program_id,
             accounts: vec![
                 AccountMeta::new(params.payer, true),
                 AccountMeta::new_readonly(Pubkey::system_program(), false),
-                AccountMeta::new_readonly(params.mint, false),
-                AccountMeta::new(attributes_pda, false),
-                AccountMeta::new_readonly(params.update_authority, true),
-                AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(params.account, false),
             ],
             data,
         })
     }
 
-    /// Build a ReplaceAttributes instruction.
+    /// Build a CloseAttributes instruction. Lamports are returned to `destination`.
     ///
     /// Accounts (strict order):
     /// - attributes_pda (writable)
     /// - update_authority (readonly, signer)
     /// - metadata_pda (readonly)
-    pub fn replace_attributes_ix(
+    /// - destination (writable)
+    pub fn close_attributes_ix(
         &self,
-        params: ReplaceAttributesParams,
+        params: CloseAttributesParams,
     ) -> anyhow::Result<Instruction> {
         let metadata_pda = self.metadata_pda(&params.mint);
         let attributes_pda = self.attributes_pda(&params.mint);
-        self.validate_attributes(&params.data)?;
-
-        let data =
-            program::instruction::MetadataInstruction::ReplaceAttributes { data: params.data }
-                .pack();
+        let data = program::instruction::MetadataInstruction::CloseAttributes {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
 
         Ok(Instruction {
             program_id: self.program_id,
@@ -190,23 +1383,33 @@ impl TokenMetadataClient {
                 AccountMeta::new(attributes_pda, false),
                 AccountMeta::new_readonly(params.update_authority, true),
                 AccountMeta::new_readonly(metadata_pda, false),
+                AccountMeta::new(params.destination, false),
             ],
             data,
         })
     }
 
-    /// Build a TransferAuthority instruction.
+    /// Build a CloseMetadata instruction. Lamports are returned to `destination`.
+    ///
+    /// The program requires the mint supply to be zero, the attributes PDA to be closed and
+    /// every delegate record and extension to be closed. The touch and creators PDAs, if any,
+    /// are closed along with the metadata.
     ///
     /// Accounts (strict order):
     /// - metadata_pda (writable)
-    /// - current_update_authority (readonly, signer)
-    pub fn transfer_authority_ix(
-        &self,
-        params: TransferAuthorityParams,
-    ) -> anyhow::Result<Instruction> {
+    /// - update_authority (readonly, signer)
+    /// - mint (readonly)
+    /// - attributes_pda (readonly)
+    /// - destination (writable)
+    /// - touch_pda (writable)
+    /// - creators_pda (writable)
+    pub fn close_metadata_ix(&self, params: CloseMetadataParams) -> anyhow::Result<Instruction> {
         let metadata_pda = self.metadata_pda(&params.mint);
-        let data = program::instruction::MetadataInstruction::TransferAuthority {
-            new_authority: params.new_authority,
+        let attributes_pda = self.attributes_pda(&params.mint);
+        let touch_pda = self.touch_pda(&params.mint);
+        let creators_pda = self.creators_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::CloseMetadata {
+            expected_revision: params.expected_revision,
         }
         .pack();
 
@@ -214,26 +1417,42 @@ impl TokenMetadataClient {
             program_id: self.program_id,
             accounts: vec![
                 AccountMeta::new(metadata_pda, false),
-                AccountMeta::new_readonly(params.current_update_authority, true),
+                AccountMeta::new_readonly(params.update_authority, true),
+                AccountMeta::new_readonly(params.mint, false),
+                AccountMeta::new_readonly(attributes_pda, false),
+                AccountMeta::new(params.destination, false),
+                AccountMeta::new(touch_pda, false),
+                AccountMeta::new(creators_pda, false),
             ],
             data,
         })
     }
 
-    /// Build a MakeImmutable instruction.
+    /// Build a Touch instruction that records the current slot in the touch PDA so indexers
+    /// re-read the mint. The payer funds the touch PDA on first use.
     ///
     /// Accounts (strict order):
+    /// - payer (writable, signer)
+    /// - system_program (readonly)
     /// - metadata_pda (writable)
-    /// - current_update_authority (readonly, signer)
-    pub fn make_immutable_ix(&self, params: MakeImmutableParams) -> anyhow::Result<Instruction> {
+    /// - touch_pda (writable)
+    /// - update_authority (readonly, signer)
+    pub fn touch_ix(&self, params: TouchParams) -> anyhow::Result<Instruction> {
         let metadata_pda = self.metadata_pda(&params.mint);
-        let data = program::instruction::MetadataInstruction::MakeImmutable.pack();
+        let touch_pda = self.touch_pda(&params.mint);
+        let data = program::instruction::MetadataInstruction::Touch {
+            expected_revision: params.expected_revision,
+        }
+        .pack();
 
         Ok(Instruction {
             program_id: self.program_id,
             accounts: vec![
+                AccountMeta::new(params.payer, true),
+                AccountMeta::new_readonly(Pubkey::system_program(), false),
                 AccountMeta::new(metadata_pda, false),
-                AccountMeta::new_readonly(params.current_update_authority, true),
+                AccountMeta::new(touch_pda, false),
+                AccountMeta::new_readonly(params.update_authority, true),
             ],
             data,
         })
@@ -313,6 +1532,7 @@ impl TokenMetadataClient {
             image: params.image,
             description: params.description,
             immutable: params.immutable,
+            uri: None,
         })?;
 
         Ok(vec![create_mint_ix, init_mint_ix, create_md_ix])
@@ -329,8 +1549,8 @@ impl TokenMetadataClient {
         Ok(out)
     }
 
-    /// Create mint, initialize, create metadata, and create attributes in one sequence.
-    /// Returns: [create_mint, initialize_mint2, create_metadata, create_attributes].
+    /// Create mint, initialize, and create metadata with attributes in one sequence.
+    /// Returns: [create_mint, initialize_mint2, create_metadata_with_attributes].
     pub fn create_token_with_metadata_and_attributes_tx(
         &self,
         params: TxCreateTokenWithMetadataAndAttributesParams,
@@ -343,30 +1563,22 @@ impl TokenMetadataClient {
             params.decimals,
         )?;
 
-        let create_md_ix = self.create_metadata_ix(CreateMetadataParams {
-            payer: params.payer,
-            mint: params.mint,
-            mint_or_freeze_authority: params.mint_authority,
-            name: params.name,
-            symbol: params.symbol,
-            image: params.image,
-            description: params.description,
-            immutable: params.immutable,
-        })?;
-
-        let create_attrs_ix = self.create_attributes_ix(CreateAttributesParams {
-            payer: params.payer,
-            mint: params.mint,
-            update_authority: params.mint_authority,
-            data: params.attributes,
-        })?;
+        let create_md_ix = self.create_metadata_with_attributes_ix(
+            CreateMetadataParams {
+                payer: params.payer,
+                mint: params.mint,
+                mint_or_freeze_authority: params.mint_authority,
+                name: params.name,
+                symbol: params.symbol,
+                image: params.image,
+                description: params.description,
+                immutable: params.immutable,
+                uri: None,
+            },
+            params.attributes,
+        )?;
 
-        Ok(vec![
-            create_mint_ix,
-            init_mint_ix,
-            create_md_ix,
-            create_attrs_ix,
-        ])
+        Ok(vec![create_mint_ix, init_mint_ix, create_md_ix])
     }
 
     /// Same as `create_token_with_metadata_and_attributes_tx`, with compute-budget instructions.
@@ -407,6 +1619,7 @@ impl TokenMetadataClient {
             image: params.image,
             description: params.description,
             immutable: params.immutable,
+            uri: None,
         })?;
 
         Ok(vec![
@@ -476,6 +1689,7 @@ impl TokenMetadataClient {
             mint: params.mint,
             current_update_authority: params.current_update_authority,
             new_authority: params.new_authority,
+            expected_revision: None,
         })?;
 
         let update_ix = self.update_metadata_ix(UpdateMetadataParams {
@@ -485,6 +1699,8 @@ impl TokenMetadataClient {
             symbol: params.symbol,
             image: params.image,
             description: params.description,
+            uri: None,
+            expected_revision: None,
         })?;
 
         Ok(vec![transfer_ix, update_ix])
@@ -520,6 +1736,37 @@ impl TokenMetadataClient {
         Ok(out)
     }
 
+    /// Close attributes (if present) and metadata in one transaction.
+    pub fn close_metadata_and_attributes_tx(
+        &self,
+        params: CloseMetadataParams,
+        has_attributes: bool,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        let mut out = Vec::with_capacity(2);
+        if has_attributes {
+            out.push(self.close_attributes_ix(CloseAttributesParams {
+                mint: params.mint,
+                update_authority: params.update_authority,
+                destination: params.destination,
+                expected_revision: None,
+            })?);
+        }
+        out.push(self.close_metadata_ix(params)?);
+        Ok(out)
+    }
+
+    /// `close_metadata_and_attributes_tx` with compute-budget instructions prepended.
+    pub fn close_metadata_and_attributes_tx_with_budget(
+        &self,
+        params: CloseMetadataParams,
+        has_attributes: bool,
+        budget: ComputeBudgetOptions,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        let mut out = self.compute_budget_ixs(&budget);
+        out.extend(self.close_metadata_and_attributes_tx(params, has_attributes)?);
+        Ok(out)
+    }
+
     /// Compute budget: set a per-transaction compute unit limit.
     pub fn set_compute_unit_limit_ix(&self, units: u32) -> Instruction {
         ComputeBudgetInstruction::set_compute_unit_limit(units)
@@ -541,6 +1788,74 @@ impl TokenMetadataClient {
         out
     }
 
+    /// Append the delegate record for `delegate` so the program treats the signer as a delegate.
+    fn with_delegate_record(
+        &self,
+        mut ix: Instruction,
+        mint: &Pubkey,
+        delegate: &Pubkey,
+    ) -> Instruction {
+        ix.accounts.push(AccountMeta::new_readonly(
+            self.delegate_pda(mint, delegate),
+            false,
+        ));
+        ix
+    }
+
+    /// Turn the authority meta for `multisig` into a non-signer and append the member signers.
+    fn with_multisig_signers(
+        &self,
+        mut ix: Instruction,
+        multisig: &Pubkey,
+        signers: &[Pubkey],
+    ) -> Instruction {
+        for meta in ix.accounts.iter_mut() {
+            if meta.pubkey == *multisig {
+                meta.is_signer = false;
+            }
+        }
+        ix.accounts.extend(
+            signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true)),
+        );
+        ix
+    }
+
+    fn collection_member_ix(&self, params: &CollectionMemberParams, data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.metadata_pda(&params.mint), false),
+                AccountMeta::new_readonly(self.metadata_pda(&params.collection_mint), false),
+                AccountMeta::new_readonly(params.collection_authority, true),
+            ],
+            data,
+        }
+    }
+
+    fn multisig_member_ix(
+        &self,
+        multisig: Pubkey,
+        member_signers: &[Pubkey],
+        data: Vec<u8>,
+    ) -> Instruction {
+        let mut accounts = vec![AccountMeta::new(multisig, false)];
+        accounts.extend(
+            member_signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true)),
+        );
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Mirrors the program's content policy (`arch_token_metadata::state::validate_*`):
+    /// length limits, non-empty name and symbol, printable ASCII symbols, no control
+    /// characters, and `https`/`ipfs`/`ar`/`data` image URIs.
     fn validate_metadata_fields(
         &self,
         name: &str,
@@ -548,14 +1863,10 @@ impl TokenMetadataClient {
         image: &str,
         description: &str,
     ) -> anyhow::Result<()> {
-        anyhow::ensure!(name.len() <= NAME_MAX_LEN, "name too long");
-        anyhow::ensure!(symbol.len() <= SYMBOL_MAX_LEN, "symbol too long");
-        anyhow::ensure!(image.len() <= IMAGE_MAX_LEN, "image too long");
-        anyhow::ensure!(
-            description.len() <= DESCRIPTION_MAX_LEN,
-            "description too long"
-        );
-        Ok(())
+        check_field("name", validate_name(name))?;
+        check_field("symbol", validate_symbol(symbol))?;
+        check_field("image", validate_image(image))?;
+        check_field("description", validate_description(description))
     }
 
     fn validate_optional_metadata_fields(
@@ -566,22 +1877,42 @@ impl TokenMetadataClient {
         description: Option<&String>,
     ) -> anyhow::Result<()> {
         if let Some(v) = name {
-            anyhow::ensure!(v.len() <= NAME_MAX_LEN, "name too long");
+            check_field("name", validate_name(v))?;
         }
         if let Some(v) = symbol {
-            anyhow::ensure!(v.len() <= SYMBOL_MAX_LEN, "symbol too long");
+            check_field("symbol", validate_symbol(v))?;
         }
         if let Some(v) = image {
-            anyhow::ensure!(v.len() <= IMAGE_MAX_LEN, "image too long");
+            check_field("image", validate_image(v))?;
         }
         if let Some(v) = description {
-            anyhow::ensure!(v.len() <= DESCRIPTION_MAX_LEN, "description too long");
+            check_field("description", validate_description(v))?;
         }
         Ok(())
     }
 
+    fn validate_content_uri(&self, content: &ContentUri) -> anyhow::Result<()> {
+        content.validate().map_err(|err| match err {
+            MetadataError::InvalidInstructionData => {
+                anyhow::anyhow!("content hash does not match its algorithm and uri")
+            }
+            err => anyhow::anyhow!("invalid uri: {err}"),
+        })
+    }
+
+    fn validate_extension_data(&self, data: &[u8]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            data.len() <= MAX_EXTENSION_DATA_LEN,
+            "extension data too long ({} > {})",
+            data.len(),
+            MAX_EXTENSION_DATA_LEN
+        );
+        Ok(())
+    }
+
     fn validate_attributes(&self, data: &[(String, String)]) -> anyhow::Result<()> {
         anyhow::ensure!(data.len() <= MAX_ATTRIBUTES, "too many attributes");
+        let mut seen = std::collections::HashSet::with_capacity(data.len());
         for (k, v) in data.iter() {
             anyhow::ensure!(
                 !k.is_empty() && !v.is_empty(),
@@ -589,22 +1920,103 @@ impl TokenMetadataClient {
             );
             anyhow::ensure!(k.len() <= MAX_KEY_LENGTH, "attribute key too long");
             anyhow::ensure!(v.len() <= MAX_VALUE_LENGTH, "attribute value too long");
+            anyhow::ensure!(seen.insert(k.as_str()), "duplicate attribute key: {k}");
+        }
+        Ok(())
+    }
+
+    fn validate_attribute_keys(&self, keys: &[String]) -> anyhow::Result<()> {
+        anyhow::ensure!(keys.len() <= MAX_ATTRIBUTES, "too many attribute keys");
+        for k in keys.iter() {
+            anyhow::ensure!(!k.is_empty(), "attribute key must be non-empty");
+            anyhow::ensure!(k.len() <= MAX_KEY_LENGTH, "attribute key too long");
         }
         Ok(())
     }
 }
 
-impl Default for TokenMetadataClient {
-    fn default() -> Self {
-        Self {
-            program_id: program::id(),
-        }
-    }
+fn check_field(field: &str, result: Result<(), MetadataError>) -> anyhow::Result<()> {
+    result.map_err(|err| anyhow::anyhow!("invalid {field}: {err}"))
+}
+
+impl Default for TokenMetadataClient {
+    fn default() -> Self {
+        Self {
+            program_id: program::id(),
+        }
+    }
+}
+
+/// Return the canonical default program id for Arch Token Metadata.
+pub fn default_program_id() -> Pubkey {
+    program::id()
+}
+
+/// Build a `ContentUri` committing to `json` (the exact bytes served at `uri`) with SHA-256.
+pub fn sha256_content_uri(uri: impl Into<String>, json: &[u8]) -> ContentUri {
+    ContentUri {
+        uri: uri.into(),
+        content_hash: sha256::Hash::hash(json).to_byte_array(),
+        hash_algorithm: ContentHashAlgorithm::Sha256,
+    }
+}
+
+/// Check JSON bytes fetched from `metadata.uri` against the on-chain content hash.
+///
+/// Fails if the metadata carries no commitment, or if the digest does not match.
+pub fn verify_content_hash(metadata: &TokenMetadata, json: &[u8]) -> anyhow::Result<()> {
+    match metadata.content_hash_algorithm {
+        ContentHashAlgorithm::None => anyhow::bail!("metadata has no content hash commitment"),
+        ContentHashAlgorithm::Sha256 => {
+            let expected = sha256::Hash::from_byte_array(metadata.content_hash);
+            let actual = sha256::Hash::hash(json);
+            anyhow::ensure!(
+                actual == expected,
+                "content hash mismatch for {}: expected {expected}, got {actual}",
+                metadata.uri
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Rent-exempt lamports for a metadata account.
+pub fn metadata_rent() -> u64 {
+    minimum_rent(TokenMetadata::LEN)
+}
+
+/// Rent-exempt lamports for an attributes account holding exactly `data`.
+///
+/// Attributes accounts are sized to their entries, so this is what `CreateAttributes` charges
+/// the payer.
+pub fn attributes_rent(data: &[(String, String)]) -> u64 {
+    minimum_rent(attributes_account_len(data))
+}
+
+/// Rent change of the attributes account when its entries change from `current` to `new`:
+/// positive values are paid by the payer, negative values are refunded to it.
+pub fn attributes_rent_delta(current: &[(String, String)], new: &[(String, String)]) -> i64 {
+    attributes_rent(new) as i64 - attributes_rent(current) as i64
 }
 
-/// Return the canonical default program id for Arch Token Metadata.
-pub fn default_program_id() -> Pubkey {
-    program::id()
+/// Rent-exempt lamports for an extension account holding `data_len` payload bytes.
+pub fn extension_rent(data_len: usize) -> u64 {
+    minimum_rent(extension_account_len(data_len))
+}
+
+/// Decode the events emitted by the token metadata program from a transaction's logs, in
+/// emission order. Every state-changing instruction logs exactly one event; `GetMetadata` and a
+/// no-op `MigrateAccount` log none. Lines that carry no event are skipped; a malformed event or
+/// one with an unknown version is an error rather than silently dropped.
+pub fn decode_events<S: AsRef<str>>(logs: &[S]) -> anyhow::Result<Vec<MetadataEvent>> {
+    let mut out = Vec::new();
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(event) = MetadataEvent::from_log(line) {
+            out.push(event.map_err(|err| anyhow::anyhow!("bad event log {line:?}: {err:?}"))?);
+        }
+    }
+    Ok(out)
 }
 
 // Well-known attribute keys
@@ -620,6 +2032,109 @@ pub mod well_known_attributes {
     pub const TAGS: &str = "tags";
 }
 
+// Per-field lock bits stored in `TokenMetadata::locked_fields`
+pub mod lock_fields {
+    pub use arch_token_metadata::state::{
+        LOCK_ALL, LOCK_ATTRIBUTES, LOCK_DESCRIPTION, LOCK_IMAGE, LOCK_NAME, LOCK_SYMBOL, LOCK_URI,
+    };
+
+    /// Field names paired with their lock bit, in bit order
+    pub const FIELDS: [(&str, u16); 6] = [
+        ("name", LOCK_NAME),
+        ("symbol", LOCK_SYMBOL),
+        ("image", LOCK_IMAGE),
+        ("description", LOCK_DESCRIPTION),
+        ("attributes", LOCK_ATTRIBUTES),
+        ("uri", LOCK_URI),
+    ];
+
+    /// Build a lock mask from field names (e.g. `["name", "symbol"]`).
+    pub fn mask_from_names<S: AsRef<str>>(names: &[S]) -> anyhow::Result<u16> {
+        let mut mask = 0u16;
+        for name in names {
+            let name = name.as_ref();
+            let Some((_, bit)) = FIELDS.iter().find(|(n, _)| *n == name) else {
+                anyhow::bail!("unknown lockable field: {name}");
+            };
+            mask |= bit;
+        }
+        Ok(mask)
+    }
+
+    /// Names of the fields locked in `mask`, in bit order.
+    pub fn names_from_mask(mask: u16) -> Vec<&'static str> {
+        FIELDS
+            .iter()
+            .filter(|(_, bit)| mask & bit != 0)
+            .map(|(n, _)| *n)
+            .collect()
+    }
+}
+
+// Typed payloads for the registered extension types
+pub mod extensions {
+    use super::{ExtensionType, TokenMetadataExtension};
+    use anyhow::Context as _;
+    use arch_program::pubkey::Pubkey;
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    /// A payload type bound to its registered extension type id. The program stores payloads
+    /// as opaque bytes; these helpers fix the borsh encoding shared by writers and readers.
+    pub trait TypedExtension: BorshSerialize + BorshDeserialize {
+        /// Registry entry this payload is stored under
+        const TYPE: ExtensionType;
+
+        /// Encode as the `data` of a CreateExtension or UpdateExtension instruction.
+        fn encode(&self) -> Vec<u8> {
+            borsh::to_vec(self).expect("serialize extension payload")
+        }
+
+        /// Decode a payload previously produced by `encode`; trailing bytes are an error.
+        fn decode(data: &[u8]) -> anyhow::Result<Self> {
+            borsh::from_slice(data).with_context(|| format!("decode {:?} extension", Self::TYPE))
+        }
+
+        /// Decode the payload of an extension account, checking its type id first.
+        fn from_account(account: &TokenMetadataExtension) -> anyhow::Result<Self> {
+            anyhow::ensure!(
+                account.extension_type == Self::TYPE.id(),
+                "extension type mismatch: expected {}, found {}",
+                Self::TYPE.id(),
+                account.extension_type
+            );
+            Self::decode(&account.data)
+        }
+    }
+
+    /// `ExtensionType::Royalty`: secondary sale royalty
+    #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct Royalty {
+        /// Royalty in basis points (1/100 of a percent)
+        pub basis_points: u16,
+        /// Account that receives royalties
+        pub recipient: Pubkey,
+    }
+
+    impl TypedExtension for Royalty {
+        const TYPE: ExtensionType = ExtensionType::Royalty;
+    }
+
+    /// `ExtensionType::Localization`: name and description in one language
+    #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct Localization {
+        /// BCP 47 language tag, e.g. `"fr"` or `"pt-BR"`
+        pub language: String,
+        /// Localized token name
+        pub name: String,
+        /// Localized description
+        pub description: String,
+    }
+
+    impl TypedExtension for Localization {
+        const TYPE: ExtensionType = ExtensionType::Localization;
+    }
+}
+
 // === Reader (async RPC-based helpers) ===
 
 /// Minimal account data used by the reader utilities.
@@ -628,6 +2143,17 @@ pub struct AccountDataLite {
     pub owner: Pubkey,
 }
 
+/// A member's collection reference as returned by [`TokenMetadataReader::get_token_collection`].
+#[derive(Clone, Debug)]
+pub struct TokenCollection {
+    /// Mint of the collection the member points at
+    pub collection_mint: Pubkey,
+    /// True once the collection's update authority has verified the member
+    pub verified: bool,
+    /// The collection's own metadata; `None` if it has since been closed
+    pub collection_metadata: Option<TokenMetadata>,
+}
+
 /// Minimal async RPC trait required by reader utilities. Implemented for arch_sdk client via an adapter.
 #[async_trait::async_trait]
 pub trait AsyncAccountReader: Send + Sync {
@@ -657,10 +2183,34 @@ impl<R: AsyncAccountReader> TokenMetadataReader<R> {
         pda
     }
 
+    fn touch_pda(&self, mint: &Pubkey) -> Pubkey {
+        let (pda, _bump) = program::find_touch_pda_with_program(&self.program_id, mint);
+        pda
+    }
+
+    fn creators_pda(&self, mint: &Pubkey) -> Pubkey {
+        let (pda, _bump) = program::find_creators_pda_with_program(&self.program_id, mint);
+        pda
+    }
+
+    fn delegate_pda(&self, mint: &Pubkey, delegate: &Pubkey) -> Pubkey {
+        let (pda, _bump) =
+            program::find_delegate_pda_with_program(&self.program_id, mint, delegate);
+        pda
+    }
+
+    fn extension_pda(&self, mint: &Pubkey, extension_type: ExtensionType) -> Pubkey {
+        let (pda, _bump) =
+            program::find_extension_pda_with_program(&self.program_id, mint, extension_type.id());
+        pda
+    }
+
     fn is_owner_ok(&self, owner: &Pubkey) -> bool {
         owner == &self.program_id
     }
 
+    /// Fetch core metadata for a mint. Locked fields are reported in `locked_fields`
+    /// (see [`lock_fields::names_from_mask`]).
     pub async fn get_token_metadata(&self, mint: Pubkey) -> anyhow::Result<Option<TokenMetadata>> {
         let pda = self.metadata_pda(&mint);
         let v = self.rpc.get_multiple_accounts(&[pda]).await?.pop().unwrap();
@@ -672,6 +2222,26 @@ impl<R: AsyncAccountReader> TokenMetadataReader<R> {
         Ok(Some(md))
     }
 
+    /// Fetch the collection a mint claims to belong to, with its verification status and the
+    /// collection's own metadata; `None` if the mint has no metadata or no collection.
+    pub async fn get_token_collection(
+        &self,
+        mint: Pubkey,
+    ) -> anyhow::Result<Option<TokenCollection>> {
+        let Some(md) = self.get_token_metadata(mint).await? else {
+            return Ok(None);
+        };
+        let Some(collection) = md.collection else {
+            return Ok(None);
+        };
+        let collection_metadata = self.get_token_metadata(collection.key).await?;
+        Ok(Some(TokenCollection {
+            collection_mint: collection.key,
+            verified: collection.verified,
+            collection_metadata,
+        }))
+    }
+
     pub async fn get_token_metadata_attributes(
         &self,
         mint: Pubkey,
@@ -687,6 +2257,144 @@ impl<R: AsyncAccountReader> TokenMetadataReader<R> {
         Ok(Some(attrs))
     }
 
+    /// Fetch the current revision of a mint's metadata, to pass as `expected_revision` on the
+    /// next write; `None` if the mint has no metadata.
+    pub async fn get_metadata_revision(&self, mint: Pubkey) -> anyhow::Result<Option<u64>> {
+        let pda = self.metadata_pda(&mint);
+        let v = self.rpc.get_multiple_accounts(&[pda]).await?.pop().unwrap();
+        let Some(acc) = v else { return Ok(None) };
+        if !self.is_owner_ok(&acc.owner) {
+            return Ok(None);
+        }
+        // Borrowed view: no need to copy the strings out just to read a counter
+        let view = TokenMetadataRef::new(&acc.data).context("read TokenMetadata")?;
+        Ok(Some(view.revision()))
+    }
+
+    /// Fetch the current revision of a mint's attributes; `None` if it has no attributes.
+    pub async fn get_attributes_revision(&self, mint: Pubkey) -> anyhow::Result<Option<u64>> {
+        Ok(self
+            .get_token_metadata_attributes(mint)
+            .await?
+            .map(|attrs| attrs.revision))
+    }
+
+    /// Fetch the touch record for a mint; `None` if the mint has never been touched.
+    pub async fn get_token_metadata_touch(
+        &self,
+        mint: Pubkey,
+    ) -> anyhow::Result<Option<TokenMetadataTouch>> {
+        let pda = self.touch_pda(&mint);
+        let v = self.rpc.get_multiple_accounts(&[pda]).await?.pop().unwrap();
+        let Some(acc) = v else { return Ok(None) };
+        if !self.is_owner_ok(&acc.owner) {
+            return Ok(None);
+        }
+        let touch = TokenMetadataTouch::unpack_from_slice(&acc.data)
+            .context("unpack TokenMetadataTouch")?;
+        Ok(Some(touch))
+    }
+
+    /// Fetch the creators list for a mint; `None` if no creators were ever set.
+    ///
+    /// Only entries with `verified == true` have been signed by the creator itself.
+    pub async fn get_token_metadata_creators(
+        &self,
+        mint: Pubkey,
+    ) -> anyhow::Result<Option<TokenMetadataCreators>> {
+        let pda = self.creators_pda(&mint);
+        let v = self.rpc.get_multiple_accounts(&[pda]).await?.pop().unwrap();
+        let Some(acc) = v else { return Ok(None) };
+        if !self.is_owner_ok(&acc.owner) {
+            return Ok(None);
+        }
+        let record = TokenMetadataCreators::unpack_from_slice(&acc.data)
+            .context("unpack TokenMetadataCreators")?;
+        if !record.is_initialized {
+            return Ok(None);
+        }
+        Ok(Some(record))
+    }
+
+    /// Fetch a mint's extension account of the given type; `None` if it was never created or
+    /// has been closed.
+    pub async fn get_token_metadata_extension(
+        &self,
+        mint: Pubkey,
+        extension_type: ExtensionType,
+    ) -> anyhow::Result<Option<TokenMetadataExtension>> {
+        let pda = self.extension_pda(&mint, extension_type);
+        let v = self.rpc.get_multiple_accounts(&[pda]).await?.pop().unwrap();
+        let Some(acc) = v else { return Ok(None) };
+        if !self.is_owner_ok(&acc.owner) {
+            return Ok(None);
+        }
+        let extension = TokenMetadataExtension::unpack_from_slice(&acc.data)
+            .context("unpack TokenMetadataExtension")?;
+        if !extension.is_initialized {
+            return Ok(None);
+        }
+        Ok(Some(extension))
+    }
+
+    /// Fetch and decode a mint's extension payload as `T`; `None` if the mint has none.
+    pub async fn get_typed_extension<T: extensions::TypedExtension>(
+        &self,
+        mint: Pubkey,
+    ) -> anyhow::Result<Option<T>> {
+        match self.get_token_metadata_extension(mint, T::TYPE).await? {
+            Some(extension) => Ok(Some(T::from_account(&extension)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the delegate record for a mint and delegate; `None` if none was granted.
+    ///
+    /// A returned record is only honored on-chain while `authority` equals the metadata's
+    /// current update authority.
+    pub async fn get_token_metadata_delegate(
+        &self,
+        mint: Pubkey,
+        delegate: Pubkey,
+    ) -> anyhow::Result<Option<TokenMetadataDelegate>> {
+        let pda = self.delegate_pda(&mint, &delegate);
+        let v = self.rpc.get_multiple_accounts(&[pda]).await?.pop().unwrap();
+        let Some(acc) = v else { return Ok(None) };
+        if !self.is_owner_ok(&acc.owner) {
+            return Ok(None);
+        }
+        let record = TokenMetadataDelegate::unpack_from_slice(&acc.data)
+            .context("unpack TokenMetadataDelegate")?;
+        if !record.is_initialized {
+            return Ok(None);
+        }
+        Ok(Some(record))
+    }
+
+    /// Fetch a multisig account; `None` if it does not exist or is not a multisig.
+    pub async fn get_multisig(
+        &self,
+        multisig: Pubkey,
+    ) -> anyhow::Result<Option<TokenMetadataMultisig>> {
+        let v = self
+            .rpc
+            .get_multiple_accounts(&[multisig])
+            .await?
+            .pop()
+            .unwrap();
+        let Some(acc) = v else { return Ok(None) };
+        if !self.is_owner_ok(&acc.owner) {
+            return Ok(None);
+        }
+        let Ok(ms) = TokenMetadataMultisig::unpack_from_slice(&acc.data) else {
+            return Ok(None);
+        };
+        if !ms.is_initialized {
+            return Ok(None);
+        }
+        Ok(Some(ms))
+    }
+
     pub async fn get_token_details(
         &self,
         mint: Pubkey,
@@ -789,16 +2497,18 @@ pub struct CreateMetadataParams {
     pub mint: Pubkey,
     /// Signer that must match the mint authority, or the freeze authority if mint authority is None
     pub mint_or_freeze_authority: Pubkey,
-    /// Token name (<= NAME_MAX_LEN)
+    /// Token name (non-empty, <= NAME_MAX_LEN)
     pub name: String,
-    /// Token symbol (<= SYMBOL_MAX_LEN)
+    /// Token symbol (printable ASCII without whitespace, <= SYMBOL_MAX_LEN)
     pub symbol: String,
-    /// Image URI (<= IMAGE_MAX_LEN)
+    /// Image URI: empty, or `https://`, `ipfs://`, `ar://` or `data:` (<= IMAGE_MAX_LEN)
     pub image: String,
     /// Description text (<= DESCRIPTION_MAX_LEN)
     pub description: String,
     /// If true, metadata is immutable (no update authority retained)
     pub immutable: bool,
+    /// Optional off-chain JSON URI and content hash (see [`sha256_content_uri`])
+    pub uri: Option<ContentUri>,
 }
 
 /// Parameters for UpdateMetadata instruction.
@@ -808,14 +2518,18 @@ pub struct UpdateMetadataParams {
     pub mint: Pubkey,
     /// Current update authority (must sign)
     pub update_authority: Pubkey,
-    /// Optional new name (<= NAME_MAX_LEN)
+    /// Optional new name (non-empty, <= NAME_MAX_LEN)
     pub name: Option<String>,
-    /// Optional new symbol (<= SYMBOL_MAX_LEN)
+    /// Optional new symbol (printable ASCII without whitespace, <= SYMBOL_MAX_LEN)
     pub symbol: Option<String>,
-    /// Optional new image URI (<= IMAGE_MAX_LEN)
+    /// Optional new image URI, same schemes as on create (<= IMAGE_MAX_LEN)
     pub image: Option<String>,
     /// Optional new description (<= DESCRIPTION_MAX_LEN)
     pub description: Option<String>,
+    /// Optional new off-chain JSON URI and content hash (see [`sha256_content_uri`])
+    pub uri: Option<ContentUri>,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
 }
 
 /// Parameters for CreateAttributes instruction.
@@ -829,6 +2543,8 @@ pub struct CreateAttributesParams {
     pub update_authority: Pubkey,
     /// Attribute key-value pairs; length <= MAX_ATTRIBUTES; each key/value length constrained
     pub data: Vec<(String, String)>,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
 }
 
 /// Parameters for ReplaceAttributes instruction.
@@ -838,8 +2554,42 @@ pub struct ReplaceAttributesParams {
     pub mint: Pubkey,
     /// Current update authority (must sign)
     pub update_authority: Pubkey,
+    /// Funds any growth of the attributes account and receives rent freed by shrinking
+    pub payer: Pubkey,
     /// New full attributes vector to replace the existing one
     pub data: Vec<(String, String)>,
+    /// If set, the program fails with `StaleRevision` unless the attributes are at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for UpsertAttributes instruction.
+#[derive(Clone, Debug)]
+pub struct UpsertAttributesParams {
+    /// Token mint whose attributes are being patched
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Funds any growth of the attributes account and receives rent freed by shrinking
+    pub payer: Pubkey,
+    /// Entries to insert or overwrite by key
+    pub data: Vec<(String, String)>,
+    /// If set, the program fails with `StaleRevision` unless the attributes are at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for RemoveAttributes instruction.
+#[derive(Clone, Debug)]
+pub struct RemoveAttributesParams {
+    /// Token mint whose attributes are being patched
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Receives the rent freed by shrinking the attributes account
+    pub payer: Pubkey,
+    /// Keys to remove
+    pub keys: Vec<String>,
+    /// If set, the program fails with `StaleRevision` unless the attributes are at this revision
+    pub expected_revision: Option<u64>,
 }
 
 /// Parameters for TransferAuthority instruction.
@@ -851,6 +2601,229 @@ pub struct TransferAuthorityParams {
     pub current_update_authority: Pubkey,
     /// New authority to set
     pub new_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for ProposeAuthority instruction.
+#[derive(Clone, Debug)]
+pub struct ProposeAuthorityParams {
+    /// Token mint whose metadata authority is being handed over
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub current_update_authority: Pubkey,
+    /// Proposed new authority (must later sign AcceptAuthority)
+    pub new_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for AcceptAuthority instruction.
+#[derive(Clone, Debug)]
+pub struct AcceptAuthorityParams {
+    /// Token mint whose metadata authority is being handed over
+    pub mint: Pubkey,
+    /// Proposed authority accepting the handover (must sign)
+    pub pending_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for CancelProposal instruction.
+#[derive(Clone, Debug)]
+pub struct CancelProposalParams {
+    /// Token mint whose pending proposal is being cancelled
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub current_update_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for LockFields instruction.
+#[derive(Clone, Debug)]
+pub struct LockFieldsParams {
+    /// Token mint whose metadata fields are being locked
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Bitmask of `lock_fields::LOCK_*` bits to lock
+    pub mask: u16,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for AddDelegate instruction.
+#[derive(Clone, Debug)]
+pub struct AddDelegateParams {
+    /// Payer that funds the delegate record
+    pub payer: Pubkey,
+    /// Token mint the delegation applies to
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Key being granted rights
+    pub delegate: Pubkey,
+    /// Fields the delegate may edit (`lock_fields::LOCK_*` bits)
+    pub scope: u16,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for RevokeDelegate instruction.
+#[derive(Clone, Debug)]
+pub struct RevokeDelegateParams {
+    /// Token mint the delegation applies to
+    pub mint: Pubkey,
+    /// Delegate whose record is being closed
+    pub delegate: Pubkey,
+    /// Update authority or the delegate itself (must sign)
+    pub signer: Pubkey,
+    /// Account that receives the reclaimed lamports; must be the update authority when the
+    /// delegate signs
+    pub destination: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for CreateMultisig instruction.
+#[derive(Clone, Debug)]
+pub struct CreateMultisigParams {
+    /// Payer that funds the multisig account
+    pub payer: Pubkey,
+    /// Fresh keypair for the multisig account (must sign)
+    pub multisig: Pubkey,
+    /// Number of member signatures required
+    pub m: u8,
+    /// Member keys
+    pub signers: Vec<Pubkey>,
+}
+
+/// Parameters for AddMultisigSigner and RemoveMultisigSigner instructions.
+#[derive(Clone, Debug)]
+pub struct MultisigSignerParams {
+    /// Multisig account being changed
+    pub multisig: Pubkey,
+    /// Member key to add or remove
+    pub signer: Pubkey,
+    /// Current members signing the change (at least `m`)
+    pub member_signers: Vec<Pubkey>,
+}
+
+/// Parameters for SetCollection instruction.
+#[derive(Clone, Debug)]
+pub struct SetCollectionParams {
+    /// Member mint whose metadata is being changed
+    pub mint: Pubkey,
+    /// Current update authority of the member (must sign)
+    pub update_authority: Pubkey,
+    /// Collection mint, or `None` to leave the current collection
+    pub collection_mint: Option<Pubkey>,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for VerifyCollectionMember and UnverifyCollectionMember instructions.
+#[derive(Clone, Debug)]
+pub struct CollectionMemberParams {
+    /// Member mint
+    pub mint: Pubkey,
+    /// Collection mint referenced by the member
+    pub collection_mint: Pubkey,
+    /// Update authority of the collection's metadata (must sign). To unverify, the member's own
+    /// update authority also works
+    pub collection_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the member metadata is at this
+    /// revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for SetCreators instruction.
+#[derive(Clone, Debug)]
+pub struct SetCreatorsParams {
+    /// Payer that funds the creators account on first use
+    pub payer: Pubkey,
+    /// Token mint the creators belong to
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// (creator address, share in percent) pairs; shares sum to at most 100
+    pub creators: Vec<(Pubkey, u8)>,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for SignCreator instruction.
+#[derive(Clone, Debug)]
+pub struct SignCreatorParams {
+    /// Token mint the creators belong to
+    pub mint: Pubkey,
+    /// Listed creator verifying its entry (must sign)
+    pub creator: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for RemoveCreator instruction.
+#[derive(Clone, Debug)]
+pub struct RemoveCreatorParams {
+    /// Token mint the creators belong to
+    pub mint: Pubkey,
+    /// Creator being removed
+    pub creator: Pubkey,
+    /// Update authority or the creator itself (must sign)
+    pub signer: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for CreateExtension instruction.
+#[derive(Clone, Debug)]
+pub struct CreateExtensionParams {
+    /// Payer that funds the extension account
+    pub payer: Pubkey,
+    /// Token mint the extension belongs to
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Registered extension type; selects the PDA
+    pub extension_type: ExtensionType,
+    /// Payload, at most `MAX_EXTENSION_DATA_LEN` bytes (see [`extensions::TypedExtension`])
+    pub data: Vec<u8>,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for UpdateExtension instruction.
+#[derive(Clone, Debug)]
+pub struct UpdateExtensionParams {
+    /// Token mint the extension belongs to
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Payer that funds growth of the extension account and receives rent freed by shrinking
+    pub payer: Pubkey,
+    /// Extension type being updated
+    pub extension_type: ExtensionType,
+    /// New payload, at most `MAX_EXTENSION_DATA_LEN` bytes
+    pub data: Vec<u8>,
+    /// If set, the program fails with `StaleRevision` unless the extension is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for CloseExtension instruction.
+#[derive(Clone, Debug)]
+pub struct CloseExtensionParams {
+    /// Token mint the extension belongs to
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Extension type being closed
+    pub extension_type: ExtensionType,
+    /// Account that receives the reclaimed lamports
+    pub destination: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the extension is at this revision
+    pub expected_revision: Option<u64>,
 }
 
 /// Parameters for MakeImmutable instruction.
@@ -860,6 +2833,57 @@ pub struct MakeImmutableParams {
     pub mint: Pubkey,
     /// Current update authority (must sign)
     pub current_update_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for MigrateAccount instruction.
+#[derive(Clone, Debug)]
+pub struct MigrateAccountParams {
+    /// Payer that funds any additional rent for the upgraded layout, or receives the rent
+    /// freed when it is smaller
+    pub payer: Pubkey,
+    /// Metadata or attributes PDA to migrate
+    pub account: Pubkey,
+}
+
+/// Parameters for CloseAttributes instruction.
+#[derive(Clone, Debug)]
+pub struct CloseAttributesParams {
+    /// Token mint whose attributes account is being closed
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Account that receives the reclaimed lamports
+    pub destination: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the attributes are at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for CloseMetadata instruction.
+#[derive(Clone, Debug)]
+pub struct CloseMetadataParams {
+    /// Token mint whose metadata account is being closed (supply must be zero)
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// Account that receives the reclaimed lamports
+    pub destination: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
+}
+
+/// Parameters for Touch instruction.
+#[derive(Clone, Debug)]
+pub struct TouchParams {
+    /// Payer that funds the touch PDA on first use
+    pub payer: Pubkey,
+    /// Token mint to signal
+    pub mint: Pubkey,
+    /// Current update authority (must sign)
+    pub update_authority: Pubkey,
+    /// If set, the program fails with `StaleRevision` unless the metadata is at this revision
+    pub expected_revision: Option<u64>,
 }
 
 /// Parameters for tx_create_token_with_metadata transaction pattern.
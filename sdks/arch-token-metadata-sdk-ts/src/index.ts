@@ -3,6 +3,7 @@
 // - Instruction builders with validation mirroring Rust SDK
 // - Transaction builders (return arrays of instructions)
 
+import { sha256 } from "@noble/hashes/sha256";
 import { findProgramAddress, Pubkey, systemProgram } from "./serde/pubkey.js";
 
 export type AccountMeta = {
@@ -33,10 +34,23 @@ export const NAME_MAX_LEN = 256;
 export const SYMBOL_MAX_LEN = 16;
 export const IMAGE_MAX_LEN = 512;
 export const DESCRIPTION_MAX_LEN = 512;
+export const URI_MAX_LEN = 200;
 export const MAX_KEY_LENGTH = 64;
 export const MAX_VALUE_LENGTH = 240;
 export const MAX_ATTRIBUTES = 32;
 
+// Per-field lock bits (mirrors on-chain `LOCK_*` in state.rs)
+export const LOCK_NAME = 1 << 0;
+export const LOCK_SYMBOL = 1 << 1;
+export const LOCK_IMAGE = 1 << 2;
+export const LOCK_DESCRIPTION = 1 << 3;
+export const LOCK_ATTRIBUTES = 1 << 4;
+export const LOCK_URI = 1 << 5;
+
+// Content hash algorithms (mirrors on-chain `ContentHashAlgorithm`)
+export const CONTENT_HASH_NONE = 0;
+export const CONTENT_HASH_SHA256 = 1;
+
 // Borsh encoding helpers minimal
 // Note: Here we rely on Rust fixtures to cross-check correctness. For now, we use a minimal encoder
 // to build instruction byte arrays in an equivalent way (Variant index + fields serialized).
@@ -61,6 +75,26 @@ function encodeOptionString(s: string | undefined | null): Uint8Array {
   return out;
 }
 
+function encodeOptionContentUri(c: ContentUri | undefined | null): Uint8Array {
+  if (c == null) return new Uint8Array([0]);
+  if (c.contentHash.length !== 32)
+    throw new Error("content hash must be 32 bytes");
+  return concat([
+    new Uint8Array([1]),
+    encodeString(c.uri),
+    c.contentHash,
+    new Uint8Array([c.hashAlgorithm]),
+  ]);
+}
+
+function encodeOptionU64(v: bigint | undefined | null): Uint8Array {
+  if (v == null) return new Uint8Array([0]);
+  const out = new Uint8Array(9);
+  out[0] = 1;
+  new DataView(out.buffer).setBigUint64(1, v, true);
+  return out;
+}
+
 function concat(parts: Uint8Array[]): Uint8Array {
   const total = parts.reduce((n, p) => n + p.length, 0);
   const out = new Uint8Array(total);
@@ -83,6 +117,62 @@ function encodeVecTupleStringString(
   return concat([len, ...rest]);
 }
 
+/**
+ * Serialized size of an attributes account holding exactly `data`, mirroring
+ * `state::attributes_account_len`. Attributes accounts are sized to their entries, so
+ * rent is charged (or refunded) for the difference whenever the entries change.
+ */
+export function attributesAccountLen(data: Array<[string, string]>): number {
+  // header (2) + is_initialized (1) + mint (32) + vec length prefix (4)
+  // + revision (8) + last_updated_slot (8) + bump (1 + 1)
+  const fixed = 2 + 1 + 32 + 4 + 8 + 8 + 2;
+  return data.reduce(
+    (acc, [k, v]) => acc + encodeString(k).length + encodeString(v).length,
+    fixed,
+  );
+}
+
+// Content policy mirrored from the on-chain program (`state::validate_*`)
+export const ALLOWED_URI_SCHEMES = ["https://", "ipfs://", "ar://", "data:"];
+// Unicode Cc, matching Rust's `char::is_control`
+const CONTROL_CHARS = /[\u0000-\u001f\u007f-\u009f]/;
+const DESCRIPTION_CONTROL_CHARS = /[\u0000-\u0008\u000b\u000c\u000e-\u001f\u007f-\u009f]/;
+
+export function validateName(name: string) {
+  if (name.length > NAME_MAX_LEN) throw new Error("name too long");
+  if (name.trim() === "") throw new Error("name is empty");
+  if (CONTROL_CHARS.test(name))
+    throw new Error("name contains a control character");
+}
+
+export function validateSymbol(symbol: string) {
+  if (symbol.length > SYMBOL_MAX_LEN) throw new Error("symbol too long");
+  if (symbol === "") throw new Error("symbol is empty");
+  if (!/^[\x21-\x7e]+$/.test(symbol))
+    throw new Error("symbol must be printable ASCII without whitespace");
+}
+
+export function validateImage(image: string) {
+  if (image.length > IMAGE_MAX_LEN) throw new Error("image too long");
+  validateUri("image", image);
+}
+
+export function validateDescription(description: string) {
+  if (description.length > DESCRIPTION_MAX_LEN)
+    throw new Error("description too long");
+  if (DESCRIPTION_CONTROL_CHARS.test(description))
+    throw new Error("description contains a control character");
+}
+
+function validateUri(field: string, uri: string) {
+  if (uri === "") return;
+  if (CONTROL_CHARS.test(uri))
+    throw new Error(`${field} contains a control character`);
+  const lower = uri.toLowerCase();
+  if (!ALLOWED_URI_SCHEMES.some((scheme) => lower.startsWith(scheme)))
+    throw new Error(`${field} has an unsupported URI scheme`);
+}
+
 // Instruction variant tags must match Rust ordering
 const IX_CREATE_METADATA = 0;
 const IX_UPDATE_METADATA = 1;
@@ -123,11 +213,22 @@ export class TokenMetadataClient {
     image: string,
     description: string,
   ) {
-    if (name.length > NAME_MAX_LEN) throw new Error("name too long");
-    if (symbol.length > SYMBOL_MAX_LEN) throw new Error("symbol too long");
-    if (image.length > IMAGE_MAX_LEN) throw new Error("image too long");
-    if (description.length > DESCRIPTION_MAX_LEN)
-      throw new Error("description too long");
+    validateName(name);
+    validateSymbol(symbol);
+    validateImage(image);
+    validateDescription(description);
+  }
+  private validateContentUri(c: ContentUri | undefined) {
+    if (!c) return;
+    if (c.uri.length > URI_MAX_LEN) throw new Error("uri too long");
+    validateUri("uri", c.uri);
+    const hasHash = c.contentHash.some((b) => b !== 0);
+    const consistent =
+      c.hashAlgorithm === CONTENT_HASH_NONE
+        ? !hasHash
+        : c.hashAlgorithm === CONTENT_HASH_SHA256 && hasHash && c.uri !== "";
+    if (!consistent)
+      throw new Error("content hash does not match its algorithm and uri");
   }
   private validateOptionalMetadataFields(params: {
     name?: string;
@@ -135,23 +236,23 @@ export class TokenMetadataClient {
     image?: string;
     description?: string;
   }) {
-    if (params.name && params.name.length > NAME_MAX_LEN)
-      throw new Error("name too long");
-    if (params.symbol && params.symbol.length > SYMBOL_MAX_LEN)
-      throw new Error("symbol too long");
-    if (params.image && params.image.length > IMAGE_MAX_LEN)
-      throw new Error("image too long");
-    if (params.description && params.description.length > DESCRIPTION_MAX_LEN)
-      throw new Error("description too long");
+    if (params.name !== undefined) validateName(params.name);
+    if (params.symbol !== undefined) validateSymbol(params.symbol);
+    if (params.image !== undefined) validateImage(params.image);
+    if (params.description !== undefined)
+      validateDescription(params.description);
   }
   private validateAttributes(data: Array<[string, string]>) {
     if (data.length > MAX_ATTRIBUTES) throw new Error("too many attributes");
+    const seen = new Set<string>();
     for (const [k, v] of data) {
       if (!k || !v)
         throw new Error("attribute key and value must be non-empty");
       if (k.length > MAX_KEY_LENGTH) throw new Error("attribute key too long");
       if (v.length > MAX_VALUE_LENGTH)
         throw new Error("attribute value too long");
+      if (seen.has(k)) throw new Error(`duplicate attribute key: ${k}`);
+      seen.add(k);
     }
   }
 
@@ -176,6 +277,7 @@ export class TokenMetadataClient {
       params.image,
       params.description,
     );
+    this.validateContentUri(params.uri);
     const variant = new Uint8Array([IX_CREATE_METADATA]);
     const body = concat([
       encodeString(params.name),
@@ -183,6 +285,7 @@ export class TokenMetadataClient {
       encodeString(params.image),
       encodeString(params.description),
       new Uint8Array([params.immutable ? 1 : 0]),
+      encodeOptionContentUri(params.uri),
     ]);
     return {
       programId: this.programId,
@@ -205,12 +308,15 @@ export class TokenMetadataClient {
   updateMetadataIx(params: UpdateMetadataParams): Instruction {
     const metadataPda = this.metadataPda(params.mint);
     this.validateOptionalMetadataFields(params);
+    this.validateContentUri(params.uri);
     const variant = new Uint8Array([IX_UPDATE_METADATA]);
     const body = concat([
       encodeOptionString(params.name),
       encodeOptionString(params.symbol),
       encodeOptionString(params.image),
       encodeOptionString(params.description),
+      encodeOptionContentUri(params.uri),
+      encodeOptionU64(params.expectedRevision),
     ]);
     return {
       programId: this.programId,
@@ -228,7 +334,10 @@ export class TokenMetadataClient {
     const attributesPda = this.attributesPda(params.mint);
     this.validateAttributes(params.data);
     const variant = new Uint8Array([IX_CREATE_ATTRIBUTES]);
-    const body = encodeVecTupleStringString(params.data);
+    const body = concat([
+      encodeVecTupleStringString(params.data),
+      encodeOptionU64(params.expectedRevision),
+    ]);
     return {
       programId: this.programId,
       accounts: [
@@ -249,13 +358,18 @@ export class TokenMetadataClient {
     const attributesPda = this.attributesPda(params.mint);
     this.validateAttributes(params.data);
     const variant = new Uint8Array([IX_REPLACE_ATTRIBUTES]);
-    const body = encodeVecTupleStringString(params.data);
+    const body = concat([
+      encodeVecTupleStringString(params.data),
+      encodeOptionU64(params.expectedRevision),
+    ]);
     return {
       programId: this.programId,
       accounts: [
         { pubkey: attributesPda, isSigner: false, isWritable: true },
         { pubkey: params.updateAuthority, isSigner: true, isWritable: false },
         { pubkey: metadataPda, isSigner: false, isWritable: false },
+        { pubkey: params.payer, isSigner: true, isWritable: true },
+        { pubkey: systemProgram(), isSigner: false, isWritable: false },
       ],
       data: concat([variant, body]),
     };
@@ -265,7 +379,10 @@ export class TokenMetadataClient {
   transferAuthorityIx(params: TransferAuthorityParams): Instruction {
     const metadataPda = this.metadataPda(params.mint);
     const variant = new Uint8Array([IX_TRANSFER_AUTHORITY]);
-    const body = new Uint8Array(params.newAuthority);
+    const body = concat([
+      new Uint8Array(params.newAuthority),
+      encodeOptionU64(params.expectedRevision),
+    ]);
     return {
       programId: this.programId,
       accounts: [
@@ -284,6 +401,7 @@ export class TokenMetadataClient {
   makeImmutableIx(params: MakeImmutableParams): Instruction {
     const metadataPda = this.metadataPda(params.mint);
     const variant = new Uint8Array([IX_MAKE_IMMUTABLE]);
+    const body = encodeOptionU64(params.expectedRevision);
     return {
       programId: this.programId,
       accounts: [
@@ -294,7 +412,7 @@ export class TokenMetadataClient {
           isWritable: false,
         },
       ],
-      data: variant,
+      data: concat([variant, body]),
     };
   }
 
@@ -616,7 +734,29 @@ function decodeOptionPubkey(
   return { value, next };
 }
 
+// Account header mirrored from on-chain program: [account_type: u8, version: u8]
+export const ACCOUNT_TYPE_METADATA = 2;
+export const ACCOUNT_TYPE_ATTRIBUTES = 3;
+export const CURRENT_ACCOUNT_VERSION = 1;
+
+function decodeAccountHeader(
+  data: Uint8Array,
+  expectedType: number,
+): { accountType: number; version: number; next: number } {
+  const accountType = data[0];
+  const version = data[1];
+  if (accountType === 1)
+    throw new Error("legacy v0 account; run MigrateAccount first");
+  if (accountType !== expectedType)
+    throw new Error(`unexpected account type ${accountType}`);
+  if (version !== CURRENT_ACCOUNT_VERSION)
+    throw new Error(`unsupported account version ${version}`);
+  return { accountType, version, next: 2 };
+}
+
 export type DecodedTokenMetadata = {
+  account_type: number;
+  version: number;
   is_initialized: boolean;
   mint: Pubkey;
   name: string;
@@ -624,16 +764,42 @@ export type DecodedTokenMetadata = {
   image: string;
   description: string;
   update_authority?: Pubkey;
+  pending_authority?: Pubkey;
+  locked_fields: number;
+  collection?: DecodedCollection;
+  uri: string;
+  content_hash: Uint8Array;
+  content_hash_algorithm: number;
+  /** Writes so far, counting creation; pass as `expectedRevision` to guard the next write */
+  revision: bigint;
+  last_updated_slot: bigint;
+  /** Canonical PDA bump stored at creation; undefined on accounts that predate it */
+  bump?: number;
+  /** Open delegate records; CloseMetadata requires 0 */
+  delegate_count: number;
+  /** Open extension accounts; CloseMetadata requires 0 */
+  extension_count: number;
+};
+
+export type DecodedCollection = {
+  key: Pubkey;
+  verified: boolean;
 };
 
 export type DecodedTokenMetadataAttributes = {
+  account_type: number;
+  version: number;
   is_initialized: boolean;
   mint: Pubkey;
   data: Array<[string, string]>;
+  revision: bigint;
+  last_updated_slot: bigint;
+  bump?: number;
 };
 
 function decodeTokenMetadata(raw: Uint8Array): DecodedTokenMetadata {
-  let o = 0;
+  const header = decodeAccountHeader(raw, ACCOUNT_TYPE_METADATA);
+  let o = header.next;
   const b1 = decodeBool(raw, o);
   o = b1.next;
   const p1 = decodePubkey(raw, o);
@@ -648,7 +814,51 @@ function decodeTokenMetadata(raw: Uint8Array): DecodedTokenMetadata {
   o = s4.next;
   const opt = decodeOptionPubkey(raw, o);
   o = opt.next;
+  // Appended fields; accounts allocated before they existed end early (read as defaults)
+  const pending =
+    o < raw.length ? decodeOptionPubkey(raw, o) : { value: undefined, next: o };
+  o = pending.next;
+  const lockedFields =
+    o + 2 <= raw.length
+      ? new DataView(raw.buffer, raw.byteOffset, raw.byteLength).getUint16(
+          o,
+          true,
+        )
+      : 0;
+  o += 2;
+  let collection: DecodedCollection | undefined;
+  if (o < raw.length && raw[o] === 1) {
+    const key = decodePubkey(raw, o + 1);
+    const verified = decodeBool(raw, key.next);
+    collection = { key: key.value, verified: verified.value };
+    o = verified.next;
+  } else {
+    o += 1;
+  }
+  let uri = "";
+  let contentHash = new Uint8Array(32);
+  let contentHashAlgorithm = CONTENT_HASH_NONE;
+  if (o + 4 <= raw.length) {
+    const u = decodeString(raw, o);
+    uri = u.value;
+    o = u.next;
+    if (o + 33 <= raw.length) {
+      contentHash = raw.slice(o, o + 32);
+      contentHashAlgorithm = raw[o + 32];
+    }
+    o += 33;
+  }
+  const revision = decodeU64OrZero(raw, o);
+  const lastUpdatedSlot = decodeU64OrZero(raw, o + 8);
+  const bump = decodeOptionU8OrNone(raw, o + 16);
+  // Option<u8>: the tag, plus the value when present
+  o += 16 + (bump === undefined ? 1 : 2);
+  const view = new DataView(raw.buffer, raw.byteOffset, raw.byteLength);
+  const delegateCount = o + 4 <= raw.length ? view.getUint32(o, true) : 0;
+  const extensionCount = o + 6 <= raw.length ? view.getUint16(o + 4, true) : 0;
   return {
+    account_type: header.accountType,
+    version: header.version,
     is_initialized: b1.value,
     mint: p1.value,
     name: s1.value,
@@ -656,9 +866,38 @@ function decodeTokenMetadata(raw: Uint8Array): DecodedTokenMetadata {
     image: s3.value,
     description: s4.value,
     update_authority: opt.value,
+    pending_authority: pending.value,
+    locked_fields: lockedFields,
+    collection,
+    uri,
+    content_hash: contentHash,
+    content_hash_algorithm: contentHashAlgorithm,
+    revision,
+    last_updated_slot: lastUpdatedSlot,
+    bump,
+    delegate_count: delegateCount,
+    extension_count: extensionCount,
   };
 }
 
+/** Read a little-endian u64, or 0 if the account ends before it (allocated before the field) */
+function decodeU64OrZero(raw: Uint8Array, offset: number): bigint {
+  if (offset + 8 > raw.length) return 0n;
+  return new DataView(raw.buffer, raw.byteOffset, raw.byteLength).getBigUint64(
+    offset,
+    true,
+  );
+}
+
+/** Read a borsh `Option<u8>`, or undefined if the account ends before it */
+function decodeOptionU8OrNone(
+  raw: Uint8Array,
+  offset: number,
+): number | undefined {
+  if (offset + 2 > raw.length || raw[offset] !== 1) return undefined;
+  return raw[offset + 1];
+}
+
 function decodeVecTupleStringString(
   raw: Uint8Array,
   offset: number,
@@ -680,14 +919,24 @@ function decodeVecTupleStringString(
 function decodeTokenMetadataAttributes(
   raw: Uint8Array,
 ): DecodedTokenMetadataAttributes {
-  let o = 0;
+  const header = decodeAccountHeader(raw, ACCOUNT_TYPE_ATTRIBUTES);
+  let o = header.next;
   const b1 = decodeBool(raw, o);
   o = b1.next;
   const p1 = decodePubkey(raw, o);
   o = p1.next;
   const vec = decodeVecTupleStringString(raw, o);
   o = vec.next;
-  return { is_initialized: b1.value, mint: p1.value, data: vec.value };
+  return {
+    account_type: header.accountType,
+    version: header.version,
+    is_initialized: b1.value,
+    mint: p1.value,
+    data: vec.value,
+    revision: decodeU64OrZero(raw, o),
+    last_updated_slot: decodeU64OrZero(raw, o + 8),
+    bump: decodeOptionU8OrNone(raw, o + 16),
+  };
 }
 
 export class TokenMetadataReader {
@@ -802,6 +1051,7 @@ export interface CreateMetadataParams {
   image: string;
   description: string;
   immutable: boolean;
+  uri?: ContentUri;
 }
 
 export interface UpdateMetadataParams {
@@ -811,6 +1061,37 @@ export interface UpdateMetadataParams {
   symbol?: string;
   image?: string;
   description?: string;
+  uri?: ContentUri;
+  /** Fail with `StaleRevision` unless the metadata is still at this revision */
+  expectedRevision?: bigint;
+}
+
+/** Off-chain JSON URI with a commitment to its contents. */
+export interface ContentUri {
+  uri: string;
+  contentHash: Uint8Array;
+  hashAlgorithm: number;
+}
+
+/** Build a ContentUri committing to `json` (the exact bytes served at `uri`) with SHA-256. */
+export function sha256ContentUri(uri: string, json: Uint8Array): ContentUri {
+  return {
+    uri,
+    contentHash: sha256(json),
+    hashAlgorithm: CONTENT_HASH_SHA256,
+  };
+}
+
+/** Check JSON bytes fetched from `md.uri` against the on-chain content hash; throws on mismatch. */
+export function verifyContentHash(
+  md: DecodedTokenMetadata,
+  json: Uint8Array,
+): void {
+  if (md.content_hash_algorithm !== CONTENT_HASH_SHA256)
+    throw new Error("metadata has no content hash commitment");
+  const digest = sha256(json);
+  if (!digest.every((b, i) => b === md.content_hash[i]))
+    throw new Error(`content hash mismatch for ${md.uri}`);
 }
 
 export interface CreateAttributesParams {
@@ -818,23 +1099,33 @@ export interface CreateAttributesParams {
   mint: Pubkey;
   updateAuthority: Pubkey;
   data: Array<[string, string]>;
+  /** Fail with `StaleRevision` unless the metadata is still at this revision */
+  expectedRevision?: bigint;
 }
 
 export interface ReplaceAttributesParams {
   mint: Pubkey;
   updateAuthority: Pubkey;
+  /** Funds growth of the attributes account and receives rent freed by shrinking. */
+  payer: Pubkey;
   data: Array<[string, string]>;
+  /** Fail with `StaleRevision` unless the attributes are still at this revision */
+  expectedRevision?: bigint;
 }
 
 export interface TransferAuthorityParams {
   mint: Pubkey;
   currentUpdateAuthority: Pubkey;
   newAuthority: Pubkey;
+  /** Fail with `StaleRevision` unless the metadata is still at this revision */
+  expectedRevision?: bigint;
 }
 
 export interface MakeImmutableParams {
   mint: Pubkey;
   currentUpdateAuthority: Pubkey;
+  /** Fail with `StaleRevision` unless the metadata is still at this revision */
+  expectedRevision?: bigint;
 }
 
 export interface TransferAuthorityThenUpdateParams {
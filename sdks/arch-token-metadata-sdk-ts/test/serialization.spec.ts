@@ -1,6 +1,13 @@
 import { describe, it, expect } from "vitest";
 import { readFileSync } from "node:fs";
-import { TokenMetadataClient, TokenMetadataReader } from "../src/index.js";
+import {
+  attributesAccountLen,
+  CONTENT_HASH_NONE,
+  sha256ContentUri,
+  TokenMetadataClient,
+  TokenMetadataReader,
+  verifyContentHash,
+} from "../src/index.js";
 import { Pubkey, systemProgram } from "../src/serde/pubkey.js";
 
 function hexToBytes(hex: string): Uint8Array {
@@ -71,12 +78,53 @@ describe("instruction serialization matches Rust fixtures", () => {
     const ix = client.replaceAttributesIx({
       mint,
       updateAuthority: auth,
+      payer,
       data: [["a", "1"]],
     });
     const golden = hexToBytes(fixtures.ReplaceAttributes);
     expect(Buffer.from(ix.data)).toEqual(Buffer.from(golden));
   });
 
+  it("rejects duplicate attribute keys", () => {
+    expect(() =>
+      client.replaceAttributesIx({
+        mint,
+        updateAuthority: auth,
+        payer,
+        data: [
+          ["a", "1"],
+          ["a", "2"],
+        ],
+      }),
+    ).toThrow(/duplicate attribute key/);
+  });
+
+  it("enforces the on-chain content policy", () => {
+    const base = {
+      payer,
+      mint,
+      mintOrFreezeAuthority: auth,
+      name: "Name",
+      symbol: "SYM",
+      image: "",
+      description: "line one\nline two",
+      immutable: false,
+    };
+    expect(() => client.createMetadataIx(base)).not.toThrow();
+    expect(() => client.createMetadataIx({ ...base, name: " " })).toThrow(
+      /name is empty/,
+    );
+    expect(() => client.createMetadataIx({ ...base, symbol: "S S" })).toThrow(
+      /printable ASCII/,
+    );
+    expect(() =>
+      client.createMetadataIx({ ...base, image: "http://x/a.png" }),
+    ).toThrow(/unsupported URI scheme/);
+    expect(() =>
+      client.updateMetadataIx({ mint, updateAuthority: auth, symbol: "" }),
+    ).toThrow(/symbol is empty/);
+  });
+
   it("TransferAuthority", () => {
     const newAuth = Buffer.alloc(32, 7) as Pubkey;
     const ix = client.transferAuthorityIx({
@@ -94,6 +142,17 @@ describe("instruction serialization matches Rust fixtures", () => {
     expect(Buffer.from(ix.data)).toEqual(Buffer.from(golden));
   });
 
+  it("appends expected_revision as a borsh Option<u64>", () => {
+    const ix = client.makeImmutableIx({
+      mint,
+      currentUpdateAuthority: auth,
+      expectedRevision: 258n,
+    });
+    expect(Buffer.from(ix.data)).toEqual(
+      Buffer.from([5, 1, 2, 1, 0, 0, 0, 0, 0, 0]),
+    );
+  });
+
   it("ComputeBudget helpers match Rust fixtures", () => {
     const cb = fixtures.ComputeBudget;
     const cbPid = hexToBytes(cb.ProgramId) as Pubkey;
@@ -202,6 +261,30 @@ describe("reader decodes packed account fixtures and supports round-trip re-enco
     expect(details.metadata!.symbol).toBe("SYM");
     expect(details.metadata!.image).toBe("https://i");
     expect(details.metadata!.description).toBe("desc");
+    expect(details.metadata!.uri).toBe("");
+    expect(details.metadata!.content_hash_algorithm).toBe(CONTENT_HASH_NONE);
+    expect(details.metadata!.revision).toBe(0n);
+    expect(details.attributes!.revision).toBe(0n);
+    expect(details.metadata!.bump).toBeUndefined();
+    expect(details.metadata!.delegate_count).toBe(0);
+    expect(details.metadata!.extension_count).toBe(0);
+    expect(() =>
+      verifyContentHash(details.metadata!, new Uint8Array()),
+    ).toThrow();
+
+    // A committed document verifies; any other bytes do not
+    const json = new TextEncoder().encode('{"name":"Name"}');
+    const content = sha256ContentUri("https://example.com/t.json", json);
+    const committed = {
+      ...details.metadata!,
+      uri: content.uri,
+      content_hash: content.contentHash,
+      content_hash_algorithm: content.hashAlgorithm,
+    };
+    expect(() => verifyContentHash(committed, json)).not.toThrow();
+    expect(() =>
+      verifyContentHash(committed, new TextEncoder().encode("{}")),
+    ).toThrow();
 
     // Build instructions from decoded values to ensure stable encoding paths
     const cm = client.createMetadataIx({
@@ -219,9 +302,14 @@ describe("reader decodes packed account fixtures and supports round-trip re-enco
     const ra = client.replaceAttributesIx({
       mint,
       updateAuthority: Buffer.alloc(32) as Pubkey,
+      payer: Buffer.alloc(32, 1) as Pubkey,
       data: details.attributes!.data,
     });
     expect(ra.data[0]).toBe(3); // variant tag sanity
+    // Attributes accounts are sized to their entries
+    expect(attributesAccountLen(details.attributes!.data)).toBe(
+      atPacked.length,
+    );
   });
 
   it("batch decodes metadata and attributes for multiple mints", async () => {
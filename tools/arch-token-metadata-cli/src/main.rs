@@ -1,7 +1,10 @@
 use anyhow::Context as _;
 use arch_sdk::{generate_new_keypair, AsyncArchRpcClient};
+use arch_token_metadata::state::{ContentHashAlgorithm, ContentUri};
 use arch_token_metadata_elf::ARCH_TOKEN_METADATA_ELF;
-use arch_token_metadata_sdk::{TokenMetadataClient, TokenMetadataReader};
+use arch_token_metadata_sdk::{
+    lock_fields, sha256_content_uri, TokenMetadataClient, TokenMetadataReader,
+};
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::{io::Write, str::FromStr};
 use tempfile::NamedTempFile;
@@ -22,6 +25,39 @@ fn parse_hex32(s: &str) -> anyhow::Result<Pubkey> {
     Ok(Pubkey::from_slice(&bytes))
 }
 
+/// Build the `uri` argument for create/update: `--json-file` commits to the file's bytes
+fn parse_content_uri(
+    uri: Option<String>,
+    json_file: Option<std::path::PathBuf>,
+) -> anyhow::Result<Option<ContentUri>> {
+    match (uri, json_file) {
+        (None, None) => Ok(None),
+        (Some(uri), None) => Ok(Some(ContentUri {
+            uri,
+            content_hash: [0u8; 32],
+            hash_algorithm: ContentHashAlgorithm::None,
+        })),
+        (Some(uri), Some(path)) => {
+            let json = std::fs::read(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            Ok(Some(sha256_content_uri(uri, &json)))
+        }
+        (None, Some(_)) => anyhow::bail!("--json-file requires --uri"),
+    }
+}
+
+fn parse_kvs(kvs: &[String]) -> anyhow::Result<Vec<(String, String)>> {
+    let mut out = Vec::with_capacity(kvs.len());
+    for kv in kvs {
+        let Some((k, v)) = kv.split_once('=') else {
+            anyhow::bail!("invalid --kv, expected key=value");
+        };
+        anyhow::ensure!(!k.is_empty() && !v.is_empty(), "empty key or value");
+        out.push((k.to_string(), v.to_string()));
+    }
+    Ok(out)
+}
+
 #[derive(Clone, Copy, Debug, ValueEnum)]
 enum NetworkArg {
     Regtest,
@@ -232,6 +268,14 @@ enum MetadataCmd {
         #[arg(long, default_value_t = false)]
         immutable: bool,
 
+        /// Off-chain JSON URI
+        #[arg(long)]
+        uri: Option<String>,
+
+        /// Local copy of the JSON served at --uri; its SHA-256 is committed on-chain
+        #[arg(long)]
+        json_file: Option<std::path::PathBuf>,
+
         /// Payer signer source
         #[command(flatten)]
         payer: SignerArg,
@@ -280,6 +324,12 @@ enum MetadataCmd {
         /// New description
         #[arg(long)]
         description: Option<String>,
+        /// New off-chain JSON URI (an empty string clears it)
+        #[arg(long)]
+        uri: Option<String>,
+        /// Local copy of the JSON served at --uri; its SHA-256 is committed on-chain
+        #[arg(long)]
+        json_file: Option<std::path::PathBuf>,
         /// Payer signer source
         #[command(flatten)]
         payer: SignerArg,
@@ -346,6 +396,140 @@ enum MetadataCmd {
         #[arg(long)]
         current_update_authority: Option<String>,
     },
+
+    /// Permanently lock individual metadata fields
+    #[command(
+        about = "Permanently lock fields (name, symbol, image, description, attributes, uri)"
+    )]
+    LockFields {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Comma-separated fields to lock, e.g. name,symbol
+        #[arg(long, value_delimiter = ',', required = true)]
+        fields: Vec<String>,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+        /// Update authority signer (defaults to payer)
+        #[arg(long)]
+        update_authority: Option<String>,
+    },
+
+    /// Propose a new update authority (two-step handover)
+    #[command(
+        alias = "auth-propose",
+        about = "Propose a new update authority; it takes effect once the new key accepts"
+    )]
+    ProposeAuthority {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Proposed authority (hex32)
+        #[arg(long)]
+        new_authority: String,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+        /// Current update authority signer (defaults to payer)
+        #[arg(long)]
+        current_update_authority: Option<String>,
+    },
+
+    /// Accept a pending update authority proposal
+    #[command(
+        alias = "auth-accept",
+        about = "Accept a pending update authority proposal (signed by the proposed key)"
+    )]
+    AcceptAuthority {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+        /// Pending authority signer (defaults to payer)
+        #[arg(long)]
+        pending_authority: Option<String>,
+    },
+
+    /// Cancel a pending update authority proposal
+    #[command(
+        alias = "auth-cancel",
+        about = "Cancel a pending update authority proposal"
+    )]
+    CancelProposal {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+        /// Current update authority signer (defaults to payer)
+        #[arg(long)]
+        current_update_authority: Option<String>,
+    },
+
+    /// Migrate legacy accounts to the current layout
+    #[command(
+        alias = "upgrade",
+        about = "Migrate legacy (v0) metadata/attributes accounts to the current layout"
+    )]
+    Migrate {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Also migrate the attributes account
+        #[arg(long, default_value_t = false)]
+        attributes: bool,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+    },
+
+    /// Patch individual attributes
+    #[command(subcommand, about = "Patch individual attributes (set, unset)")]
+    Attrs(AttrsCmd),
+}
+
+#[derive(Subcommand, Debug)]
+enum AttrsCmd {
+    /// Insert or overwrite individual attributes
+    #[command(
+        alias = "upsert",
+        about = "Insert or overwrite individual attributes by key"
+    )]
+    Set {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Repeatable key=value
+        #[arg(long = "kv")]
+        kvs: Vec<String>,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+        /// Update authority signer (defaults to payer)
+        #[arg(long)]
+        update_authority: Option<String>,
+    },
+
+    /// Remove individual attributes
+    #[command(alias = "remove", about = "Remove individual attributes by key")]
+    Unset {
+        /// Mint address
+        #[arg(long)]
+        mint: String,
+        /// Repeatable attribute key
+        #[arg(long = "key")]
+        keys: Vec<String>,
+        /// Payer signer source
+        #[command(flatten)]
+        payer: SignerArg,
+        /// Update authority signer (defaults to payer)
+        #[arg(long)]
+        update_authority: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -395,28 +579,56 @@ async fn main() -> anyhow::Result<()> {
             let (md_opt, at_opt) = reader.get_token_details(mint_pk).await?;
             let md_json = md_opt.as_ref().map(|m| {
                 serde_json::json!({
+                    "version": m.version,
                     "is_initialized": m.is_initialized,
                     "mint": hex::encode(m.mint),
                     "name": m.name,
                     "symbol": m.symbol,
                     "image": m.image,
                     "description": m.description,
-                    "update_authority": m.update_authority.map(|p| hex::encode(p)),
+                    "update_authority": m.update_authority.map(hex::encode),
+                    "pending_authority": m.pending_authority.map(hex::encode),
+                    "locked_fields": lock_fields::names_from_mask(m.locked_fields),
+                    "collection": m.collection.map(|c| serde_json::json!({
+                        "mint": hex::encode(c.key),
+                        "verified": c.verified,
+                    })),
+                    "uri": m.uri,
+                    "content_hash": hex::encode(m.content_hash),
+                    "content_hash_algorithm": format!("{:?}", m.content_hash_algorithm),
+                    "revision": m.revision,
+                    "last_updated_slot": m.last_updated_slot,
                 })
             });
             let attrs_json = at_opt.as_ref().map(|a| {
                 serde_json::json!({
+                    "version": a.version,
                     "is_initialized": a.is_initialized,
                     "mint": hex::encode(a.mint),
                     "data": a.data,
+                    "revision": a.revision,
+                    "last_updated_slot": a.last_updated_slot,
                 })
             });
+            let creators_json = reader.get_token_metadata_creators(mint_pk).await?.map(|c| {
+                c.creators
+                    .iter()
+                    .map(|creator| {
+                        serde_json::json!({
+                            "address": hex::encode(creator.address),
+                            "verified": creator.verified,
+                            "share": creator.share,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
             println!(
                 "{}",
                 serde_json::to_string_pretty(&serde_json::json!({
                     "mint": mint_json,
                     "metadata": md_json,
                     "attributes": attrs_json,
+                    "creators": creators_json,
                 }))?
             );
         }
@@ -432,7 +644,7 @@ async fn main() -> anyhow::Result<()> {
             let auth_kp = if let Some(spec) = mint_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
 
             let auth_pk = pubkey_xonly(&auth_kp);
@@ -524,6 +736,8 @@ async fn main() -> anyhow::Result<()> {
             image,
             description,
             immutable,
+            uri,
+            json_file,
             payer,
             mint_authority,
         }) => {
@@ -534,7 +748,7 @@ async fn main() -> anyhow::Result<()> {
             let auth_kp = if let Some(spec) = mint_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
             let auth_pk = pubkey_xonly(&auth_kp);
             let mint_pk = parse_hex32(&mint)?;
@@ -556,6 +770,7 @@ async fn main() -> anyhow::Result<()> {
                     image,
                     description,
                     immutable,
+                    uri: parse_content_uri(uri, json_file)?,
                 })?,
             );
 
@@ -587,24 +802,13 @@ async fn main() -> anyhow::Result<()> {
             payer,
             update_authority,
         }) => {
-            fn parse_kvs(kvs: &[String]) -> anyhow::Result<Vec<(String, String)>> {
-                let mut out = Vec::with_capacity(kvs.len());
-                for kv in kvs {
-                    let Some((k, v)) = kv.split_once('=') else {
-                        anyhow::bail!("invalid --kv, expected key=value");
-                    };
-                    anyhow::ensure!(!k.is_empty() && !v.is_empty(), "empty key or value");
-                    out.push((k.to_string(), v.to_string()));
-                }
-                Ok(out)
-            }
             let client = TokenMetadataClient::new(args.metadata_program_id);
             let payer_kp = keypair_from_source(&payer.signer)?;
             let payer_pk = pubkey_xonly(&payer_kp);
             let auth_kp = if let Some(spec) = update_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
             let auth_pk = pubkey_xonly(&auth_kp);
             let mint_pk = parse_hex32(&mint)?;
@@ -616,6 +820,7 @@ async fn main() -> anyhow::Result<()> {
                     mint: mint_pk,
                     update_authority: auth_pk,
                     data,
+                    expected_revision: None,
                 })?;
             let recent = Hash::from_str(
                 &AsyncArchRpcClient::new(&args.rpc)
@@ -650,6 +855,8 @@ async fn main() -> anyhow::Result<()> {
             symbol,
             image,
             description,
+            uri,
+            json_file,
             payer,
             update_authority,
         }) => {
@@ -659,7 +866,7 @@ async fn main() -> anyhow::Result<()> {
             let auth_kp = if let Some(spec) = update_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
             let auth_pk = pubkey_xonly(&auth_kp);
             let mint_pk = parse_hex32(&mint)?;
@@ -671,6 +878,8 @@ async fn main() -> anyhow::Result<()> {
                 symbol,
                 image,
                 description,
+                uri: parse_content_uri(uri, json_file)?,
+                expected_revision: None,
             })?;
             let recent = Hash::from_str(
                 &AsyncArchRpcClient::new(&args.rpc)
@@ -705,24 +914,13 @@ async fn main() -> anyhow::Result<()> {
             payer,
             update_authority,
         }) => {
-            fn parse_kvs(kvs: &[String]) -> anyhow::Result<Vec<(String, String)>> {
-                let mut out = Vec::with_capacity(kvs.len());
-                for kv in kvs {
-                    let Some((k, v)) = kv.split_once('=') else {
-                        anyhow::bail!("invalid --kv, expected key=value");
-                    };
-                    anyhow::ensure!(!k.is_empty() && !v.is_empty(), "empty key or value");
-                    out.push((k.to_string(), v.to_string()));
-                }
-                Ok(out)
-            }
             let client = TokenMetadataClient::new(args.metadata_program_id);
             let payer_kp = keypair_from_source(&payer.signer)?;
             let payer_pk = pubkey_xonly(&payer_kp);
             let auth_kp = if let Some(spec) = update_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
             let auth_pk = pubkey_xonly(&auth_kp);
             let mint_pk = parse_hex32(&mint)?;
@@ -732,7 +930,9 @@ async fn main() -> anyhow::Result<()> {
                 client.replace_attributes_ix(arch_token_metadata_sdk::ReplaceAttributesParams {
                     mint: mint_pk,
                     update_authority: auth_pk,
+                    payer: payer_pk,
                     data,
+                    expected_revision: None,
                 })?;
             let recent = Hash::from_str(
                 &AsyncArchRpcClient::new(&args.rpc)
@@ -773,7 +973,7 @@ async fn main() -> anyhow::Result<()> {
             let current_kp = if let Some(spec) = current_update_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
             let current_pk = pubkey_xonly(&current_kp);
             let mint_pk = parse_hex32(&mint)?;
@@ -784,6 +984,7 @@ async fn main() -> anyhow::Result<()> {
                     mint: mint_pk,
                     current_update_authority: current_pk,
                     new_authority: new_pk,
+                    expected_revision: None,
                 })?;
             let recent = Hash::from_str(
                 &AsyncArchRpcClient::new(&args.rpc)
@@ -823,7 +1024,7 @@ async fn main() -> anyhow::Result<()> {
             let current_kp = if let Some(spec) = current_update_authority.as_ref() {
                 keypair_from_source(spec)?
             } else {
-                payer_kp.clone()
+                payer_kp
             };
             let current_pk = pubkey_xonly(&current_kp);
             let mint_pk = parse_hex32(&mint)?;
@@ -831,6 +1032,7 @@ async fn main() -> anyhow::Result<()> {
             let ix = client.make_immutable_ix(arch_token_metadata_sdk::MakeImmutableParams {
                 mint: mint_pk,
                 current_update_authority: current_pk,
+                expected_revision: None,
             })?;
             let recent = Hash::from_str(
                 &AsyncArchRpcClient::new(&args.rpc)
@@ -859,6 +1061,328 @@ async fn main() -> anyhow::Result<()> {
             );
         }
 
+        Commands::Metadata(MetadataCmd::LockFields {
+            mint,
+            fields,
+            payer,
+            update_authority,
+        }) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let ua_kp = if let Some(spec) = update_authority.as_ref() {
+                keypair_from_source(spec)?
+            } else {
+                payer_kp
+            };
+            let ua_pk = pubkey_xonly(&ua_kp);
+            let mint_pk = parse_hex32(&mint)?;
+            let mask = lock_fields::mask_from_names(&fields)?;
+
+            let ix = client.lock_fields_ix(arch_token_metadata_sdk::LockFieldsParams {
+                mint: mint_pk,
+                update_authority: ua_pk,
+                mask,
+                expected_revision: None,
+            })?;
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&[ix], Some(payer_pk), recent),
+                vec![payer_kp, ua_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.lock-fields: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
+        Commands::Metadata(MetadataCmd::ProposeAuthority {
+            mint,
+            new_authority,
+            payer,
+            current_update_authority,
+        }) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let signer_kp = if let Some(spec) = current_update_authority.as_ref() {
+                keypair_from_source(spec)?
+            } else {
+                payer_kp
+            };
+            let signer_pk = pubkey_xonly(&signer_kp);
+            let mint_pk = parse_hex32(&mint)?;
+            let new_pk = parse_hex32(&new_authority)?;
+
+            let ix =
+                client.propose_authority_ix(arch_token_metadata_sdk::ProposeAuthorityParams {
+                    mint: mint_pk,
+                    current_update_authority: signer_pk,
+                    new_authority: new_pk,
+                    expected_revision: None,
+                })?;
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&[ix], Some(payer_pk), recent),
+                vec![payer_kp, signer_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.propose-authority: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
+        Commands::Metadata(MetadataCmd::AcceptAuthority {
+            mint,
+            payer,
+            pending_authority,
+        }) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let signer_kp = if let Some(spec) = pending_authority.as_ref() {
+                keypair_from_source(spec)?
+            } else {
+                payer_kp
+            };
+            let signer_pk = pubkey_xonly(&signer_kp);
+            let mint_pk = parse_hex32(&mint)?;
+
+            let ix =
+                client.accept_authority_ix(arch_token_metadata_sdk::AcceptAuthorityParams {
+                    mint: mint_pk,
+                    pending_authority: signer_pk,
+                    expected_revision: None,
+                })?;
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&[ix], Some(payer_pk), recent),
+                vec![payer_kp, signer_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.accept-authority: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
+        Commands::Metadata(MetadataCmd::CancelProposal {
+            mint,
+            payer,
+            current_update_authority,
+        }) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let signer_kp = if let Some(spec) = current_update_authority.as_ref() {
+                keypair_from_source(spec)?
+            } else {
+                payer_kp
+            };
+            let signer_pk = pubkey_xonly(&signer_kp);
+            let mint_pk = parse_hex32(&mint)?;
+
+            let ix = client.cancel_proposal_ix(arch_token_metadata_sdk::CancelProposalParams {
+                mint: mint_pk,
+                current_update_authority: signer_pk,
+                expected_revision: None,
+            })?;
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&[ix], Some(payer_pk), recent),
+                vec![payer_kp, signer_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.cancel-proposal: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
+        Commands::Metadata(MetadataCmd::Migrate {
+            mint,
+            attributes,
+            payer,
+        }) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let mint_pk = parse_hex32(&mint)?;
+
+            let mut ixs =
+                vec![
+                    client.migrate_account_ix(arch_token_metadata_sdk::MigrateAccountParams {
+                        payer: payer_pk,
+                        account: client.metadata_pda(&mint_pk),
+                    })?,
+                ];
+            if attributes {
+                ixs.push(client.migrate_account_ix(
+                    arch_token_metadata_sdk::MigrateAccountParams {
+                        payer: payer_pk,
+                        account: client.attributes_pda(&mint_pk),
+                    },
+                )?);
+            }
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&ixs, Some(payer_pk), recent),
+                vec![payer_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.migrate: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
+        Commands::Metadata(MetadataCmd::Attrs(AttrsCmd::Set {
+            mint,
+            kvs,
+            payer,
+            update_authority,
+        })) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let auth_kp = if let Some(spec) = update_authority.as_ref() {
+                keypair_from_source(spec)?
+            } else {
+                payer_kp
+            };
+            let auth_pk = pubkey_xonly(&auth_kp);
+            let mint_pk = parse_hex32(&mint)?;
+            let data = parse_kvs(&kvs)?;
+
+            let ix =
+                client.upsert_attributes_ix(arch_token_metadata_sdk::UpsertAttributesParams {
+                    mint: mint_pk,
+                    update_authority: auth_pk,
+                    payer: payer_pk,
+                    data,
+                    expected_revision: None,
+                })?;
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&[ix], Some(payer_pk), recent),
+                vec![payer_kp, auth_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.attrs.set: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
+        Commands::Metadata(MetadataCmd::Attrs(AttrsCmd::Unset {
+            mint,
+            keys,
+            payer,
+            update_authority,
+        })) => {
+            let client = TokenMetadataClient::new(args.metadata_program_id);
+            let payer_kp = keypair_from_source(&payer.signer)?;
+            let payer_pk = pubkey_xonly(&payer_kp);
+            let auth_kp = if let Some(spec) = update_authority.as_ref() {
+                keypair_from_source(spec)?
+            } else {
+                payer_kp
+            };
+            let auth_pk = pubkey_xonly(&auth_kp);
+            let mint_pk = parse_hex32(&mint)?;
+
+            let ix =
+                client.remove_attributes_ix(arch_token_metadata_sdk::RemoveAttributesParams {
+                    mint: mint_pk,
+                    update_authority: auth_pk,
+                    payer: payer_pk,
+                    keys,
+                    expected_revision: None,
+                })?;
+            let recent = Hash::from_str(&rpc.get_best_block_hash().await?)?;
+            let tx = arch_sdk::build_and_sign_transaction(
+                ArchMessage::new(&[ix], Some(payer_pk), recent),
+                vec![payer_kp, auth_kp],
+                args.network.to_bitcoin(),
+            )?;
+            let txid = rpc.send_transaction(tx).await?;
+            let processed = rpc.wait_for_processed_transaction(&txid).await?;
+            eprintln!(
+                "metadata.attrs.unset: txid={} status={:?}",
+                txid, processed.status
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "txid": txid,
+                    "status": format!("{:?}", processed.status),
+                    "logs": processed.logs,
+                }))?
+            );
+        }
+
         Commands::Program(ProgramCmd::Deploy { deployer }) => {
             let mut temp_file = NamedTempFile::new()?;
             temp_file.write_all(ARCH_TOKEN_METADATA_ELF)?;
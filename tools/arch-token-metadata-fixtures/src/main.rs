@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 use anyhow::Context;
 use apl_token;
 use arch_program::program_pack::Pack;
@@ -7,7 +9,11 @@ use arch_program::system_instruction;
 use arch_token_metadata::{
     find_attributes_pda_with_program, find_metadata_pda_with_program, id as program_id_fn,
     instruction::MetadataInstruction,
-    state::{TokenMetadata, TokenMetadataAttributes},
+    state::{
+        attributes_account_len, AccountType, ContentHashAlgorithm, TokenMetadata,
+        TokenMetadataAttributes, CURRENT_ACCOUNT_VERSION, LOCK_DESCRIPTION, LOCK_IMAGE, LOCK_NAME,
+        LOCK_SYMBOL,
+    },
 };
 use serde_json::json;
 
@@ -24,24 +30,121 @@ fn main() -> anyhow::Result<()> {
         image: image.clone(),
         description: description.clone(),
         immutable: false,
+        uri: None,
     };
     let update = MetadataInstruction::UpdateMetadata {
         name: Some("New".into()),
         symbol: None,
         image: None,
         description: None,
+        uri: None,
+        expected_revision: None,
     };
     let create_attrs = MetadataInstruction::CreateAttributes {
         data: vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())],
+        expected_revision: None,
     };
     let replace_attrs = MetadataInstruction::ReplaceAttributes {
         data: vec![("a".into(), "1".into())],
+        expected_revision: None,
     };
     let new_auth = Pubkey::from_slice(&[7u8; 32]);
     let transfer = MetadataInstruction::TransferAuthority {
         new_authority: new_auth,
+        expected_revision: None,
+    };
+    let make_imm = MetadataInstruction::MakeImmutable {
+        expected_revision: None,
+    };
+    let migrate = MetadataInstruction::MigrateAccount;
+    let close_attrs = MetadataInstruction::CloseAttributes {
+        expected_revision: None,
+    };
+    let close_md = MetadataInstruction::CloseMetadata {
+        expected_revision: None,
+    };
+    let upsert_attrs = MetadataInstruction::UpsertAttributes {
+        data: vec![("a".into(), "2".into())],
+        expected_revision: None,
+    };
+    let remove_attrs = MetadataInstruction::RemoveAttributes {
+        keys: vec!["a".into()],
+        expected_revision: None,
+    };
+    let touch = MetadataInstruction::Touch {
+        expected_revision: None,
+    };
+    let propose = MetadataInstruction::ProposeAuthority {
+        new_authority: new_auth,
+        expected_revision: None,
+    };
+    let accept = MetadataInstruction::AcceptAuthority {
+        expected_revision: None,
+    };
+    let cancel = MetadataInstruction::CancelProposal {
+        expected_revision: None,
+    };
+    let lock_fields = MetadataInstruction::LockFields {
+        mask: LOCK_NAME | LOCK_SYMBOL,
+        expected_revision: None,
+    };
+    let add_delegate = MetadataInstruction::AddDelegate {
+        delegate: new_auth,
+        scope: LOCK_IMAGE | LOCK_DESCRIPTION,
+        expected_revision: None,
+    };
+    let revoke_delegate = MetadataInstruction::RevokeDelegate {
+        expected_revision: None,
+    };
+    let create_multisig = MetadataInstruction::CreateMultisig {
+        m: 2,
+        signers: vec![new_auth, Pubkey::from_slice(&[8u8; 32])],
+    };
+    let add_multisig_signer = MetadataInstruction::AddMultisigSigner { signer: new_auth };
+    let remove_multisig_signer = MetadataInstruction::RemoveMultisigSigner { signer: new_auth };
+    let set_collection = MetadataInstruction::SetCollection {
+        collection_mint: Some(new_auth),
+        expected_revision: None,
+    };
+    let verify_collection_member = MetadataInstruction::VerifyCollectionMember {
+        expected_revision: None,
+    };
+    let unverify_collection_member = MetadataInstruction::UnverifyCollectionMember {
+        expected_revision: None,
+    };
+    let set_creators = MetadataInstruction::SetCreators {
+        creators: vec![(new_auth, 100)],
+        expected_revision: None,
+    };
+    let sign_creator = MetadataInstruction::SignCreator {
+        expected_revision: None,
+    };
+    let remove_creator = MetadataInstruction::RemoveCreator {
+        creator: new_auth,
+        expected_revision: None,
+    };
+    let get_metadata = MetadataInstruction::GetMetadata;
+    let create_with_attrs = MetadataInstruction::CreateMetadataWithAttributes {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        image: image.clone(),
+        description: description.clone(),
+        immutable: false,
+        uri: None,
+        data: vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())],
+    };
+    let create_extension = MetadataInstruction::CreateExtension {
+        extension_type: 1,
+        data: vec![1, 2, 3],
+        expected_revision: None,
+    };
+    let update_extension = MetadataInstruction::UpdateExtension {
+        data: vec![4, 5],
+        expected_revision: None,
+    };
+    let close_extension = MetadataInstruction::CloseExtension {
+        expected_revision: None,
     };
-    let make_imm = MetadataInstruction::MakeImmutable;
 
     let program_id = program_id_fn();
     // Two sample mints for PDA fixtures
@@ -89,6 +192,32 @@ fn main() -> anyhow::Result<()> {
         "ReplaceAttributes": hex::encode(replace_attrs.pack()),
         "TransferAuthority": hex::encode(transfer.pack()),
         "MakeImmutable": hex::encode(make_imm.pack()),
+        "MigrateAccount": hex::encode(migrate.pack()),
+        "CloseAttributes": hex::encode(close_attrs.pack()),
+        "CloseMetadata": hex::encode(close_md.pack()),
+        "UpsertAttributes": hex::encode(upsert_attrs.pack()),
+        "RemoveAttributes": hex::encode(remove_attrs.pack()),
+        "Touch": hex::encode(touch.pack()),
+        "ProposeAuthority": hex::encode(propose.pack()),
+        "AcceptAuthority": hex::encode(accept.pack()),
+        "CancelProposal": hex::encode(cancel.pack()),
+        "LockFields": hex::encode(lock_fields.pack()),
+        "AddDelegate": hex::encode(add_delegate.pack()),
+        "RevokeDelegate": hex::encode(revoke_delegate.pack()),
+        "CreateMultisig": hex::encode(create_multisig.pack()),
+        "AddMultisigSigner": hex::encode(add_multisig_signer.pack()),
+        "RemoveMultisigSigner": hex::encode(remove_multisig_signer.pack()),
+        "SetCollection": hex::encode(set_collection.pack()),
+        "VerifyCollectionMember": hex::encode(verify_collection_member.pack()),
+        "UnverifyCollectionMember": hex::encode(unverify_collection_member.pack()),
+        "SetCreators": hex::encode(set_creators.pack()),
+        "SignCreator": hex::encode(sign_creator.pack()),
+        "RemoveCreator": hex::encode(remove_creator.pack()),
+        "GetMetadata": hex::encode(get_metadata.pack()),
+        "CreateMetadataWithAttributes": hex::encode(create_with_attrs.pack()),
+        "CreateExtension": hex::encode(create_extension.pack()),
+        "UpdateExtension": hex::encode(update_extension.pack()),
+        "CloseExtension": hex::encode(close_extension.pack()),
         "SystemProgram": hex::encode(Pubkey::system_program()),
         "ProgramId": hex::encode(program_id),
         "TokenProgramId": hex::encode(token_program_id),
@@ -159,6 +288,8 @@ fn sample_packed_metadata_hex(
     description: &str,
 ) -> anyhow::Result<String> {
     let md = TokenMetadata {
+        account_type: AccountType::Metadata,
+        version: CURRENT_ACCOUNT_VERSION,
         is_initialized: true,
         mint,
         name: name.to_string(),
@@ -166,6 +297,17 @@ fn sample_packed_metadata_hex(
         image: image.to_string(),
         description: description.to_string(),
         update_authority: Some(*update_authority),
+        pending_authority: None,
+        locked_fields: 0,
+        collection: None,
+        uri: String::new(),
+        content_hash: [0u8; 32],
+        content_hash_algorithm: ContentHashAlgorithm::None,
+        revision: 0,
+        last_updated_slot: 0,
+        bump: None,
+        delegate_count: 0,
+        extension_count: 0,
     };
     let mut buf = vec![0u8; TokenMetadata::LEN];
     md.pack_into_slice(&mut buf);
@@ -174,11 +316,17 @@ fn sample_packed_metadata_hex(
 
 fn sample_packed_attributes_hex(mint: Pubkey) -> anyhow::Result<String> {
     let attrs = TokenMetadataAttributes {
+        account_type: AccountType::Attributes,
+        version: CURRENT_ACCOUNT_VERSION,
         is_initialized: true,
         mint,
         data: vec![("k1".into(), "v1".into()), ("k2".into(), "v2".into())],
+        revision: 0,
+        last_updated_slot: 0,
+        bump: None,
     };
-    let mut buf = vec![0u8; TokenMetadataAttributes::LEN];
+    // Attributes accounts are sized to their entries
+    let mut buf = vec![0u8; attributes_account_len(&attrs.data)];
     attrs.pack_into_slice(&mut buf);
     Ok(hex::encode(buf))
 }